@@ -1,7 +1,11 @@
 // Settings components exports
 export { default as OllamaConfig } from './OllamaConfig.vue'
 export { default as RunPodConfig } from './RunPodConfig.vue'
+export { default as OpenAiCompatConfig } from './OpenAiCompatConfig.vue'
+export { default as DeepLConfig } from './DeepLConfig.vue'
+export { default as LibreTranslateConfig } from './LibreTranslateConfig.vue'
 export { default as PauseControls } from './PauseControls.vue'
+export { default as SequentialControls } from './SequentialControls.vue'
 export { default as TranslationLanguages } from './TranslationLanguages.vue'
 export { default as OllamaStatusBadge } from './OllamaStatusBadge.vue'
 export { default as RunPodStatusBadge } from './RunPodStatusBadge.vue'
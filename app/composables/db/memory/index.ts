@@ -0,0 +1,20 @@
+// Translation Memory Database Composables
+// Exports for translation memory storage and retrieval operations
+
+export {
+  normalizeMemorySource,
+  recordTranslationMemory,
+  recordEntryTranslationMemory,
+  getTranslationMemoryEntries,
+  getTranslationMemoryCandidates
+} from './memory'
+
+// Types
+export type {
+  TranslationMemoryEntry,
+  TranslationMemoryLookupRequest,
+  TranslationMemoryLookupResponse
+} from './types'
+
+// Bridge
+export { setupTranslationMemoryBridge } from './memoryBridge'
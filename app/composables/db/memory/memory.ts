@@ -0,0 +1,126 @@
+// Translation Memory Operations
+// Remembers the translations applied to entries and retrieves them for the backend
+
+import { executeQuery, executeStatement } from '../useDatabase'
+import { executeDBOperation } from '../useDBOperation'
+import type { DBOperationResult } from '../project/types'
+import type { TranslationMemoryEntry } from './types'
+
+// SQLite limits the number of bound parameters per statement
+const SOURCES_PER_QUERY = 200
+
+// Most recent candidates scored for a fuzzy lookup
+const MAX_FUZZY_CANDIDATES = 500
+
+type MemoryMatchRow = Pick<TranslationMemoryEntry, 'normalized_source' | 'source_text' | 'translated_text'>
+
+/**
+ * Key of a source text in the translation memory
+ * Source texts are stored already prepared for translation: only stray whitespace is normalized,
+ * line breaks are kept.
+ * Must stay in sync with normalize_memory_source in src-tauri/src/translation/memory.rs
+ */
+export function normalizeMemorySource(text: string): string {
+  return text
+    .replace(/\r\n/g, '\n')
+    .split('\n')
+    .map(line => line.split(/[ \t]+/).filter(word => word.length > 0).join(' '))
+    .join('\n')
+    .replace(/^\n+|\n+$/g, '')
+}
+
+/**
+ * Remember the translation of a source text for a language pair
+ * The last translation applied wins, manual corrections included.
+ */
+export async function recordTranslationMemory(
+  sourceText: string,
+  translatedText: string,
+  sourceLanguage: string,
+  targetLanguage: string
+): Promise<DBOperationResult> {
+  return executeDBOperation(async () => {
+    const normalizedSource = normalizeMemorySource(sourceText)
+    if (!normalizedSource || !translatedText.trim()) {
+      return
+    }
+
+    await executeStatement(
+      `INSERT INTO translation_memory
+         (normalized_source, source_text, translated_text, source_language, target_language, source_length)
+       VALUES (?, ?, ?, ?, ?, ?)
+       ON CONFLICT(normalized_source, source_language, target_language) DO UPDATE SET
+         source_text = excluded.source_text,
+         translated_text = excluded.translated_text,
+         usage_count = usage_count + 1,
+         updated_at = CURRENT_TIMESTAMP`,
+      // Array.from counts characters like SQLite LENGTH() and the backend
+      [normalizedSource, sourceText, translatedText, sourceLanguage, targetLanguage, Array.from(normalizedSource).length]
+    )
+  }, 'recording translation memory')
+}
+
+/**
+ * Remember the translation applied to a text entry
+ */
+export async function recordEntryTranslationMemory(
+  textId: number,
+  translatedText: string,
+  sourceLanguage: string,
+  targetLanguage: string
+): Promise<DBOperationResult> {
+  const rows = await executeQuery<{ source_text: string }>(
+    'SELECT source_text FROM translation_entries WHERE id = ?',
+    [textId]
+  ).catch(() => [])
+  const entry = rows[0]
+  if (!entry) {
+    return { success: false, error: `Text entry ${textId} not found` }
+  }
+  return recordTranslationMemory(entry.source_text, translatedText, sourceLanguage, targetLanguage)
+}
+
+/**
+ * Get the entries stored for exactly these normalized source texts
+ */
+export async function getTranslationMemoryEntries(
+  normalizedSources: string[],
+  sourceLanguage: string,
+  targetLanguage: string
+): Promise<DBOperationResult<MemoryMatchRow[]>> {
+  return executeDBOperation(async () => {
+    const entries: MemoryMatchRow[] = []
+    for (let i = 0; i < normalizedSources.length; i += SOURCES_PER_QUERY) {
+      const chunk = normalizedSources.slice(i, i + SOURCES_PER_QUERY)
+      const rows = await executeQuery<MemoryMatchRow>(
+        `SELECT normalized_source, source_text, translated_text FROM translation_memory
+         WHERE source_language = ? AND target_language = ?
+         AND normalized_source IN (${chunk.map(() => '?').join(', ')})`,
+        [sourceLanguage, targetLanguage, ...chunk]
+      )
+      entries.push(...rows)
+    }
+    return entries
+  }, 'getting translation memory entries')
+}
+
+/**
+ * Get the fuzzy match candidates of a lookup: entries whose source length is within the bounds
+ * The backend scores them; the most recently used ones are sent first.
+ */
+export async function getTranslationMemoryCandidates(
+  sourceLanguage: string,
+  targetLanguage: string,
+  minLength: number,
+  maxLength: number
+): Promise<DBOperationResult<MemoryMatchRow[]>> {
+  return executeDBOperation(async () => {
+    return executeQuery<MemoryMatchRow>(
+      `SELECT normalized_source, source_text, translated_text FROM translation_memory
+       WHERE source_language = ? AND target_language = ? AND source_length BETWEEN ? AND ?
+       ORDER BY updated_at DESC
+       LIMIT ?`,
+      [sourceLanguage, targetLanguage, minLength, maxLength, MAX_FUZZY_CANDIDATES]
+    )
+  }, 'getting translation memory candidates')
+}
@@ -0,0 +1,50 @@
+// Translation Memory Bridge - Frontend event listener for backend translation memory lookup
+// Listens to "translation-memory-lookup-request" events from Rust backend
+// Responds with "translation-memory-lookup-response" containing the matching entries
+
+import { listen, emit } from '@tauri-apps/api/event'
+import { getTranslationMemoryCandidates, getTranslationMemoryEntries } from './memory'
+import type { TranslationMemoryLookupRequest, TranslationMemoryLookupResponse } from './types'
+
+/**
+ * Setup event listener for translation memory lookup requests from backend
+ * Exact lookups send the normalized sources, fuzzy lookups the length bounds of their candidates
+ */
+export async function setupTranslationMemoryBridge(): Promise<() => void> {
+  const unlisten = await listen<TranslationMemoryLookupRequest>(
+    'translation-memory-lookup-request',
+    async (event) => {
+      const { request_id, source_language, target_language, normalized_sources, min_length, max_length } = event.payload
+
+      try {
+        const result = min_length !== undefined && max_length !== undefined
+          ? await getTranslationMemoryCandidates(source_language, target_language, min_length, max_length)
+          : await getTranslationMemoryEntries(normalized_sources, source_language, target_language)
+
+        const response: TranslationMemoryLookupResponse = {
+          request_id,
+          success: result.success,
+          data: result.success ? result.data : undefined,
+          error: result.success ? undefined : result.error
+        }
+
+        await emit('translation-memory-lookup-response', response)
+      } catch (error) {
+        console.error('[TranslationMemoryBridge] Error processing translation-memory-lookup-request:', error)
+
+        const errorResponse: TranslationMemoryLookupResponse = {
+          request_id,
+          success: false,
+          data: undefined,
+          error: error instanceof Error ? error.message : String(error)
+        }
+
+        await emit('translation-memory-lookup-response', errorResponse)
+      }
+    }
+  )
+
+  console.log('[TranslationMemoryBridge] Event listener setup complete')
+
+  return unlisten
+}
@@ -0,0 +1,32 @@
+// Translation Memory Types
+// Translations remembered by normalized source text and language pair
+
+export interface TranslationMemoryEntry {
+  id: number
+  normalized_source: string
+  source_text: string
+  translated_text: string
+  source_language: string
+  target_language: string
+  source_length: number
+  usage_count: number
+  created_at?: string
+  updated_at?: string
+}
+
+// Event payload types for translation memory bridge communication
+export interface TranslationMemoryLookupRequest {
+  request_id: string
+  source_language: string
+  target_language: string
+  normalized_sources: string[]
+  min_length?: number  // With max_length: fuzzy lookup, every entry whose source length is within the bounds
+  max_length?: number
+}
+
+export interface TranslationMemoryLookupResponse {
+  request_id: string
+  success: boolean
+  data?: Array<Pick<TranslationMemoryEntry, 'normalized_source' | 'source_text' | 'translated_text'>>
+  error?: string
+}
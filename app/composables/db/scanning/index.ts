@@ -9,7 +9,8 @@ import type {
   FileValidationResult,
   GameFile,
   TranslationEntry,
-  TextEntry
+  TextEntry,
+  IgnoreRules
 } from '~/types/scanning-commands'
 
 /**
@@ -127,7 +128,15 @@ export function isScanFailed(progress: ScanProgress): boolean {
 
 /**
  * Extract texts directly from a game folder
+ * Texts that don't need translation (HP, identifiers, ignore rules) are marked Ignored
  */
-export async function extractTextsFromFolder(folderPath: string): Promise<TextEntry[]> {
-  return await invoke('extract_texts_from_folder', { folderPath })
+export async function extractTextsFromFolder(
+  folderPath: string,
+  options: { targetLanguage?: string; ignoreRules?: IgnoreRules } = {}
+): Promise<TextEntry[]> {
+  return await invoke('extract_texts_from_folder', {
+    folderPath,
+    targetLanguage: options.targetLanguage,
+    ignoreRules: options.ignoreRules
+  })
 }
@@ -44,13 +44,23 @@ export {
   pauseTranslationSession,
   resumeTranslationSession,
   stopTranslationSession,
-  getProjectTranslationSessions,
+  requeueTranslationErrors,
   translateSingleText,
   getTranslationSuggestions,
   updateTextWithTranslation,
   bulkUpdateTranslations
 } from './translation'
 
+// Session persistence operations
+export {
+  saveTranslationSession,
+  checkpointTranslationSession,
+  getTranslationSession,
+  getProjectTranslationSessions,
+  getTranslationSessionQueue,
+  deleteTranslationSession
+} from './sessions'
+
 // Types
 export type {
   DBTextEntry,
@@ -72,3 +82,8 @@ export type {
   SingleTranslationResult,
   StartTranslationRequest
 } from './translation'
+
+export type {
+  PersistedTranslationSession,
+  PersistedSessionSettings
+} from './sessions'
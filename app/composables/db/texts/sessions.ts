@@ -0,0 +1,240 @@
+// Translation Session Persistence
+// Sequential sessions only live in the backend memory: they are checkpointed here while
+// their progress is monitored, so a session cut short by closing the app can be resumed
+
+import { executeStatement, executeQuery } from '../useDatabase'
+import { executeTextOperation } from '../useDBOperation'
+import { useSettings } from '../../useTauriSetting'
+import type { TextOperationResult } from './types'
+import type {
+  StartTranslationRequest,
+  TranslationProgress,
+  TranslationSession,
+  TranslationText
+} from './translation'
+
+// Options a session was started with, reused when it is resumed
+export type PersistedSessionSettings = Omit<StartTranslationRequest, 'projectId' | 'texts' | 'startFrom'>
+
+export interface PersistedTranslationSession {
+  id: string
+  project_id: number
+  provider: string
+  status: TranslationSession['status']
+  settings: PersistedSessionSettings
+  total_count: number
+  processed_count: number
+  error_count: number
+  created_at: string
+  updated_at: string
+}
+
+// SQLite limits the number of bound parameters per statement
+const ROWS_PER_STATEMENT = 200
+
+let interruptedSessionsMarked: Promise<void> | null = null
+
+/**
+ * Mark the sessions left unfinished by a previous run of the app as interrupted
+ * Backend sessions do not survive a restart: whatever was still running, paused, in error
+ * or completed with failed entries when the app closed can only be resumed from its checkpoint.
+ * Runs once, before the first session operation of this run.
+ */
+function ensureInterruptedSessionsMarked(): Promise<void> {
+  if (!interruptedSessionsMarked) {
+    interruptedSessionsMarked = executeStatement(
+      `UPDATE translation_sessions SET status = 'interrupted', updated_at = ?
+       WHERE status IN ('running', 'paused', 'error') OR (status = 'completed' AND error_count > 0)`,
+      [new Date().toISOString()]
+    )
+      .then(result => {
+        if (result.rowsAffected > 0) {
+          console.log(`💾 ${result.rowsAffected} session(s) de traduction interrompue(s) détectée(s)`)
+        }
+      })
+      .catch(error => {
+        interruptedSessionsMarked = null
+        throw error
+      })
+  }
+  return interruptedSessionsMarked
+}
+
+async function forEachChunk<T>(items: T[], operation: (chunk: T[]) => Promise<unknown>) {
+  for (let i = 0; i < items.length; i += ROWS_PER_STATEMENT) {
+    await operation(items.slice(i, i + ROWS_PER_STATEMENT))
+  }
+}
+
+function parseSession(row: Omit<PersistedTranslationSession, 'settings'> & { settings: string }): PersistedTranslationSession {
+  let settings: PersistedSessionSettings = {}
+  try {
+    settings = JSON.parse(row.settings)
+  } catch {
+    console.warn(`⚠️ Paramètres illisibles pour la session ${row.id}, valeurs par défaut utilisées`)
+  }
+  return { ...row, settings }
+}
+
+/**
+ * Persist a session that was just started, with its queue of entries
+ */
+export async function saveTranslationSession(
+  sessionId: string,
+  request: StartTranslationRequest
+): Promise<TextOperationResult> {
+  return executeTextOperation(async () => {
+    await ensureInterruptedSessionsMarked()
+
+    const { provider } = await useSettings().loadSettings()
+    const { projectId, texts, startFrom, ...settings } = request
+
+    // The backend starts at `startFrom`, earlier entries are not part of the queue
+    const startIndex = startFrom !== undefined ? Math.max(texts.findIndex(text => text.id === startFrom), 0) : 0
+    const queue = texts.slice(startIndex)
+
+    await executeStatement(
+      `INSERT INTO translation_sessions (id, project_id, provider, status, settings, total_count)
+       VALUES (?, ?, ?, 'running', ?, ?)`,
+      [sessionId, projectId, provider, JSON.stringify(settings), queue.length]
+    )
+
+    await forEachChunk(queue.map((text, position) => ({ id: text.id, position })), async chunk => {
+      const placeholders = chunk.map(() => '(?, ?, ?)').join(', ')
+      const params = chunk.flatMap(entry => [sessionId, entry.id, entry.position])
+      await executeStatement(
+        `INSERT OR IGNORE INTO translation_session_entries (session_id, entry_id, position) VALUES ${placeholders}`,
+        params
+      )
+    })
+  }, 'saving translation session')
+}
+
+/**
+ * Checkpoint a session from a progress report of the backend
+ * Successful translations are drained by the backend, so each report only carries the new ones;
+ * errors are cumulative.
+ */
+export async function checkpointTranslationSession(
+  sessionId: string,
+  progress: TranslationProgress
+): Promise<TextOperationResult> {
+  return executeTextOperation(async () => {
+    await ensureInterruptedSessionsMarked()
+
+    await executeStatement(
+      `UPDATE translation_sessions
+       SET status = ?, processed_count = ?, error_count = ?, updated_at = ?
+       WHERE id = ?`,
+      [progress.status, progress.processed_count, progress.errors.length, new Date().toISOString(), sessionId]
+    )
+
+    const doneIds = progress.successful_translations.map(translation => translation.entry_id)
+    await forEachChunk(doneIds, async chunk => {
+      await executeStatement(
+        `UPDATE translation_session_entries
+         SET state = 'done', error_message = NULL, error_kind = NULL
+         WHERE session_id = ? AND entry_id IN (${chunk.map(() => '?').join(', ')})`,
+        [sessionId, ...chunk]
+      )
+    })
+
+    for (const error of progress.errors) {
+      await executeStatement(
+        `UPDATE translation_session_entries
+         SET state = 'error', error_message = ?, error_kind = ?
+         WHERE session_id = ? AND entry_id = ? AND state != 'done'`,
+        [error.error_message, error.kind ?? null, sessionId, error.entry_id]
+      )
+    }
+  }, 'checkpointing translation session')
+}
+
+/**
+ * Get a persisted session
+ */
+export async function getTranslationSession(
+  sessionId: string
+): Promise<TextOperationResult<PersistedTranslationSession | null>> {
+  return executeTextOperation(async () => {
+    await ensureInterruptedSessionsMarked()
+
+    const rows = await executeQuery<Omit<PersistedTranslationSession, 'settings'> & { settings: string }>(
+      'SELECT * FROM translation_sessions WHERE id = ?',
+      [sessionId]
+    )
+    return rows[0] ? parseSession(rows[0]) : null
+  }, 'getting translation session')
+}
+
+/**
+ * Get the interrupted sessions of a project, most recent first
+ */
+export async function getProjectTranslationSessions(
+  projectId: number
+): Promise<TextOperationResult<TranslationSession[]>> {
+  return executeTextOperation(async () => {
+    await ensureInterruptedSessionsMarked()
+
+    const rows = await executeQuery<Omit<PersistedTranslationSession, 'settings'> & { settings: string }>(
+      `SELECT * FROM translation_sessions
+       WHERE project_id = ? AND status = 'interrupted'
+       ORDER BY updated_at DESC`,
+      [projectId]
+    )
+
+    return rows.map(row => ({
+      session_id: row.id,
+      project_id: row.project_id,
+      current_entry: undefined,
+      processed_count: row.processed_count,
+      total_count: row.total_count,
+      status: 'interrupted' as const,
+      estimated_time_remaining: undefined,
+      error_count: row.error_count
+    }))
+  }, 'getting project translation sessions')
+}
+
+/**
+ * Get the entries of a session that are not translated yet, in queue order
+ * Failed entries are included: resuming a session gives them another chance.
+ */
+export async function getTranslationSessionQueue(
+  sessionId: string
+): Promise<TextOperationResult<TranslationText[]>> {
+  return executeTextOperation(async () => {
+    const rows = await executeQuery<{
+      id: number
+      source_text: string
+      text_type: string | null
+      location: string | null
+    }>(
+      `SELECT te.id, te.source_text, te.text_type, te.location
+       FROM translation_session_entries se
+       JOIN translation_entries te ON te.id = se.entry_id
+       WHERE se.session_id = ? AND se.state != 'done'
+       ORDER BY se.position`,
+      [sessionId]
+    )
+
+    return rows.map(row => ({
+      id: row.id,
+      sourceText: row.source_text,
+      textType: row.text_type ?? undefined,
+      location: row.location ?? undefined
+    }))
+  }, 'getting translation session queue')
+}
+
+/**
+ * Delete a persisted session and its queue
+ */
+export async function deleteTranslationSession(
+  sessionId: string
+): Promise<TextOperationResult> {
+  return executeTextOperation(async () => {
+    await executeStatement('DELETE FROM translation_session_entries WHERE session_id = ?', [sessionId])
+    await executeStatement('DELETE FROM translation_sessions WHERE id = ?', [sessionId])
+  }, 'deleting translation session')
+}
@@ -1,22 +1,36 @@
 // Translation Operations
-// Handles translation-related database operations and Ollama/RunPod integration
+// Handles translation-related database operations and provider integration (Ollama, RunPod, OpenAI-compatible, DeepL, LibreTranslate)
 
 import type {
   TextOperationResult,
   BulkTextOperationResult
 } from './types'
 import { invokeTauri, invokeTauriVoid } from '../useTauriInvoke'
-import { useSettings } from '../../useTauriSetting'
+import { getProviderModel, useSettings } from '../../useTauriSetting'
+import type {
+  DeepLSettings,
+  LibreTranslateSettings,
+  OpenAiCompatSettings
+} from '../../useTauriSetting'
 
 /**
  * Helper function to get provider settings
  */
-async function getProviderSettings(): Promise<{ provider: string; pod_id?: string }> {
+async function getProviderSettings(): Promise<{
+  provider: string
+  pod_id?: string
+  openai_compat?: OpenAiCompatSettings
+  deepl?: DeepLSettings
+  libretranslate?: LibreTranslateSettings
+}> {
   const settings = useSettings()
   const userSettings = await settings.loadSettings()
   return {
     provider: userSettings.provider,
-    pod_id: userSettings.provider === 'runpod' ? userSettings.runpod.pod_id : undefined
+    pod_id: userSettings.provider === 'runpod' ? userSettings.runpod.pod_id : undefined,
+    openai_compat: userSettings.provider === 'openai_compat' ? userSettings.openai_compat : undefined,
+    deepl: userSettings.provider === 'deepl' ? userSettings.deepl : undefined,
+    libretranslate: userSettings.provider === 'libretranslate' ? userSettings.libretranslate : undefined
   }
 }
 
@@ -27,7 +41,7 @@ export interface TranslationSession {
   current_entry?: number
   processed_count: number
   total_count: number
-  status: 'idle' | 'running' | 'paused' | 'completed' | 'error'
+  status: 'idle' | 'running' | 'paused' | 'completed' | 'error' | 'interrupted' // interrupted: persisted, not running since the app restarted
   estimated_time_remaining?: number
   error_count: number
 }
@@ -44,11 +58,25 @@ export interface TranslationProgress {
     entry_id: number
     error_message: string
     timestamp: string
+    kind?: 'transient' | 'rate_limited' | 'fatal_config' | 'bad_output'
+    attempts?: number
   }>
   successful_translations: Array<{
     entry_id: number
     translated_text: string
     timestamp: string
+    over_budget?: BudgetViolation | null
+  }>
+  // Measured over the running time of the session, pauses excluded
+  throughput?: {
+    entries_per_minute: number
+    chars_per_minute: number
+  } | null
+  latency_by_model?: Array<{
+    model: string
+    samples: number
+    p50_ms: number
+    p95_ms: number
   }>
 }
 
@@ -56,7 +84,7 @@ export interface TranslationProgress {
 export interface TranslationSuggestion {
   suggestion: string
   confidence: number
-  source: 'ollama' | 'glossary' | 'similar'
+  source: 'ollama' | 'glossary' | 'similar' | 'memory' // memory: similar text already translated, confidence = similarity
 }
 
 export interface TranslationText {
@@ -64,6 +92,22 @@ export interface TranslationText {
   sourceText: string
   context?: string
   textType?: string  // Text type for category filtering: 'dialogue', 'system', 'item', 'skill', 'other'
+  location?: string  // Structured location ("item:1:name") used for length budgets
+}
+
+export interface LengthBudget {
+  pattern: string
+  max_cells: number
+  max_lines?: number | null
+}
+
+export interface BudgetViolation {
+  location: string
+  pattern: string
+  max_cells: number
+  max_lines?: number | null
+  actual_cells: number
+  actual_lines: number
 }
 
 export interface StartTranslationRequest {
@@ -78,6 +122,14 @@ export interface StartTranslationRequest {
     batchSize: number
     pauseDurationMinutes: number
   }
+  lengthBudgets?: LengthBudget[]
+  retranslateOverBudget?: boolean
+  promptBatchSize?: number
+  contextLines?: number
+  parallelRequests?: number
+  useTranslationMemory?: boolean // Reuse remembered translations of identical texts (default true)
+  deduplicate?: boolean // Translate identical source texts once (default true)
+  deduplicateExcludedTypes?: string[] // Text types translated entry by entry (default dialogue)
 }
 
 
@@ -87,14 +139,15 @@ export interface StartTranslationRequest {
 export async function startSequentialTranslation(
   request: StartTranslationRequest
 ): Promise<TextOperationResult<{ session_id: string; total_entries: number }>> {
-  const { provider, pod_id } = await getProviderSettings()
+  const { provider, pod_id, openai_compat, deepl, libretranslate } = await getProviderSettings()
   
   // Convert texts to backend format (camelCase for Rust serde rename_all)
   const backendTexts = request.texts.map(text => ({
     id: text.id,
     sourceText: text.sourceText,
     context: text.context,
-    textType: text.textType
+    textType: text.textType,
+    location: text.location
   }))
   
   return invokeTauri('start_sequential_translation', {
@@ -106,7 +159,18 @@ export async function startSequentialTranslation(
     targetLanguage: request.targetLanguage,
     model: request.model,
     pauseSettings: request.pauseSettings,
-    podId: pod_id
+    lengthBudgets: request.lengthBudgets,
+    retranslateOverBudget: request.retranslateOverBudget,
+    promptBatchSize: request.promptBatchSize,
+    contextLines: request.contextLines,
+    parallelRequests: request.parallelRequests,
+    useTranslationMemory: request.useTranslationMemory,
+    deduplicate: request.deduplicate,
+    deduplicateExcludedTypes: request.deduplicateExcludedTypes,
+    podId: pod_id,
+    openaiCompat: openai_compat,
+    deepl,
+    libretranslate
   })
 }
 
@@ -116,11 +180,14 @@ export async function startSequentialTranslation(
 export async function getTranslationProgress(
   sessionId: string
 ): Promise<TextOperationResult<TranslationProgress>> {
-  const { provider, pod_id } = await getProviderSettings()
+  const { provider, pod_id, openai_compat, deepl, libretranslate } = await getProviderSettings()
   return invokeTauri('get_sequential_progress', { 
     sessionId,
     provider,
-    podId: pod_id
+    podId: pod_id,
+    openaiCompat: openai_compat,
+    deepl,
+    libretranslate
   })
 }
 
@@ -130,11 +197,14 @@ export async function getTranslationProgress(
 export async function pauseTranslationSession(
   sessionId: string
 ): Promise<TextOperationResult> {
-  const { provider, pod_id } = await getProviderSettings()
+  const { provider, pod_id, openai_compat, deepl, libretranslate } = await getProviderSettings()
   return invokeTauriVoid('pause_sequential_session', { 
     sessionId,
     provider,
-    podId: pod_id
+    podId: pod_id,
+    openaiCompat: openai_compat,
+    deepl,
+    libretranslate
   })
 }
 
@@ -144,11 +214,14 @@ export async function pauseTranslationSession(
 export async function resumeTranslationSession(
   sessionId: string
 ): Promise<TextOperationResult> {
-  const { provider, pod_id } = await getProviderSettings()
+  const { provider, pod_id, openai_compat, deepl, libretranslate } = await getProviderSettings()
   return invokeTauriVoid('resume_sequential_session', { 
     sessionId,
     provider,
-    podId: pod_id
+    podId: pod_id,
+    openaiCompat: openai_compat,
+    deepl,
+    libretranslate
   })
 }
 
@@ -158,21 +231,33 @@ export async function resumeTranslationSession(
 export async function stopTranslationSession(
   sessionId: string
 ): Promise<TextOperationResult> {
-  const { provider, pod_id } = await getProviderSettings()
+  const { provider, pod_id, openai_compat, deepl, libretranslate } = await getProviderSettings()
   return invokeTauriVoid('stop_sequential_session', { 
     sessionId,
     provider,
-    podId: pod_id
+    podId: pod_id,
+    openaiCompat: openai_compat,
+    deepl,
+    libretranslate
   })
 }
 
 /**
- * Get active translation sessions for a project
+ * Requeue the failed entries of a translation session and resume it
+ * Returns the number of entries put back in the queue
  */
-export async function getProjectTranslationSessions(
-  projectId: number
-): Promise<TextOperationResult<TranslationSession[]>> {
-  return invokeTauri('get_project_sessions', { projectId })
+export async function requeueTranslationErrors(
+  sessionId: string
+): Promise<TextOperationResult<number>> {
+  const { provider, pod_id, openai_compat, deepl, libretranslate } = await getProviderSettings()
+  return invokeTauri<number>('requeue_sequential_errors', {
+    sessionId,
+    provider,
+    podId: pod_id,
+    openaiCompat: openai_compat,
+    deepl,
+    libretranslate
+  })
 }
 
 /**
@@ -192,20 +277,13 @@ export async function translateSingleText(
   context?: string,
   model?: string
 ): Promise<TextOperationResult<SingleTranslationResult>> {
-  const { provider, pod_id } = await getProviderSettings()
+  const { provider, pod_id, openai_compat, deepl, libretranslate } = await getProviderSettings()
   const settings = useSettings()
   const userSettings = await settings.loadSettings()
   
   // Use model from settings if not provided
-  let finalModel = model
-  if (!finalModel) {
-    if (provider === 'ollama') {
-      finalModel = userSettings.ollama.model
-    } else if (provider === 'runpod') {
-      finalModel = userSettings.runpod.model || undefined
-      // If RunPod model is empty, we'll let backend handle it (it should use first available model)
-    }
-  }
+  // (an empty RunPod model lets the backend pick the first available one)
+  const finalModel = model || getProviderModel(userSettings)
   
   return invokeTauri<SingleTranslationResult>('translate_single_text', {
     provider,
@@ -214,7 +292,10 @@ export async function translateSingleText(
     targetLanguage,
     context,
     model: finalModel,
-    podId: pod_id
+    podId: pod_id,
+    openaiCompat: openai_compat,
+    deepl,
+    libretranslate
   })
 }
 
@@ -226,12 +307,18 @@ export async function getTranslationSuggestions(
   context?: string,
   maxSuggestions = 3
 ): Promise<TextOperationResult<TranslationSuggestion[]>> {
-  const { provider, pod_id } = await getProviderSettings()
+  const { provider, pod_id, openai_compat, deepl, libretranslate } = await getProviderSettings()
+  const { translation } = await useSettings().loadSettings()
   const result = await invokeTauri<TranslationSuggestion[]>('get_translation_suggestions', {
     provider,
     sourceText,
     context,
-    podId: pod_id
+    sourceLanguage: translation.sourceLanguage,
+    targetLanguage: translation.targetLanguage,
+    podId: pod_id,
+    openaiCompat: openai_compat,
+    deepl,
+    libretranslate
   })
 
   if (result.success && result.data) {
@@ -2,22 +2,29 @@
 // Handles .ludolingo.json marker files for project identification
 
 import { writeTextFile, readTextFile, exists, remove } from '@tauri-apps/plugin-fs'
+import type { IgnoreRules } from '~/types/scanning-commands'
 
 export interface ProjectMarker {
   projectId: number
   createdAt: string
   version: string
+  ignoreRules?: IgnoreRules // Règles d'exclusion du projet (regex, tokens techniques)
 }
 
 /**
  * Create a project marker file in the game directory
  */
-export async function createProjectMarker(projectPath: string, projectId: number): Promise<void> {
+export async function createProjectMarker(
+  projectPath: string,
+  projectId: number,
+  ignoreRules?: IgnoreRules
+): Promise<void> {
   try {
     const markerData: ProjectMarker = {
       projectId,
       createdAt: new Date().toISOString(),
-      version: '1.0'
+      version: '1.0',
+      ...(ignoreRules ? { ignoreRules } : {})
     }
 
     const markerPath = `${projectPath}/.ludolingo.json`
@@ -0,0 +1,81 @@
+/**
+ * Composable for DeepL / LibreTranslate checking before translation
+ * Mirrors the Ollama/RunPod verification logic
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { useSettings } from '~/composables/useTauriSetting'
+import type { DeepLSettings, LibreTranslateSettings } from '~/composables/useTauriSetting'
+import { useNotifications } from '~/composables/useNotifications'
+
+export interface MachineTranslationStatus {
+  available: boolean
+  languages_available?: string[]
+  error?: string
+}
+
+export type MachineTranslationProvider = 'deepl' | 'libretranslate'
+
+const PROVIDER_LABELS: Record<MachineTranslationProvider, string> = {
+  deepl: 'DeepL',
+  libretranslate: 'LibreTranslate'
+}
+
+/**
+ * Check a machine translation engine and list its languages
+ */
+export async function checkMachineTranslationStatus(
+  provider: 'deepl',
+  config: DeepLSettings
+): Promise<MachineTranslationStatus>
+export async function checkMachineTranslationStatus(
+  provider: 'libretranslate',
+  config: LibreTranslateSettings
+): Promise<MachineTranslationStatus>
+export async function checkMachineTranslationStatus(
+  provider: MachineTranslationProvider,
+  config: DeepLSettings | LibreTranslateSettings
+): Promise<MachineTranslationStatus> {
+  try {
+    return await invoke<MachineTranslationStatus>(`check_${provider}_status`, { config })
+  } catch (error) {
+    return {
+      available: false,
+      error: error instanceof Error ? error.message : String(error)
+    }
+  }
+}
+
+export function useMachineTranslationCheck() {
+  const settings = useSettings()
+  const { notifyError } = useNotifications()
+
+  /**
+   * Check the configured engine before starting translation
+   * @returns true if the engine answers, false otherwise
+   */
+  const checkMachineTranslationBeforeTranslation = async (
+    provider: MachineTranslationProvider
+  ): Promise<boolean> => {
+    const userSettings = await settings.loadSettings()
+    const label = PROVIDER_LABELS[provider]
+
+    const status = provider === 'deepl'
+      ? await checkMachineTranslationStatus('deepl', userSettings.deepl)
+      : await checkMachineTranslationStatus('libretranslate', userSettings.libretranslate)
+
+    if (!status.available) {
+      notifyError(
+        `Impossible de se connecter à ${label}. ${status.error || ''} Veuillez vérifier votre configuration dans les paramètres.`,
+        `${label} indisponible`
+      )
+      return false
+    }
+
+    return true
+  }
+
+  return {
+    checkMachineTranslationBeforeTranslation
+  }
+}
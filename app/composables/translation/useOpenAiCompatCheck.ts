@@ -0,0 +1,66 @@
+/**
+ * Composable for OpenAI-compatible server checking before translation
+ * Mirrors the Ollama/RunPod verification logic
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { useSettings } from '~/composables/useTauriSetting'
+import type { OpenAiCompatSettings } from '~/composables/useTauriSetting'
+import { useNotifications } from '~/composables/useNotifications'
+
+export interface OpenAiCompatStatus {
+  available: boolean
+  models_available?: string[]
+  error?: string
+}
+
+/**
+ * Check an OpenAI-compatible server and list its models
+ */
+export async function checkOpenAiCompatStatus(config: OpenAiCompatSettings): Promise<OpenAiCompatStatus> {
+  try {
+    return await invoke<OpenAiCompatStatus>('check_openai_compat_status', { config })
+  } catch (error) {
+    return {
+      available: false,
+      error: error instanceof Error ? error.message : String(error)
+    }
+  }
+}
+
+export function useOpenAiCompatCheck() {
+  const settings = useSettings()
+  const { notifyError } = useNotifications()
+
+  /**
+   * Check the configured server before starting translation
+   * @returns true if the server answers and a model is configured, false otherwise
+   */
+  const checkOpenAiCompatBeforeTranslation = async (): Promise<boolean> => {
+    const userSettings = await settings.loadSettings()
+    const config = userSettings.openai_compat
+
+    if (!config.base_url.trim() || !config.model.trim()) {
+      notifyError(
+        'L\'URL du serveur ou le modèle n\'est pas configuré. Veuillez configurer le serveur compatible OpenAI dans les paramètres.',
+        'Serveur compatible OpenAI non configuré'
+      )
+      return false
+    }
+
+    const status = await checkOpenAiCompatStatus(config)
+    if (!status.available) {
+      notifyError(
+        `Impossible de se connecter au serveur. ${status.error || ''} Veuillez vérifier votre configuration dans les paramètres.`,
+        'Serveur compatible OpenAI indisponible'
+      )
+      return false
+    }
+
+    return true
+  }
+
+  return {
+    checkOpenAiCompatBeforeTranslation
+  }
+}
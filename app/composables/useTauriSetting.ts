@@ -3,8 +3,30 @@
 
 import { useTauriStore } from '~/composables/useTauriProject'
 
+// Serveur compatible OpenAI (llama.cpp server, vLLM, LM Studio, endpoints hébergés)
+export interface OpenAiCompatSettings {
+  base_url: string // Inclut le segment de version, ex: http://localhost:8080/v1
+  api_key: string
+  model: string
+  temperature: number | null
+  top_p: number | null
+  max_tokens: number | null
+}
+
+// DeepL API v2 (traduction automatique)
+export interface DeepLSettings {
+  api_key: string
+  base_url: string | null // Optionnel : déduit du type de clé (":fx" = gratuite)
+}
+
+// Instance LibreTranslate (auto-hébergée ou publique)
+export interface LibreTranslateSettings {
+  base_url: string
+  api_key: string | null
+}
+
 export interface AppSettings {
-  provider: 'ollama' | 'runpod'
+  provider: 'ollama' | 'runpod' | 'openai_compat' | 'deepl' | 'libretranslate'
   ollama: {
     endpoint: string
     port: number
@@ -14,6 +36,9 @@ export interface AppSettings {
     pod_id: string
     model: string
   }
+  openai_compat: OpenAiCompatSettings
+  deepl: DeepLSettings
+  libretranslate: LibreTranslateSettings
   translation: {
     sourceLanguage: string
     targetLanguage: string
@@ -22,6 +47,11 @@ export interface AppSettings {
       batchSize: number
       pauseDurationMinutes: number
     }
+    promptBatchSize: number
+    contextLines: number
+    parallelRequests: number
+    deduplicateTexts: boolean
+    deduplicateExcludedTypes: string[] // Text types translated entry by entry
   }
   updater: {
     autoCheck: boolean
@@ -30,6 +60,23 @@ export interface AppSettings {
   }
 }
 
+/**
+ * Modèle à utiliser selon le provider choisi
+ * Les moteurs de traduction automatique (DeepL, LibreTranslate) n'en ont pas
+ */
+export function getProviderModel(settings: AppSettings): string | undefined {
+  switch (settings.provider) {
+    case 'ollama':
+      return settings.ollama.model
+    case 'runpod':
+      return settings.runpod.model || undefined
+    case 'openai_compat':
+      return settings.openai_compat.model || undefined
+    default:
+      return undefined
+  }
+}
+
 /**
  * Composable pour gérer les paramètres de l'application
  * Utilise le Tauri store pour la persistance
@@ -48,6 +95,22 @@ export function useSettings() {
       pod_id: '',
       model: ''
     },
+    openai_compat: {
+      base_url: 'http://localhost:8080/v1',
+      api_key: '',
+      model: '',
+      temperature: null,
+      top_p: null,
+      max_tokens: null
+    },
+    deepl: {
+      api_key: '',
+      base_url: null
+    },
+    libretranslate: {
+      base_url: 'http://localhost:5000',
+      api_key: null
+    },
     translation: {
       sourceLanguage: 'ja',
       targetLanguage: 'fr',
@@ -55,7 +118,12 @@ export function useSettings() {
         enabled: true,
         batchSize: 150,
         pauseDurationMinutes: 5
-      }
+      },
+      promptBatchSize: 1,
+      contextLines: 3,
+      parallelRequests: 1,
+      deduplicateTexts: true,
+      deduplicateExcludedTypes: ['dialogue']
     },
     updater: {
       autoCheck: true,
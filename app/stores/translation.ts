@@ -7,6 +7,8 @@ import { useBaseStoreState, executeAsyncOperation } from '~/composables/stores/u
 import { useOllamaStore } from '~/stores/ollama'
 import { useProjectsStore } from '~/stores/projects'
 import { isConnectionError } from '~/utils/connectionErrors'
+import { useSettings } from '~/composables/useTauriSetting'
+import { recordEntryTranslationMemory } from '~/composables/db/memory'
 import type {
   TranslationSession,
   TranslationProgress,
@@ -20,7 +22,13 @@ import {
   pauseTranslationSession,
   resumeTranslationSession,
   stopTranslationSession,
+  requeueTranslationErrors,
+  saveTranslationSession,
+  checkpointTranslationSession,
+  getTranslationSession,
   getProjectTranslationSessions,
+  getTranslationSessionQueue,
+  deleteTranslationSession,
   getTranslationSuggestions,
   updateTextWithTranslation
 } from '~/composables/db/texts'
@@ -138,6 +146,7 @@ export const useTranslationStore = defineStore('translation', () => {
   const pausedSessions = getSessionsByStatus('paused')
   const completedSessions = getSessionsByStatus('completed')
   const errorSessions = getSessionsByStatus('error')
+  const interruptedSessions = getSessionsByStatus('interrupted')
 
   const getSessionProgress = (sessionId: string) => {
     return sessionProgress.value.get(sessionId) || null
@@ -174,6 +183,12 @@ export const useTranslationStore = defineStore('translation', () => {
           sessionPauseSettings.value.set(result.data.session_id, request.pauseSettings)
         }
 
+        // Persist the session and its queue so it can be resumed after a restart
+        const saved = await saveTranslationSession(result.data.session_id, request)
+        if (!saved.success) {
+          console.warn(`⚠️ Session ${result.data.session_id} non sauvegardée:`, saved.error)
+        }
+
         // Start progress monitoring
         monitorSessionProgress(result.data.session_id)
 
@@ -219,12 +234,73 @@ export const useTranslationStore = defineStore('translation', () => {
   const stopSession = async (sessionId: string) => {
     return executeSessionOperation(
       sessionId,
-      () => stopTranslationSession(sessionId),
+      async () => {
+        const result = await stopTranslationSession(sessionId)
+        // A stopped session is not meant to be resumed
+        if (result.success) await deleteTranslationSession(sessionId)
+        return result
+      },
       'Stop',
       { removeSession: true }
     )
   }
 
+  const requeueSessionErrors = async (sessionId: string) => {
+    let requeued = 0
+    await executeSessionOperation(
+      sessionId,
+      async () => {
+        const result = await requeueTranslationErrors(sessionId)
+        requeued = result.data ?? 0
+        return result
+      },
+      'Requeue',
+      {
+        newStatus: 'running',
+        onSuccess: (session) => monitorSessionProgress(session.session_id) // Completed sessions are no longer monitored
+      }
+    )
+    return requeued
+  }
+
+  const discardInterruptedSession = async (sessionId: string) => {
+    const result = await deleteTranslationSession(sessionId)
+    if (!result.success) {
+      throw new Error(result.error || `Failed to discard session ${sessionId}`)
+    }
+    activeSessions.value = activeSessions.value.filter(s => s.session_id !== sessionId)
+    sessionProgress.value.delete(sessionId)
+  }
+
+  // Resume a session interrupted by an app restart from its checkpoint:
+  // a new backend session is started with the entries not translated yet
+  const resumeInterruptedSession = async (sessionId: string) => {
+    const persisted = await getTranslationSession(sessionId)
+    if (!persisted.success || !persisted.data) {
+      throw new Error(persisted.error || `Session ${sessionId} not found`)
+    }
+    const queue = await getTranslationSessionQueue(sessionId)
+    if (!queue.success || !queue.data) {
+      throw new Error(queue.error || `Failed to load the queue of session ${sessionId}`)
+    }
+
+    let session: TranslationSession | null = null
+    if (queue.data.length > 0) {
+      const { provider } = await useSettings().loadSettings()
+      const { model, ...settings } = persisted.data.settings
+      session = await startTranslation({
+        ...settings,
+        // The model belongs to the provider the session was started with
+        model: persisted.data.provider === provider ? model : undefined,
+        projectId: persisted.data.project_id,
+        texts: queue.data
+      })
+    }
+
+    await discardInterruptedSession(sessionId)
+    return session
+  }
+
   const loadProjectSessions = async (projectId: number) => {
     return executeAsyncOperation(async () => {
       const result = await getProjectTranslationSessions(projectId)
@@ -375,6 +451,14 @@ export const useTranslationStore = defineStore('translation', () => {
           })
           sessionProgress.value.set(sessionId, progress)
 
+          // Checkpoint the session; once completed without errors there is nothing left to resume
+          const checkpoint = progress.status === 'completed' && progress.errors.length === 0
+            ? await deleteTranslationSession(sessionId)
+            : await checkpointTranslationSession(sessionId, progress)
+          if (!checkpoint.success) {
+            console.warn(`⚠️ Checkpoint de la session ${sessionId} impossible:`, checkpoint.error)
+          }
+
           // Stop monitoring if session is completed or errored
           if (progress.status === 'completed' || progress.status === 'error') {
             clearInterval(intervalId)
@@ -485,6 +569,20 @@ export const useTranslationStore = defineStore('translation', () => {
       const result = await updateTextWithTranslation(textId, translatedText, status)
 
       if (result.success) {
+        // Mémoriser la traduction pour les textes identiques ou proches
+        if (status === 'Translated') {
+          const { translation } = await useSettings().loadSettings()
+          const memoryResult = await recordEntryTranslationMemory(
+            textId,
+            translatedText,
+            translation.sourceLanguage,
+            translation.targetLanguage
+          )
+          if (!memoryResult.success) {
+            console.warn(`⚠️ [Memory] Traduction non mémorisée pour le texte ${textId}:`, memoryResult.error)
+          }
+        }
+
         // 2. Mettre à jour directement dans le store pour une réactivité immédiate (comme dans glossary)
         const projectsStore = useProjectsStore()
         const project = projectsStore.currentProject
@@ -604,6 +702,7 @@ export const useTranslationStore = defineStore('translation', () => {
     hasActiveSessions,
     runningSessions,
     pausedSessions,
+    interruptedSessions,
     completedSessions,
     errorSessions,
     getSessionProgress,
@@ -614,6 +713,9 @@ export const useTranslationStore = defineStore('translation', () => {
     pauseSession,
     resumeSession,
     stopSession,
+    requeueSessionErrors,
+    resumeInterruptedSession,
+    discardInterruptedSession,
     loadProjectSessions,
     getSuggestions,
     applyTranslation,
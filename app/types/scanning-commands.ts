@@ -87,7 +87,18 @@ export interface ScanningCommands {
   validate_file_format: (file_path: string) => Promise<FileValidationResult>;
 
   // Extract texts directly from folder
-  extract_texts_from_folder: (folder_path: string) => Promise<TextEntry[]>;
+  // Texts that don't need translation are marked Ignored when target_language is set
+  extract_texts_from_folder: (
+    folder_path: string,
+    target_language?: string,
+    ignore_rules?: IgnoreRules
+  ) => Promise<TextEntry[]>;
+}
+
+// Project ignore rules: texts matching a regex or a token are not translated
+export interface IgnoreRules {
+  patterns?: string[];
+  tokens?: string[];
 }
 
 export interface TextEntry {
@@ -0,0 +1,38 @@
+-- Migration 002: Persistent sequential translation sessions
+-- Sequential sessions live in the backend memory only: the frontend checkpoints them here
+-- while monitoring their progress, so a session cut short by closing the app can be resumed
+
+-- Translation sessions table: one row per sequential session started by the frontend
+CREATE TABLE IF NOT EXISTS translation_sessions (
+    id TEXT PRIMARY KEY,  -- session_id returned by the backend (e.g. "seq_1718000000000_1")
+    project_id INTEGER NOT NULL,
+    provider TEXT NOT NULL,  -- 'ollama', 'runpod', 'openai_compat', 'deepl', 'libretranslate'
+    status TEXT NOT NULL DEFAULT 'running',  -- 'running', 'paused', 'completed', 'error', 'interrupted'
+    settings TEXT NOT NULL DEFAULT '{}',  -- JSON: languages, model, pause and sequential options the session was started with
+    total_count INTEGER NOT NULL DEFAULT 0,
+    processed_count INTEGER NOT NULL DEFAULT 0,
+    error_count INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- Session queue table: entries of a session, in the order they are sent to the provider
+CREATE TABLE IF NOT EXISTS translation_session_entries (
+    session_id TEXT NOT NULL,
+    entry_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,  -- Index in the session queue
+    state TEXT NOT NULL DEFAULT 'pending',  -- 'pending', 'done', 'error'
+    error_message TEXT,
+    error_kind TEXT,  -- 'transient', 'rate_limited', 'fatal_config', 'bad_output'
+    PRIMARY KEY (session_id, entry_id),
+    FOREIGN KEY (session_id) REFERENCES translation_sessions(id) ON DELETE CASCADE,
+    FOREIGN KEY (entry_id) REFERENCES translation_entries(id) ON DELETE CASCADE
+);
+
+-- Translation sessions indexes
+CREATE INDEX IF NOT EXISTS idx_translation_sessions_project ON translation_sessions(project_id);
+CREATE INDEX IF NOT EXISTS idx_translation_sessions_status ON translation_sessions(status);
+
+-- Session queue indexes
+CREATE INDEX IF NOT EXISTS idx_session_entries_queue ON translation_session_entries(session_id, state, position);  -- Remaining queue of a session, in order
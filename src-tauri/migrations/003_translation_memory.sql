@@ -0,0 +1,21 @@
+-- Migration 003: Translation memory
+-- Translations applied to entries are remembered by source text and language pair, across projects:
+-- exact matches are reused by sequential sessions without calling the model, similar ones are suggested
+
+-- Translation memory table: one translation per normalized source text and language pair
+CREATE TABLE IF NOT EXISTS translation_memory (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    normalized_source TEXT NOT NULL,  -- Source text as prepared for translation, stray whitespace normalized
+    source_text TEXT NOT NULL,  -- Source text as last seen
+    translated_text TEXT NOT NULL,  -- Last translation applied
+    source_language TEXT NOT NULL,
+    target_language TEXT NOT NULL,
+    source_length INTEGER NOT NULL,  -- Characters of normalized_source, to preselect fuzzy match candidates
+    usage_count INTEGER NOT NULL DEFAULT 1,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(normalized_source, source_language, target_language)
+);
+
+-- Translation memory indexes
+CREATE INDEX IF NOT EXISTS idx_translation_memory_length ON translation_memory(source_language, target_language, source_length);  -- Fuzzy match candidates
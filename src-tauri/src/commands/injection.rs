@@ -1,19 +1,51 @@
 // Injection commands for reinjecting translations into game files
 // Implements the injection workflow for game localization
 
-use crate::parsers::engine::TranslationEntry;
+use crate::core::backup::{self, BackupInfo};
+use crate::core::staging::StagedFiles;
+use crate::parsers::dry_run::InjectionReport;
+use crate::parsers::engine::{PromptType, TextEntry, TranslationEntry};
 use crate::parsers::factory::EngineFactory;
-use crate::parsers::handler::GameEngineHandler;
+use crate::parsers::handler::{stage_game_files, GameEngineHandler, InjectionOptions};
+use crate::parsers::round_trip::{check_round_trip, RoundTripMismatch};
+use crate::parsers::text::formatter::{QuotePolicy, UniversalFormatter};
+use crate::parsers::text::length_budget::{check_length_budget, BudgetViolation, LengthBudget};
+use crate::parsers::text::validation::{check_translation, RetranslationReason};
+use crate::parsers::text::word_wrap::WordWrapSettings;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
-use std::sync::Mutex;
-use tauri::State;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, State};
 
-// Shared state for injection progress tracking
-#[derive(Default)]
+/// Event emitted with the `InjectionProgress` of a running injection
+pub const INJECTION_PROGRESS_EVENT: &str = "injection-progress";
+
+// Shared state for injection progress tracking, cloned into the background tasks
+#[derive(Default, Clone)]
 pub struct InjectionState {
-    pub current_injections: Mutex<HashMap<String, InjectionProgress>>,
+    pub current_injections: Arc<Mutex<HashMap<String, InjectionProgress>>>,
+    /// Cancellation flags of the running injections
+    pub cancellations: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+}
+
+impl InjectionState {
+    /// Ask a running injection to stop before its next file
+    ///
+    /// An injection that already started replacing the target files runs to its end.
+    pub fn cancel(&self, injection_id: &str) -> Result<(), String> {
+        if let Some(cancelled) = self.cancellations.lock().unwrap().get(injection_id) {
+            cancelled.store(true, Ordering::SeqCst);
+            return Ok(());
+        }
+        let known = self.current_injections.lock().unwrap().contains_key(injection_id);
+        if known {
+            Err("Injection already finished".to_string())
+        } else {
+            Err("Injection not found".to_string())
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,12 +54,96 @@ pub struct InjectionRequest {
     pub game_path: String,
     pub translations: Vec<TranslationEntryInput>,
     pub file_ids: Option<Vec<i64>>,
+    /// Target language of the translations, used to restore normalized quotes
+    pub target_language: Option<String>,
+    /// Quote policy override (defaults to the target language policy)
+    pub quote_policy: Option<QuotePolicy>,
+    /// Re-flow translated dialogue to the message window width
+    pub word_wrap: Option<WordWrapSettings>,
+    /// Folder receiving a translated copy of the data root, leaving the game untouched
+    pub output_path: Option<String>,
+    /// Restrict the injection to part of the translations (all of them when unset)
+    pub filters: Option<InjectionFilters>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslationEntryInput {
     pub id: String,
     pub translated_text: String,
+    /// Review status of the translation (counts as 'translated' when unset)
+    pub status: Option<ReviewStatus>,
+}
+
+/// Review status of a translation, in increasing order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewStatus {
+    Extracted,
+    Translated,
+    Reviewed,
+}
+
+/// Selects the translations of a selective injection; every set filter must match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InjectionFilters {
+    /// Files to inject into, relative to the game root (e.g. `data/System.json`);
+    /// the other files are left untouched
+    pub files: Option<Vec<String>>,
+    /// Location prefix of the texts to inject (e.g. `map:9:`)
+    pub location_prefix: Option<String>,
+    /// Text types to inject
+    pub text_types: Option<Vec<PromptType>>,
+    /// Minimum review status of the translations to inject
+    pub min_status: Option<ReviewStatus>,
+}
+
+impl InjectionFilters {
+    /// Whether the filters need the extracted game texts to match translations
+    fn needs_game_texts(&self) -> bool {
+        self.files.is_some() || self.location_prefix.is_some() || self.text_types.is_some()
+    }
+
+    fn matches_status(&self, translation: &TranslationEntryInput) -> bool {
+        let status = translation.status.unwrap_or(ReviewStatus::Translated);
+        self.min_status.map_or(true, |min_status| status >= min_status)
+    }
+
+    fn matches_entry(&self, entry: &TextEntry) -> bool {
+        let in_files = self.files.as_ref().map_or(true, |files| {
+            entry
+                .file_path
+                .as_ref()
+                .is_some_and(|path| files.contains(path))
+        });
+        let at_location = self
+            .location_prefix
+            .as_ref()
+            .map_or(true, |prefix| entry.location.starts_with(prefix.as_str()));
+        let of_type = self
+            .text_types
+            .as_ref()
+            .map_or(true, |types| types.contains(&entry.text_type));
+        in_files && at_location && of_type
+    }
+
+    /// Keep the translations matching every filter
+    ///
+    /// `entries` maps ids to the extracted game texts; it is only read when
+    /// `needs_game_texts`.
+    fn select(
+        &self,
+        translations: &mut Vec<TranslationEntryInput>,
+        entries: &HashMap<&str, &TextEntry>,
+    ) {
+        translations.retain(|t| self.matches_status(t));
+        if self.needs_game_texts() {
+            translations.retain(|t| {
+                entries
+                    .get(t.id.as_str())
+                    .is_some_and(|entry| self.matches_entry(entry))
+            });
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +155,10 @@ pub struct InjectionProgress {
     pub entries_injected: usize,
     pub errors: Vec<InjectionError>,
     pub status: InjectionStatus,
+    /// Current phase; when the injection failed, the phase that failed
+    pub phase: InjectionPhase,
+    /// Backup of the game files taken before writing (none when writing to an output folder)
+    pub backup_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,14 +177,37 @@ pub enum InjectionStatus {
     Cancelled,
 }
 
+/// Steps of an injection, in order
+///
+/// Target files are only modified during `Committing`: a failure in an earlier phase
+/// leaves them untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InjectionPhase {
+    /// Saving the game files to a backup set (skipped when writing to an output folder)
+    Backup,
+    /// Copying the game files to the staging directory
+    Staging,
+    /// Writing translations into the staged files
+    Injecting,
+    /// Flushing the staged files to disk
+    Syncing,
+    /// Renaming the staged files over the target files
+    Committing,
+    Completed,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InjectionResult {
     pub injection_id: String,
-    pub status: String, // 'completed' | 'partial' | 'failed'
+    pub status: String, // 'completed' | 'partial' | 'failed' | 'cancelled' | 'in_progress'
     pub files_processed: usize,
     pub entries_injected: usize,
     pub errors: Vec<InjectionError>,
     pub completed_at: String,
+    /// Last phase reached (the failed phase when status is 'failed')
+    pub phase: InjectionPhase,
+    /// Backup to restore with `rollback_injection`
+    pub backup_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +222,25 @@ pub struct InjectionValidationResult {
     pub valid: bool,
     pub issues: Vec<ValidationIssue>,
     pub summary: ValidationSummary,
+    /// Translations exceeding the length budget of their field
+    pub over_budget_entries: Vec<OverBudgetEntry>,
+    /// Translations left untranslated by the model, to send back for retranslation
+    pub retranslation_entries: Vec<RetranslationEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverBudgetEntry {
+    pub entry_id: String,
+    pub translated_text: String,
+    #[serde(flatten)]
+    pub violation: BudgetViolation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetranslationEntry {
+    pub entry_id: String,
+    pub translated_text: String,
+    pub reason: RetranslationReason,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,15 +258,109 @@ pub struct ValidationRequest {
     pub translated_count: usize,
     pub untranslated_count: usize,
     pub file_ids: Option<Vec<i64>>,
+    /// Translations to check against length budgets
+    pub translations: Option<Vec<TranslationEntryInput>>,
+    /// Length budgets checked before the engine defaults (first match wins)
+    pub length_budgets: Option<Vec<LengthBudget>>,
+    /// Target language, used to detect residual source-language script
+    pub target_language: Option<String>,
+    /// Quote policy the translations were injected with
+    pub quote_policy: Option<QuotePolicy>,
+    /// Word wrap settings of the injection: messages re-flowed to more lines than their
+    /// window shows, continued in a new window, are reported as over budget
+    pub word_wrap: Option<WordWrapSettings>,
+    /// Backup taken by an injection into the game folder: the game is then checked
+    /// after injection, by extracting it again and comparing it with the original files
+    pub backup_id: Option<String>,
+    /// Filters of a selective injection: the translations they leave out are neither
+    /// checked nor expected in the injected game
+    pub filters: Option<InjectionFilters>,
 }
 
 /// Start translation injection
+///
+/// The injection runs on a background task and returns immediately; its progress is
+/// emitted as `injection-progress` events after each file and phase.
 #[tauri::command]
 pub async fn start_injection(
     request: InjectionRequest,
+    app: AppHandle,
     state: State<'_, InjectionState>,
 ) -> Result<(String, usize, u64), String> {
     let injection_id = format!("inj_{}", chrono::Utc::now().timestamp_millis());
+    let game_path = PathBuf::from(&request.game_path);
+    let output_path = request.output_path.clone();
+    let (handler, translations, options) = prepare_injection(request)?;
+    let output_path = resolve_output_path(handler.as_ref(), &game_path, output_path.as_deref())?;
+
+    // Files the injection will touch; an output folder receives the whole data root
+    let files = match output_path {
+        Some(_) => handler.list_data_root_files(&game_path),
+        None => handler
+            .list_files_to_process(&game_path)
+            .into_iter()
+            .filter(|file| {
+                let relative = file.strip_prefix(&game_path).unwrap_or(file);
+                options.includes_file(&relative.to_string_lossy())
+            })
+            .collect(),
+    };
+    let total_files = files.len();
+
+    // Initialize progress
+    let progress = InjectionProgress {
+        injection_id: injection_id.clone(),
+        current_file: String::new(),
+        files_processed: 0,
+        total_files,
+        entries_injected: 0,
+        errors: Vec::new(),
+        status: InjectionStatus::Pending,
+        phase: InjectionPhase::Backup,
+        backup_id: None,
+    };
+
+    state
+        .current_injections
+        .lock()
+        .unwrap()
+        .insert(injection_id.clone(), progress);
+
+    let run = InjectionRun::new(&state, &injection_id, move |progress| {
+        if let Err(e) = app.emit(INJECTION_PROGRESS_EVENT, progress) {
+            log::warn!("Failed to emit {}: {}", INJECTION_PROGRESS_EVENT, e);
+        }
+    });
+    tokio::task::spawn_blocking(move || {
+        perform_injection(
+            &game_path,
+            output_path.as_deref(),
+            handler.as_ref(),
+            &files,
+            translations,
+            &options,
+            &run,
+        );
+    });
+
+    // Estimate duration (rough estimate: 1 second per file)
+    let estimated_duration = total_files as u64;
+
+    Ok((injection_id, total_files, estimated_duration))
+}
+
+/// Simulate an injection and report the changes per file, without writing into the game
+#[tauri::command]
+pub async fn dry_run_injection(request: InjectionRequest) -> Result<InjectionReport, String> {
+    let game_path_string = request.game_path.clone();
+    let (handler, translations, options) = prepare_injection(request)?;
+    handler.dry_run_inject_all_texts(Path::new(&game_path_string), &translations, &options)
+}
+
+/// Validate the game path, detect its engine and convert the translations to parser format
+fn prepare_injection(
+    request: InjectionRequest,
+) -> Result<(Box<dyn GameEngineHandler>, Vec<TranslationEntry>, InjectionOptions), String> {
     let game_path = Path::new(&request.game_path);
 
     // Validate game path exists
@@ -119,43 +375,109 @@ pub async fn start_injection(
     let handler = EngineFactory::create_handler(game_path)
         .map_err(|e| format!("Failed to detect game engine: {}", e))?;
 
-    // Convert input translations to parser format
-    let translations: Vec<TranslationEntry> = request
-        .translations
+    // Japanese quotes normalized during extraction are restored per target language;
+    // restoring the original marks needs the marks recorded with each game text
+    let target_language = request.target_language;
+    let quote_policy = request.quote_policy;
+    let restores_original = target_language.as_deref().is_some_and(|lang| {
+        quote_policy.unwrap_or_else(|| QuotePolicy::for_language(lang))
+            == QuotePolicy::RestoreOriginal
+    });
+
+    // Selective injection: keep the translations matching every filter
+    let filters = request.filters.unwrap_or_default();
+    let game_texts = if filters.needs_game_texts() || restores_original {
+        extract_original_texts(handler.as_ref(), game_path)
+            .map_err(|e| format!("Impossible de lire les textes du jeu: {}", e))?
+    } else {
+        Vec::new()
+    };
+    let entries: HashMap<&str, &TextEntry> =
+        game_texts.iter().map(|entry| (entry.id.as_str(), entry)).collect();
+    let mut selected = request.translations;
+    filters.select(&mut selected, &entries);
+
+    let options = InjectionOptions {
+        word_wrap: request.word_wrap,
+        files: filters.files,
+    };
+    let translations: Vec<TranslationEntry> = selected
         .into_iter()
         .map(|t| TranslationEntry {
+            translated_text: restore_translation_quotes(
+                t.translated_text,
+                target_language.as_deref(),
+                quote_policy,
+                entries
+                    .get(t.id.as_str())
+                    .and_then(|entry| entry.source_quotes.as_deref()),
+            ),
             id: t.id,
-            translated_text: t.translated_text,
         })
         .collect();
 
-    // Count files to process
-    let total_files = handler.count_files_to_process(game_path);
+    Ok((handler, translations, options))
+}
 
-    // Initialize progress
-    let progress = InjectionProgress {
-        injection_id: injection_id.clone(),
-        current_file: String::new(),
-        files_processed: 0,
-        total_files,
-        entries_injected: 0,
-        errors: Vec::new(),
-        status: InjectionStatus::Pending,
-    };
+/// Extract the texts of the game as it was before any injection
+///
+/// Translation ids are those of the original game, which word wrap may have shifted in
+/// the injected files. The original files are staged in a temporary directory.
+fn extract_original_texts(
+    handler: &dyn GameEngineHandler,
+    game_path: &Path,
+) -> Result<Vec<TextEntry>, String> {
+    let staging_dir = tempfile::TempDir::new()
+        .map_err(|e| format!("Impossible de créer le dossier temporaire: {}", e))?;
+    let staged = stage_game_files(handler, game_path, staging_dir.path(), "originals")?;
+    handler.extract_all_texts(staged.root())
+}
 
-    state
-        .current_injections
-        .lock()
-        .unwrap()
-        .insert(injection_id.clone(), progress);
+/// Restore the Japanese quotes normalized during extraction for the target language
+fn restore_translation_quotes(
+    text: String,
+    target_language: Option<&str>,
+    quote_policy: Option<QuotePolicy>,
+    source_quotes: Option<&str>,
+) -> String {
+    match target_language {
+        Some(lang) => UniversalFormatter::restore_quotes(&text, lang, quote_policy, source_quotes),
+        None => text,
+    }
+}
 
-    // Perform injection synchronously (can be made async later)
-    perform_injection_sync(game_path, handler.as_ref(), translations, injection_id.clone(), state);
+/// Check the output folder of an injection and create it
+///
+/// Returns `None` when the injection writes into the game folder itself.
+fn resolve_output_path(
+    handler: &dyn GameEngineHandler,
+    game_path: &Path,
+    output_path: Option<&str>,
+) -> Result<Option<PathBuf>, String> {
+    let output_path = match output_path.map(str::trim) {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => return Ok(None),
+    };
+    if output_path == game_path {
+        return Ok(None);
+    }
 
-    // Estimate duration (rough estimate: 1 second per file)
-    let estimated_duration = total_files as u64;
+    // The data root is mirrored into the output: it can't live inside it
+    if output_path.starts_with(handler.get_data_root(game_path)) {
+        return Err(format!(
+            "Le dossier de sortie ne peut pas être dans les données du jeu: {}",
+            output_path.display()
+        ));
+    }
 
-    Ok((injection_id, total_files, estimated_duration))
+    std::fs::create_dir_all(&output_path).map_err(|e| {
+        format!(
+            "Impossible de créer le dossier de sortie {}: {}",
+            output_path.display(),
+            e
+        )
+    })?;
+    Ok(Some(output_path))
 }
 
 /// Get injection progress
@@ -172,18 +494,14 @@ pub fn get_injection_progress(
 }
 
 /// Cancel injection
+///
+/// The injection stops before its next file and leaves the target files untouched.
 #[tauri::command]
 pub fn cancel_injection(
     injection_id: String,
     state: State<'_, InjectionState>,
 ) -> Result<(), String> {
-    let mut injections = state.current_injections.lock().unwrap();
-    if let Some(progress) = injections.get_mut(&injection_id) {
-        progress.status = InjectionStatus::Cancelled;
-        Ok(())
-    } else {
-        Err("Injection not found".to_string())
-    }
+    state.cancel(&injection_id)
 }
 
 /// Get injection result
@@ -201,6 +519,7 @@ pub fn get_injection_result(
         InjectionStatus::Completed => "completed",
         InjectionStatus::Partial => "partial",
         InjectionStatus::Failed => "failed",
+        InjectionStatus::Cancelled => "cancelled",
         _ => "in_progress",
     };
 
@@ -211,9 +530,27 @@ pub fn get_injection_result(
         entries_injected: progress.entries_injected,
         errors: progress.errors.clone(),
         completed_at: chrono::Utc::now().to_rfc3339(),
+        phase: progress.phase,
+        backup_id: progress.backup_id.clone(),
     })
 }
 
+/// List the injection backups of a game, newest first
+#[tauri::command]
+pub fn list_injection_backups(game_path: String) -> Result<Vec<BackupInfo>, String> {
+    backup::list_backups(Path::new(&game_path))
+        .map_err(|e| format!("Impossible de lister les sauvegardes: {}", e))
+}
+
+/// Restore the game files saved in a backup, undoing the injections made since
+///
+/// Returns the number of restored files.
+#[tauri::command]
+pub fn rollback_injection(game_path: String, backup_id: String) -> Result<usize, String> {
+    backup::restore_backup(Path::new(&game_path), &backup_id)
+        .map_err(|e| format!("Impossible de restaurer la sauvegarde {}: {}", backup_id, e))
+}
+
 /// Validate injection (dry run)
 #[tauri::command]
 pub async fn validate_injection(
@@ -237,6 +574,8 @@ pub async fn validate_injection(
                 entries_to_inject: 0,
                 untranslated_entries: request.untranslated_count,
             },
+            over_budget_entries: Vec::new(),
+            retranslation_entries: Vec::new(),
         });
     }
 
@@ -254,6 +593,8 @@ pub async fn validate_injection(
                 entries_to_inject: 0,
                 untranslated_entries: request.untranslated_count,
             },
+            over_budget_entries: Vec::new(),
+            retranslation_entries: Vec::new(),
         });
     }
 
@@ -274,6 +615,8 @@ pub async fn validate_injection(
                     entries_to_inject: 0,
                     untranslated_entries: request.untranslated_count,
                 },
+                over_budget_entries: Vec::new(),
+                retranslation_entries: Vec::new(),
             });
         }
     };
@@ -374,6 +717,81 @@ pub async fn validate_injection(
         });
     }
 
+    // 7. Translations are checked against the game as it was before any injection
+    let has_translations = request.translations.as_ref().is_some_and(|t| !t.is_empty());
+    let staging_dir = tempfile::TempDir::new()
+        .map_err(|e| format!("Impossible de créer le dossier temporaire: {}", e))?;
+    let originals = stage_game_files(handler.as_ref(), game_path, staging_dir.path(), "originals")?;
+    let original_texts = if request.backup_id.is_some() || has_translations {
+        handler
+            .extract_all_texts(originals.root())
+            .map_err(|e| format!("Erreur extraction des textes: {}", e))?
+    } else {
+        Vec::new()
+    };
+
+    let translations = selected_translations(&request, &original_texts);
+
+    // 8. After an injection, check the game files still load and hold the injected texts
+    if request.backup_id.is_some() {
+        issues.extend(verify_injected_game(
+            handler.as_ref(),
+            game_path,
+            &original_texts,
+            &translations,
+            &request,
+        ));
+    }
+
+    // 9. Check translations against their source text and field length budgets
+    let extracted: HashMap<String, TextEntry> = original_texts
+        .into_iter()
+        .map(|entry| (entry.id.clone(), entry))
+        .collect();
+
+    let mut budgets = request.length_budgets.clone().unwrap_or_default();
+    budgets.extend(handler.default_length_budgets());
+    let mut over_budget_entries = find_over_budget_entries(&extracted, &translations, &budgets);
+    if let Some(word_wrap) = &request.word_wrap {
+        over_budget_entries.extend(find_message_overflow_entries(
+            handler.as_ref(),
+            originals.root(),
+            &extracted,
+            &translations,
+            &request,
+            word_wrap,
+        )?);
+    }
+
+    if !over_budget_entries.is_empty() {
+        issues.push(ValidationIssue {
+            file_path: String::new(),
+            severity: "warning".to_string(),
+            message: format!(
+                "{} traduction(s) dépassent la longueur maximale de leur champ et risquent d'être tronquées",
+                over_budget_entries.len()
+            ),
+        });
+    }
+
+    let retranslation_entries = match &request.target_language {
+        Some(target_language) => {
+            find_retranslation_entries(&extracted, &translations, target_language)
+        }
+        None => Vec::new(),
+    };
+
+    if !retranslation_entries.is_empty() {
+        issues.push(ValidationIssue {
+            file_path: String::new(),
+            severity: "warning".to_string(),
+            message: format!(
+                "{} traduction(s) contiennent encore du texte source et doivent être retraduites",
+                retranslation_entries.len()
+            ),
+        });
+    }
+
     // Determine if validation is valid (no errors, only warnings allowed)
     let has_errors = issues.iter().any(|i| i.severity == "error");
     let valid = !has_errors && files_to_process > 0 && request.translated_count > 0;
@@ -386,46 +804,1120 @@ pub async fn validate_injection(
             entries_to_inject: request.translated_count,
             untranslated_entries: request.untranslated_count,
         },
+        over_budget_entries,
+        retranslation_entries,
     })
 }
 
-/// Perform actual injection operation synchronously
-fn perform_injection_sync(
+/// Translations of a validation request that its injection filters keep
+///
+/// `texts` are the extracted game texts, matched by id with the translations.
+fn selected_translations(
+    request: &ValidationRequest,
+    texts: &[TextEntry],
+) -> Vec<TranslationEntryInput> {
+    let entries: HashMap<&str, &TextEntry> =
+        texts.iter().map(|entry| (entry.id.as_str(), entry)).collect();
+    let mut translations = request.translations.clone().unwrap_or_default();
+    request
+        .filters
+        .clone()
+        .unwrap_or_default()
+        .select(&mut translations, &entries);
+    translations
+}
+
+/// Round-trip check of a game after injection
+///
+/// Every file to process must still parse, every translation must be read back at its
+/// location, and every other location must keep its original text. `before` holds the
+/// texts of the game as it was before any injection (see `stage_game_files`), and
+/// `translations` those the injection kept.
+fn verify_injected_game(
+    handler: &dyn GameEngineHandler,
     game_path: &Path,
+    before: &[TextEntry],
+    translations: &[TranslationEntryInput],
+    request: &ValidationRequest,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let error = |file_path: String, message: String| ValidationIssue {
+        file_path,
+        severity: "error".to_string(),
+        message,
+    };
+
+    for file in handler.list_files_to_process(game_path) {
+        let is_json = file.extension().is_some_and(|ext| ext == "json");
+        let loaded = std::fs::read(&file).map_err(|e| e.to_string()).and_then(|content| {
+            if is_json {
+                serde_json::from_slice::<serde_json::Value>(&content)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            } else {
+                Ok(())
+            }
+        });
+        if let Err(e) = loaded {
+            issues.push(error(
+                file.display().to_string(),
+                format!("Le fichier ne se charge plus après l'injection: {}", e),
+            ));
+        }
+    }
+
+    let after = match handler.extract_all_texts(game_path) {
+        Ok(after) => after,
+        Err(e) => {
+            issues.push(error(
+                game_path.display().to_string(),
+                format!("Les textes du jeu ne peuvent plus être extraits après l'injection: {}", e),
+            ));
+            return issues;
+        }
+    };
+
+    let source_quotes: HashMap<&str, &str> = before
+        .iter()
+        .filter_map(|entry| Some((entry.id.as_str(), entry.source_quotes.as_deref()?)))
+        .collect();
+    let expected: HashMap<String, String> = translations
+        .iter()
+        .filter(|t| !t.translated_text.is_empty())
+        .map(|t| {
+            let text = restore_translation_quotes(
+                t.translated_text.clone(),
+                request.target_language.as_deref(),
+                request.quote_policy,
+                source_quotes.get(t.id.as_str()).copied(),
+            );
+            (t.id.clone(), text)
+        })
+        .collect();
+
+    for mismatch in check_round_trip(before, &after, &expected) {
+        let message = match &mismatch.mismatch {
+            RoundTripMismatch::MissingLocation { .. } => {
+                format!("{}: emplacement introuvable après l'injection", mismatch.location)
+            }
+            RoundTripMismatch::WrongTranslation {
+                expected, actual, ..
+            } => format!(
+                "{}: la traduction injectée n'a pas été retrouvée (attendu « {} », lu « {} »)",
+                mismatch.location, expected, actual
+            ),
+            RoundTripMismatch::UnexpectedChange { before, after, .. } => format!(
+                "{}: texte non traduit modifié par l'injection (« {} » devenu « {} »)",
+                mismatch.location, before, after
+            ),
+        };
+        issues.push(error(mismatch.file_path.unwrap_or_default(), message));
+    }
+
+    issues
+}
+
+/// Check translations against length budgets, using extracted locations
+fn find_over_budget_entries(
+    extracted: &HashMap<String, TextEntry>,
+    translations: &[TranslationEntryInput],
+    budgets: &[LengthBudget],
+) -> Vec<OverBudgetEntry> {
+    translations
+        .iter()
+        .filter(|t| !t.translated_text.is_empty())
+        .filter_map(|t| {
+            let entry = extracted.get(&t.id)?;
+            let violation = check_length_budget(&entry.location, &t.translated_text, budgets)?;
+            Some(OverBudgetEntry {
+                entry_id: t.id.clone(),
+                translated_text: t.translated_text.clone(),
+                violation,
+            })
+        })
+        .collect()
+}
+
+/// Check translated messages against their message window once re-flowed
+///
+/// Word wrap continues a message needing more lines than its window shows in a new
+/// window; such messages are reported on their first translated line, to be shortened.
+fn find_message_overflow_entries(
     handler: &dyn GameEngineHandler,
-    translations: Vec<TranslationEntry>,
+    game_path: &Path,
+    extracted: &HashMap<String, TextEntry>,
+    translations: &[TranslationEntryInput],
+    request: &ValidationRequest,
+    word_wrap: &WordWrapSettings,
+) -> Result<Vec<OverBudgetEntry>, String> {
+    let injected: Vec<TranslationEntry> = translations
+        .iter()
+        .filter(|t| !t.translated_text.is_empty())
+        .map(|t| TranslationEntry {
+            id: t.id.clone(),
+            translated_text: restore_translation_quotes(
+                t.translated_text.clone(),
+                request.target_language.as_deref(),
+                request.quote_policy,
+                extracted
+                    .get(&t.id)
+                    .and_then(|entry| entry.source_quotes.as_deref()),
+            ),
+        })
+        .collect();
+
+    let overflows = handler
+        .find_message_overflows(game_path, &injected, word_wrap)
+        .map_err(|e| format!("Erreur lors du contrôle des fenêtres de message: {}", e))?;
+    Ok(overflows
+        .into_iter()
+        .map(|(entry_id, violation)| {
+            let translated_text = translations
+                .iter()
+                .find(|t| t.id == entry_id)
+                .map(|t| t.translated_text.clone())
+                .unwrap_or_default();
+            OverBudgetEntry {
+                entry_id,
+                translated_text,
+                violation,
+            }
+        })
+        .collect())
+}
+
+/// Find translations still containing source-language text or copied from the source
+fn find_retranslation_entries(
+    extracted: &HashMap<String, TextEntry>,
+    translations: &[TranslationEntryInput],
+    target_language: &str,
+) -> Vec<RetranslationEntry> {
+    translations
+        .iter()
+        .filter_map(|t| {
+            let entry = extracted.get(&t.id)?;
+            let reason = check_translation(&entry.source_text, &t.translated_text, target_language)?;
+            Some(RetranslationEntry {
+                entry_id: t.id.clone(),
+                translated_text: t.translated_text.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Get the default length budgets of the game engine detected at game_path
+#[tauri::command]
+pub fn get_default_length_budgets(game_path: String) -> Result<Vec<LengthBudget>, String> {
+    let handler = EngineFactory::create_handler(Path::new(&game_path))?;
+    Ok(handler.default_length_budgets())
+}
+
+/// Injection running on a background task
+///
+/// Holds the cancellation flag of the injection and reports every progress update.
+struct InjectionRun {
     injection_id: String,
-    state: State<'_, InjectionState>,
-) {
-    // Update progress
-    {
-        let mut injections = state.current_injections.lock().unwrap();
-        if let Some(progress) = injections.get_mut(&injection_id) {
-            progress.status = InjectionStatus::InProgress;
-            progress.total_files = handler.count_files_to_process(game_path);
+    state: InjectionState,
+    cancelled: Arc<AtomicBool>,
+    on_progress: Box<dyn Fn(&InjectionProgress) + Send>,
+}
+
+impl InjectionRun {
+    /// Register the cancellation flag of an injection whose progress is in `state`
+    fn new(
+        state: &InjectionState,
+        injection_id: &str,
+        on_progress: impl Fn(&InjectionProgress) + Send + 'static,
+    ) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        state
+            .cancellations
+            .lock()
+            .unwrap()
+            .insert(injection_id.to_string(), cancelled.clone());
+        Self {
+            injection_id: injection_id.to_string(),
+            state: state.clone(),
+            cancelled,
+            on_progress: Box::new(on_progress),
         }
     }
 
-    // Perform injection using handler
-    match handler.inject_all_texts(game_path, &translations) {
+    fn update(&self, update: impl FnOnce(&mut InjectionProgress)) {
+        let progress = {
+            let mut injections = self.state.current_injections.lock().unwrap();
+            injections.get_mut(&self.injection_id).map(|progress| {
+                update(progress);
+                progress.clone()
+            })
+        };
+        if let Some(progress) = progress {
+            (self.on_progress)(&progress);
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err("Injection annulée".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Drop for InjectionRun {
+    fn drop(&mut self) {
+        self.state
+            .cancellations
+            .lock()
+            .unwrap()
+            .remove(&self.injection_id);
+    }
+}
+
+/// Run an injection to its end and record its outcome in the progress
+///
+/// Translations are written into a staging copy of the game files, which replaces the
+/// originals only once every file has been written and synced: a failed or cancelled
+/// injection leaves them untouched.
+fn perform_injection(
+    game_path: &Path,
+    output_path: Option<&Path>,
+    handler: &dyn GameEngineHandler,
+    files: &[PathBuf],
+    translations: Vec<TranslationEntry>,
+    options: &InjectionOptions,
+    run: &InjectionRun,
+) {
+    run.update(|progress| {
+        progress.status = InjectionStatus::InProgress;
+        progress.total_files = files.len();
+    });
+
+    let result = run_injection_phases(
+        game_path,
+        output_path,
+        handler,
+        files,
+        &translations,
+        options,
+        run,
+    );
+
+    run.update(|progress| match result {
         Ok(()) => {
-            let mut injections = state.current_injections.lock().unwrap();
-            if let Some(progress) = injections.get_mut(&injection_id) {
-                progress.status = InjectionStatus::Completed;
-                progress.files_processed = progress.total_files;
-                progress.entries_injected = translations.len();
-            }
+            progress.status = InjectionStatus::Completed;
+            progress.phase = InjectionPhase::Completed;
+            progress.files_processed = progress.total_files;
+            progress.entries_injected = translations.len();
+        }
+        // Cancellation is only checked before committing
+        Err(_) if run.is_cancelled() && progress.phase != InjectionPhase::Committing => {
+            progress.status = InjectionStatus::Cancelled;
         }
         Err(e) => {
-            let mut injections = state.current_injections.lock().unwrap();
-            if let Some(progress) = injections.get_mut(&injection_id) {
-                progress.status = InjectionStatus::Failed;
-                progress.errors.push(InjectionError {
-                    file_path: game_path.display().to_string(),
-                    error_message: e,
-                });
-            }
+            progress.status = InjectionStatus::Failed;
+            progress.errors.push(InjectionError {
+                file_path: game_path.display().to_string(),
+                error_message: e,
+            });
+        }
+    });
+}
+
+/// Run the injection phases, recording the current phase and file in the progress
+///
+/// Translations are injected into the game files as they were before any injection
+/// (the original copies in the backups), as word wrap may have shifted the ids of an
+/// injected file. With an output folder, `files` are staged from the game and committed
+/// into it at the same relative paths: only the files that differ from the previous
+/// output are rewritten, and the game itself is never modified. Cancellation is checked
+/// after each injected file and before committing.
+fn run_injection_phases(
+    game_path: &Path,
+    output_path: Option<&Path>,
+    handler: &dyn GameEngineHandler,
+    files: &[PathBuf],
+    translations: &[TranslationEntry],
+    options: &InjectionOptions,
+    run: &InjectionRun,
+) -> Result<(), String> {
+    let set_phase = |phase: InjectionPhase| {
+        run.update(|progress| progress.phase = phase);
+    };
+
+    // Snapshot every game file the injection will touch before writing; an output
+    // folder is regenerated from the game and needs none
+    set_phase(InjectionPhase::Backup);
+    let backup = match output_path {
+        Some(_) => None,
+        None => {
+            let backup = backup::create_backup(game_path, files, Some(&run.injection_id))
+                .map_err(|e| format!("Impossible de sauvegarder les fichiers du jeu: {}", e))?;
+            run.update(|progress| {
+                progress.backup_id = Some(backup.id.clone());
+            });
+            Some(backup)
         }
+    };
+
+    set_phase(InjectionPhase::Staging);
+    let target_path = output_path.unwrap_or(game_path);
+    let staged = StagedFiles::stage_to(game_path, target_path, files, &run.injection_id)
+        .and_then(|staged| {
+            staged.restore_originals(&backup::original_files(game_path)?)?;
+            Ok(staged)
+        })
+        .map_err(|e| format!("Impossible de préparer les fichiers: {}", e))?;
+
+    set_phase(InjectionPhase::Injecting);
+    handler.inject_all_texts_with_progress(staged.root(), translations, options, &mut |file| {
+        run.update(|progress| {
+            progress.current_file = file.to_string();
+            progress.files_processed = (progress.files_processed + 1).min(progress.total_files);
+        });
+        run.check_cancelled()
+    })?;
+
+    set_phase(InjectionPhase::Syncing);
+    let changed = staged
+        .changed_files()
+        .and_then(|changed| staged.sync(&changed).map(|()| changed))
+        .map_err(|e| format!("Impossible d'enregistrer les fichiers modifiés: {}", e))?;
+
+    // Last chance to cancel: once committing starts, the injection runs to its end
+    run.check_cancelled()?;
+    set_phase(InjectionPhase::Committing);
+    if let Err(e) = staged.commit(&changed) {
+        let Some(backup) = backup else {
+            return Err(format!(
+                "Impossible d'écrire les fichiers dans le dossier de sortie: {}",
+                e
+            ));
+        };
+        // Undo the files already replaced
+        let restored = backup::restore_backup(game_path, &backup.id);
+        return Err(match restored {
+            Ok(_) => format!("Impossible de remplacer les fichiers du jeu (fichiers restaurés): {}", e),
+            Err(restore_error) => format!(
+                "Impossible de remplacer les fichiers du jeu: {}. La restauration de la sauvegarde {} a échoué: {}",
+                e, backup.id, restore_error
+            ),
+        });
     }
+
+    // The output folder mirrors the data root: drop the files the game no longer has
+    let data_root = handler.get_data_root(game_path);
+    if let (Some(_), Ok(relative_root)) = (output_path, data_root.strip_prefix(game_path)) {
+        staged.remove_unstaged(relative_root).map_err(|e| {
+            format!("Impossible de supprimer les anciens fichiers du dossier de sortie: {}", e)
+        })?;
+    }
+
+    Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::rpg_maker::handler::RpgMakerHandler;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn start_progress(state: &InjectionState, injection_id: &str) {
+        state.current_injections.lock().unwrap().insert(
+            injection_id.to_string(),
+            InjectionProgress {
+                injection_id: injection_id.to_string(),
+                current_file: String::new(),
+                files_processed: 0,
+                total_files: 0,
+                entries_injected: 0,
+                errors: Vec::new(),
+                status: InjectionStatus::InProgress,
+                phase: InjectionPhase::Backup,
+                backup_id: None,
+            },
+        );
+    }
+
+    fn create_game(actors: &str, map: &str) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        fs::create_dir_all(&data_dir).unwrap();
+        fs::write(data_dir.join("Actors.json"), actors).unwrap();
+        fs::write(data_dir.join("Map001.json"), map).unwrap();
+        temp_dir
+    }
+
+    fn translations() -> Vec<TranslationEntry> {
+        vec![
+            TranslationEntry {
+                id: "actor_1_name".to_string(),
+                translated_text: "Héros".to_string(),
+            },
+            TranslationEntry {
+                id: "map_1_display_name".to_string(),
+                translated_text: "Village".to_string(),
+            },
+        ]
+    }
+
+    const ACTORS: &str = "[\nnull,\n{\"id\":1,\"name\":\"勇者\",\"nickname\":\"\",\"profile\":\"\"}\n]";
+
+    #[test]
+    fn test_injection_commits_all_files() {
+        let game = create_game(ACTORS, "{\"displayName\":\"村\",\"events\":[]}");
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let files = handler.list_files_to_process(game_path);
+        let state = InjectionState::default();
+        start_progress(&state, "inj_ok");
+
+        let result = run_injection_phases(
+            game_path,
+            None,
+            &handler,
+            &files,
+            &translations(),
+            &InjectionOptions::default(),
+            &InjectionRun::new(&state, "inj_ok", |_| {}),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+
+        let actors = fs::read_to_string(game_path.join("data/Actors.json")).unwrap();
+        let map = fs::read_to_string(game_path.join("data/Map001.json")).unwrap();
+        assert!(actors.contains("Héros"));
+        assert!(map.contains("Village"));
+        assert!(!game_path.join(crate::core::staging::STAGING_DIR_NAME).exists());
+
+        let progress = &state.current_injections.lock().unwrap()["inj_ok"];
+        assert!(progress.backup_id.is_some());
+    }
+
+    #[test]
+    fn test_failed_injection_leaves_game_untouched() {
+        // Malformed map: Actors.json is injected first, then the map fails
+        let malformed_map = "{\"displayName\":\"村\",\"events\":[";
+        let game = create_game(ACTORS, malformed_map);
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let files = handler.list_files_to_process(game_path);
+        let state = InjectionState::default();
+        start_progress(&state, "inj_fail");
+
+        let result = run_injection_phases(
+            game_path,
+            None,
+            &handler,
+            &files,
+            &translations(),
+            &InjectionOptions::default(),
+            &InjectionRun::new(&state, "inj_fail", |_| {}),
+        );
+        assert!(result.is_err());
+
+        assert_eq!(
+            fs::read_to_string(game_path.join("data/Actors.json")).unwrap(),
+            ACTORS
+        );
+        assert_eq!(
+            fs::read_to_string(game_path.join("data/Map001.json")).unwrap(),
+            malformed_map
+        );
+        assert!(!game_path.join(crate::core::staging::STAGING_DIR_NAME).exists());
+
+        let progress = &state.current_injections.lock().unwrap()["inj_fail"];
+        assert_eq!(progress.phase, InjectionPhase::Injecting);
+    }
+
+    #[test]
+    fn test_injection_reports_progress_per_file() {
+        let game = create_game(ACTORS, "{\"displayName\":\"村\",\"events\":[]}");
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let files = handler.list_files_to_process(game_path);
+        let state = InjectionState::default();
+        start_progress(&state, "inj_events");
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let run = InjectionRun::new(&state, "inj_events", move |progress| {
+            recorded.lock().unwrap().push(progress.clone());
+        });
+        perform_injection(
+            game_path,
+            None,
+            &handler,
+            &files,
+            translations(),
+            &InjectionOptions::default(),
+            &run,
+        );
+        drop(run);
+
+        let events = events.lock().unwrap();
+        let injected: Vec<&str> = events
+            .iter()
+            .filter(|progress| progress.phase == InjectionPhase::Injecting)
+            .map(|progress| progress.current_file.as_str())
+            .filter(|file| !file.is_empty())
+            .collect();
+        assert_eq!(injected, vec!["data/Actors.json", "data/Map001.json"]);
+        let last = events.last().unwrap();
+        assert!(matches!(last.status, InjectionStatus::Completed));
+        assert_eq!(last.files_processed, files.len());
+        assert!(state.cancellations.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cancelled_injection_leaves_game_untouched() {
+        let map = "{\"displayName\":\"村\",\"events\":[]}";
+        let game = create_game(ACTORS, map);
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let files = handler.list_files_to_process(game_path);
+        let state = InjectionState::default();
+        start_progress(&state, "inj_cancel");
+
+        // Cancel once the first file is injected
+        let canceller = state.clone();
+        let run = InjectionRun::new(&state, "inj_cancel", move |progress| {
+            if !progress.current_file.is_empty() {
+                let _ = canceller.cancel("inj_cancel");
+            }
+        });
+        perform_injection(
+            game_path,
+            None,
+            &handler,
+            &files,
+            translations(),
+            &InjectionOptions::default(),
+            &run,
+        );
+        drop(run);
+
+        assert_eq!(
+            fs::read_to_string(game_path.join("data/Actors.json")).unwrap(),
+            ACTORS
+        );
+        assert_eq!(fs::read_to_string(game_path.join("data/Map001.json")).unwrap(), map);
+        assert!(!game_path.join(crate::core::staging::STAGING_DIR_NAME).exists());
+
+        let progress = state.current_injections.lock().unwrap()["inj_cancel"].clone();
+        assert!(matches!(progress.status, InjectionStatus::Cancelled));
+        assert_eq!(progress.current_file, "data/Actors.json");
+        assert!(progress.errors.is_empty());
+        assert_eq!(
+            state.cancel("inj_cancel"),
+            Err("Injection already finished".to_string())
+        );
+    }
+
+    fn injection_request(game_path: &Path, filters: InjectionFilters) -> InjectionRequest {
+        InjectionRequest {
+            project_id: 1,
+            game_path: game_path.display().to_string(),
+            translations: translations()
+                .into_iter()
+                .map(|t| TranslationEntryInput {
+                    status: Some(if t.id.starts_with("map_") {
+                        ReviewStatus::Reviewed
+                    } else {
+                        ReviewStatus::Translated
+                    }),
+                    id: t.id,
+                    translated_text: t.translated_text,
+                })
+                .collect(),
+            file_ids: None,
+            target_language: None,
+            quote_policy: None,
+            word_wrap: None,
+            output_path: None,
+            filters: Some(filters),
+        }
+    }
+
+    #[test]
+    fn test_selective_injection_filters() {
+        let map = "{\"displayName\":\"村\",\"events\":[]}";
+        let game = create_game(ACTORS, map);
+        let game_path = game.path();
+        fs::write(game_path.join("package.json"), "{}").unwrap();
+
+        let selected_ids = |filters: InjectionFilters| -> Vec<String> {
+            let (_, translations, _) =
+                prepare_injection(injection_request(game_path, filters)).unwrap();
+            translations.into_iter().map(|t| t.id).collect()
+        };
+        assert_eq!(
+            selected_ids(InjectionFilters {
+                location_prefix: Some("map:1:".to_string()),
+                ..Default::default()
+            }),
+            vec!["map_1_display_name"]
+        );
+        assert_eq!(
+            selected_ids(InjectionFilters {
+                text_types: Some(vec![PromptType::Character]),
+                ..Default::default()
+            }),
+            vec!["actor_1_name"]
+        );
+        assert_eq!(
+            selected_ids(InjectionFilters {
+                min_status: Some(ReviewStatus::Reviewed),
+                ..Default::default()
+            }),
+            vec!["map_1_display_name"]
+        );
+
+        // Only the selected file is written, even with translations for the others
+        let (handler, _, options) = prepare_injection(injection_request(
+            game_path,
+            InjectionFilters {
+                files: Some(vec!["data/Actors.json".to_string()]),
+                ..Default::default()
+            },
+        ))
+        .unwrap();
+        handler
+            .inject_all_texts(game_path, &translations(), &options)
+            .unwrap();
+        assert!(fs::read_to_string(game_path.join("data/Actors.json"))
+            .unwrap()
+            .contains("Héros"));
+        assert_eq!(fs::read_to_string(game_path.join("data/Map001.json")).unwrap(), map);
+    }
+
+    #[test]
+    fn test_dry_run_reports_changes_without_writing() {
+        let map = "{\"displayName\":\"村\",\"events\":[]}";
+        let game = create_game(ACTORS, map);
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let mut translations = translations();
+        translations.push(TranslationEntry {
+            id: "actor_7_name".to_string(),
+            translated_text: "Fantôme".to_string(),
+        });
+
+        let report = handler
+            .dry_run_inject_all_texts(game_path, &translations, &InjectionOptions::default())
+            .unwrap();
+
+        let files: Vec<&str> = report.files.iter().map(|f| f.file_path.as_str()).collect();
+        assert_eq!(files, vec!["data/Actors.json", "data/Map001.json"]);
+        let actor_change = &report.files[0].changes[0];
+        assert_eq!(actor_change.entry_id, "actor_1_name");
+        assert_eq!(actor_change.old_text, "勇者");
+        assert_eq!(actor_change.new_text, "Héros");
+        assert_eq!(report.orphan_translations, vec!["actor_7_name".to_string()]);
+
+        // Nothing written
+        assert_eq!(
+            fs::read_to_string(game_path.join("data/Actors.json")).unwrap(),
+            ACTORS
+        );
+        assert_eq!(
+            fs::read_to_string(game_path.join("data/Map001.json")).unwrap(),
+            map
+        );
+        assert!(!game_path.join(crate::core::staging::STAGING_DIR_NAME).exists());
+    }
+
+    #[test]
+    fn test_injection_into_output_folder_leaves_game_untouched() {
+        let map = "{\"displayName\":\"村\",\"events\":[]}";
+        let game = create_game(ACTORS, map);
+        let game_path = game.path();
+        let output = TempDir::new().unwrap();
+        let output_path = output.path().join("translated");
+        let handler = RpgMakerHandler::new_mz();
+        fs::write(game_path.join("data/System.png"), "not json").unwrap();
+
+        let resolved =
+            resolve_output_path(&handler, game_path, output_path.to_str()).unwrap();
+        assert_eq!(resolved.as_deref(), Some(output_path.as_path()));
+        let files = handler.list_data_root_files(game_path);
+        assert_eq!(files.len(), 3);
+
+        let state = InjectionState::default();
+        start_progress(&state, "inj_out");
+        run_injection_phases(
+            game_path,
+            Some(&output_path),
+            &handler,
+            &files,
+            &translations(),
+            &InjectionOptions::default(),
+            &InjectionRun::new(&state, "inj_out", |_| {}),
+        )
+        .unwrap();
+
+        // Game untouched, no backup taken
+        assert_eq!(
+            fs::read_to_string(game_path.join("data/Actors.json")).unwrap(),
+            ACTORS
+        );
+        assert!(!game_path.join(crate::core::backup::BACKUP_DIR_NAME).exists());
+        assert!(state.current_injections.lock().unwrap()["inj_out"]
+            .backup_id
+            .is_none());
+
+        let actors_path = output_path.join("data/Actors.json");
+        assert!(fs::read_to_string(&actors_path).unwrap().contains("Héros"));
+        assert_eq!(
+            fs::read_to_string(output_path.join("data/System.png")).unwrap(),
+            "not json"
+        );
+        assert!(!output_path.join(crate::core::staging::STAGING_DIR_NAME).exists());
+
+        // A second run with one more translation only rewrites the map, and drops the
+        // file removed from the game since
+        let actors_modified = fs::metadata(&actors_path).unwrap().modified().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let mut translations = translations();
+        translations[1].translated_text = "Hameau".to_string();
+        fs::remove_file(game_path.join("data/System.png")).unwrap();
+        run_injection_phases(
+            game_path,
+            Some(&output_path),
+            &handler,
+            &handler.list_data_root_files(game_path),
+            &translations,
+            &InjectionOptions::default(),
+            &InjectionRun::new(&state, "inj_out", |_| {}),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::metadata(&actors_path).unwrap().modified().unwrap(),
+            actors_modified
+        );
+        assert!(fs::read_to_string(output_path.join("data/Map001.json"))
+            .unwrap()
+            .contains("Hameau"));
+        assert_eq!(fs::read_to_string(game_path.join("data/Map001.json")).unwrap(), map);
+        assert!(!output_path.join("data/System.png").exists());
+    }
+
+    #[test]
+    fn test_output_folder_cannot_be_inside_data_root() {
+        let game = create_game(ACTORS, "{\"events\":[]}");
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let inside = game_path.join("data/translated");
+
+        assert!(resolve_output_path(&handler, game_path, inside.to_str()).is_err());
+        assert_eq!(
+            resolve_output_path(&handler, game_path, game_path.to_str()).unwrap(),
+            None
+        );
+        assert_eq!(resolve_output_path(&handler, game_path, Some("  ")).unwrap(), None);
+    }
+
+    fn validation_request(game_path: &Path, backup_id: Option<String>) -> ValidationRequest {
+        ValidationRequest {
+            project_id: 1,
+            game_path: game_path.display().to_string(),
+            total_translations: 2,
+            translated_count: 2,
+            untranslated_count: 0,
+            file_ids: None,
+            translations: Some(
+                translations()
+                    .into_iter()
+                    .map(|t| TranslationEntryInput {
+                        id: t.id,
+                        translated_text: t.translated_text,
+                        status: None,
+                    })
+                    .collect(),
+            ),
+            length_budgets: None,
+            target_language: Some("fr".to_string()),
+            quote_policy: None,
+            word_wrap: None,
+            backup_id,
+            filters: None,
+        }
+    }
+
+    const MESSAGE_MAP: &str = "{\"displayName\":\"\",\"events\":[null,{\"id\":1,\"name\":\"EV001\",\"pages\":[{\"list\":[{\"code\":101,\"indent\":0,\"parameters\":[\"\",0,0,2]},{\"code\":401,\"indent\":0,\"parameters\":[\"ようこそ\"]},{\"code\":0,\"indent\":0,\"parameters\":[]}]}]}]}";
+
+    #[test]
+    fn test_validation_reports_overflowing_messages() {
+        let game = create_game(ACTORS, MESSAGE_MAP);
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let extracted: HashMap<String, TextEntry> = handler
+            .extract_all_texts(game_path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.id.clone(), entry))
+            .collect();
+        let message_id = "map_1_event_1_1_message_1";
+        assert!(extracted.contains_key(message_id), "{:?}", extracted.keys());
+
+        let translations = vec![TranslationEntryInput {
+            id: message_id.to_string(),
+            translated_text: "Bienvenue dans notre humble village, voyageur".to_string(),
+            status: None,
+        }];
+        let request = validation_request(game_path, None);
+        let word_wrap = WordWrapSettings {
+            max_width: 16,
+            face_max_width: 16,
+            max_lines: 2,
+            font_metrics: None,
+        };
+
+        let entries = find_message_overflow_entries(
+            &handler,
+            game_path,
+            &extracted,
+            &translations,
+            &request,
+            &word_wrap,
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 1, "{:?}", entries);
+        assert_eq!(entries[0].entry_id, message_id);
+        assert_eq!(entries[0].violation.location, "map:1:event:1:message:1");
+        assert_eq!(entries[0].violation.location, extracted[message_id].location);
+        assert_eq!(entries[0].violation.max_lines, Some(2));
+        assert!(entries[0].violation.actual_lines > 2);
+
+        // A wider window fits the message, and the check never writes into the game
+        let word_wrap = WordWrapSettings {
+            max_width: 60,
+            ..word_wrap
+        };
+        let entries = find_message_overflow_entries(
+            &handler,
+            game_path,
+            &extracted,
+            &translations,
+            &request,
+            &word_wrap,
+        )
+        .unwrap();
+        assert!(entries.is_empty(), "{:?}", entries);
+        assert_eq!(
+            fs::read_to_string(game_path.join("data/Map001.json")).unwrap(),
+            MESSAGE_MAP
+        );
+    }
+
+    #[test]
+    fn test_round_trip_verification_after_word_wrap() {
+        // Two messages: the first one is continued in a new window, shifting the second
+        let map = "{\"displayName\":\"\",\"events\":[null,{\"id\":1,\"name\":\"EV001\",\"pages\":[{\"list\":[{\"code\":101,\"indent\":0,\"parameters\":[\"\",0,0,2]},{\"code\":401,\"indent\":0,\"parameters\":[\"ようこそ\"]},{\"code\":101,\"indent\":0,\"parameters\":[\"\",0,0,2]},{\"code\":401,\"indent\":0,\"parameters\":[\"さようなら\"]},{\"code\":0,\"indent\":0,\"parameters\":[]}]}]}]}";
+        let game = create_game(ACTORS, map);
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let message_id = "map_1_event_1_1_message_1";
+        let farewell_id = "map_1_event_1_1_message_3";
+        let translation = "Bienvenue dans notre village anticonstitutionnellement accueillant";
+        let translations = vec![
+            TranslationEntry {
+                id: message_id.to_string(),
+                translated_text: translation.to_string(),
+            },
+            TranslationEntry {
+                id: farewell_id.to_string(),
+                translated_text: "Au revoir".to_string(),
+            },
+        ];
+        let word_wrap = WordWrapSettings {
+            max_width: 16,
+            face_max_width: 16,
+            max_lines: 2,
+            font_metrics: None,
+        };
+        let options = InjectionOptions {
+            word_wrap: Some(word_wrap.clone()),
+            files: None,
+        };
+
+        // Injecting again starts from the original files, whose ids the translations use
+        let state = InjectionState::default();
+        for injection_id in ["inj_wrap_1", "inj_wrap_2"] {
+            start_progress(&state, injection_id);
+            run_injection_phases(
+                game_path,
+                None,
+                &handler,
+                &handler.list_files_to_process(game_path),
+                &translations,
+                &options,
+                &InjectionRun::new(&state, injection_id, |_| {}),
+            )
+            .unwrap();
+        }
+        let backup_id = state.current_injections.lock().unwrap()["inj_wrap_2"]
+            .backup_id
+            .clone();
+
+        let map: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(game_path.join("data/Map001.json")).unwrap())
+                .unwrap();
+        let commands = map["events"][1]["pages"][0]["list"].as_array().unwrap();
+        let codes: Vec<i64> = commands.iter().map(|c| c["code"].as_i64().unwrap()).collect();
+        assert_eq!(codes, vec![101, 401, 401, 101, 401, 401, 101, 401, 101, 401, 0]);
+        assert_eq!(commands[9]["parameters"][0], "Au revoir");
+
+        let mut request = validation_request(game_path, backup_id);
+        request.translations = Some(
+            translations
+                .iter()
+                .map(|t| TranslationEntryInput {
+                    id: t.id.clone(),
+                    translated_text: t.translated_text.clone(),
+                    status: None,
+                })
+                .collect(),
+        );
+        let original_texts = extract_original_texts(&handler, game_path).unwrap();
+        assert!(original_texts
+            .iter()
+            .any(|entry| entry.id == farewell_id && entry.source_text == "さようなら"));
+        let issues = verify_injected_game(
+            &handler,
+            game_path,
+            &original_texts,
+            request.translations.as_deref().unwrap(),
+            &request,
+        );
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!(!game_path.join(crate::core::staging::STAGING_DIR_NAME).exists());
+
+        // The message continued in a new window is reported, at its original id
+        let extracted: HashMap<String, TextEntry> = original_texts
+            .into_iter()
+            .map(|entry| (entry.id.clone(), entry))
+            .collect();
+        let staging_dir = TempDir::new().unwrap();
+        let originals = stage_game_files(&handler, game_path, staging_dir.path(), "test").unwrap();
+        let overflows = find_message_overflow_entries(
+            &handler,
+            originals.root(),
+            &extracted,
+            request.translations.as_deref().unwrap(),
+            &request,
+            &word_wrap,
+        )
+        .unwrap();
+        assert_eq!(overflows.len(), 1, "{:?}", overflows);
+        assert_eq!(overflows[0].entry_id, message_id);
+    }
+
+    #[test]
+    fn test_round_trip_verification_after_injection() {
+        let game = create_game(ACTORS, "{\"displayName\":\"村\",\"events\":[]}");
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let files = handler.list_files_to_process(game_path);
+        let state = InjectionState::default();
+        start_progress(&state, "inj_verify");
+        run_injection_phases(
+            game_path,
+            None,
+            &handler,
+            &files,
+            &translations(),
+            &InjectionOptions::default(),
+            &InjectionRun::new(&state, "inj_verify", |_| {}),
+        )
+        .unwrap();
+        let backup_id = state.current_injections.lock().unwrap()["inj_verify"]
+            .backup_id
+            .clone();
+        let request = validation_request(game_path, backup_id);
+        let translations = request.translations.clone().unwrap();
+
+        // The original texts come from the backup
+        let original_texts = extract_original_texts(&handler, game_path).unwrap();
+        assert!(original_texts
+            .iter()
+            .any(|entry| entry.id == "actor_1_name" && entry.source_text == "勇者"));
+        let issues =
+            verify_injected_game(&handler, game_path, &original_texts, &translations, &request);
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!(!game_path.join(crate::core::staging::STAGING_DIR_NAME).exists());
+
+        // The map lost its translation and the actors file no longer parses
+        fs::write(
+            game_path.join("data/Map001.json"),
+            "{\"displayName\":\"Hameau\",\"events\":[]}",
+        )
+        .unwrap();
+        let issues =
+            verify_injected_game(&handler, game_path, &original_texts, &translations, &request);
+        assert_eq!(issues.len(), 1, "{:?}", issues);
+        assert!(issues[0].message.contains("Village"));
+
+        fs::write(game_path.join("data/Actors.json"), "[null,").unwrap();
+        let issues =
+            verify_injected_game(&handler, game_path, &original_texts, &translations, &request);
+        assert!(issues
+            .iter()
+            .any(|issue| issue.file_path.ends_with("Actors.json")
+                && issue.message.contains("ne se charge plus")));
+    }
+
+    #[test]
+    fn test_round_trip_verification_after_selective_injection() {
+        let game = create_game(ACTORS, "{\"displayName\":\"村\",\"events\":[]}");
+        let game_path = game.path();
+        let handler = RpgMakerHandler::new_mz();
+        let filters = InjectionFilters {
+            files: Some(vec!["data/Actors.json".to_string()]),
+            ..Default::default()
+        };
+        let state = InjectionState::default();
+        start_progress(&state, "inj_selective");
+        run_injection_phases(
+            game_path,
+            None,
+            &handler,
+            &handler.list_files_to_process(game_path),
+            &translations(),
+            &InjectionOptions {
+                word_wrap: None,
+                files: filters.files.clone(),
+            },
+            &InjectionRun::new(&state, "inj_selective", |_| {}),
+        )
+        .unwrap();
+        let backup_id = state.current_injections.lock().unwrap()["inj_selective"]
+            .backup_id
+            .clone();
+        let original_texts = extract_original_texts(&handler, game_path).unwrap();
+
+        // The map translation was left out by the filters, not lost
+        let mut request = validation_request(game_path, backup_id);
+        request.filters = Some(filters);
+        let translations = selected_translations(&request, &original_texts);
+        assert_eq!(translations.len(), 1);
+        assert_eq!(translations[0].id, "actor_1_name");
+        let issues =
+            verify_injected_game(&handler, game_path, &original_texts, &translations, &request);
+        assert!(issues.is_empty(), "{:?}", issues);
+
+        request.filters = None;
+        let translations = selected_translations(&request, &original_texts);
+        let issues =
+            verify_injected_game(&handler, game_path, &original_texts, &translations, &request);
+        assert_eq!(issues.len(), 1, "{:?}", issues);
+        assert!(issues[0].message.contains("Village"));
+    }
+}
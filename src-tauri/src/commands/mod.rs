@@ -2,6 +2,7 @@
 // This module contains Tauri commands exposed to the frontend
 
 pub mod injection;
+pub mod patch;
 pub mod projects;
 pub mod scanning;
 pub mod translation;
@@ -10,6 +11,7 @@ pub mod validation;
 
 // Re-export all commands
 pub use injection::*;
+pub use patch::*;
 pub use projects::*;
 pub use scanning::*;
 pub use translation::*;
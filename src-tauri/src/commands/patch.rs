@@ -0,0 +1,345 @@
+// Patch commands for distributing translations
+// Packages the game files changed by injection so end users can patch their own copy,
+// and verifies, applies or reverts such packages on a game folder
+
+use crate::core::backup;
+use crate::core::patch::apply::{
+    self, FileCheckStatus, PatchApplyResult, PatchPackage, PatchVerification,
+};
+use crate::core::patch::{self, PatchExportOptions, PatchManifest};
+use crate::parsers::factory::EngineFactory;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchExportRequest {
+    pub game_path: String,
+    /// Translated copy made by an injection into an output folder; the game folder then
+    /// holds the original files
+    pub output_path: Option<String>,
+    /// Backup holding the original files of a game injected in place (defaults to all
+    /// backups, taking each file from the oldest backup that holds it)
+    pub backup_id: Option<String>,
+    /// Zip archive to create
+    pub package_path: String,
+    /// Store files at least this large as binary deltas
+    pub delta_min_size: Option<u64>,
+}
+
+/// Export the files changed by injection as a patch package
+#[tauri::command]
+pub fn export_patch_package(request: PatchExportRequest) -> Result<PatchManifest, String> {
+    let game_path = Path::new(&request.game_path);
+    if !game_path.is_dir() {
+        return Err(format!(
+            "Le chemin du jeu n'existe pas ou n'est pas un dossier: {}",
+            request.game_path
+        ));
+    }
+    let handler = EngineFactory::create_handler(game_path)
+        .map_err(|e| format!("Impossible de détecter le moteur de jeu: {}", e))?;
+
+    let (originals, patched_root) = match request.output_path.as_deref() {
+        Some(output_path) if !output_path.trim().is_empty() => {
+            let output_path = PathBuf::from(output_path);
+            if !output_path.is_dir() {
+                return Err(format!(
+                    "Le dossier de sortie n'existe pas: {}",
+                    output_path.display()
+                ));
+            }
+            let originals = handler
+                .list_data_root_files(&output_path)
+                .into_iter()
+                .filter_map(|file| file.strip_prefix(&output_path).ok().map(Path::to_path_buf))
+                .map(|relative| {
+                    let original = game_path.join(&relative);
+                    (relative, original)
+                })
+                .collect();
+            (originals, output_path)
+        }
+        _ => {
+            let originals = match request.backup_id {
+                Some(backup_id) => {
+                    let (backup_dir, files) = backup::backup_contents(game_path, &backup_id)
+                        .map_err(|e| {
+                            format!("Impossible de lire la sauvegarde {}: {}", backup_id, e)
+                        })?;
+                    files
+                        .into_iter()
+                        .map(|relative| {
+                            let original = backup_dir.join(&relative);
+                            (relative, original)
+                        })
+                        .collect()
+                }
+                None => backup::original_files(game_path)
+                    .map_err(|e| format!("Impossible de lire les sauvegardes: {}", e))?,
+            };
+            if originals.is_empty() {
+                return Err("Aucune sauvegarde des fichiers originaux: injectez les traductions avant d'exporter un patch".to_string());
+            }
+            (originals, game_path.to_path_buf())
+        }
+    };
+
+    let options = PatchExportOptions {
+        engine: handler.engine_name().to_string(),
+        delta_min_size: request.delta_min_size,
+    };
+    patch::export_patch_from_originals(
+        &originals,
+        &patched_root,
+        Path::new(&request.package_path),
+        &options,
+    )
+    .map_err(|e| format!("Impossible d'exporter le patch: {}", e))
+}
+
+/// Check a patch package against a game folder, file by file
+#[tauri::command]
+pub fn verify_patch_package(
+    game_path: String,
+    package_path: String,
+) -> Result<PatchVerification, String> {
+    let game_path = Path::new(&game_path);
+    let package = open_package_for_game(game_path, Path::new(&package_path))?;
+    package
+        .verify(game_path)
+        .map_err(|e| format!("Impossible de vérifier le patch: {}", e))
+}
+
+/// Apply a patch package to a game folder
+///
+/// Refuses when a game file differs from the original the patch expects, listing each
+/// such file. The replaced files are backed up to revert the patch.
+#[tauri::command]
+pub fn apply_patch_package(
+    game_path: String,
+    package_path: String,
+) -> Result<PatchApplyResult, String> {
+    let game_path = Path::new(&game_path);
+    let mut package = open_package_for_game(game_path, Path::new(&package_path))?;
+
+    let verification = package
+        .verify(game_path)
+        .map_err(|e| format!("Impossible de vérifier le patch: {}", e))?;
+    if !verification.applicable && !verification.already_applied {
+        let report: Vec<String> = verification
+            .problems()
+            .map(|check| {
+                let problem = match check.status {
+                    FileCheckStatus::Missing => "fichier manquant",
+                    _ => "fichier différent de l'original attendu",
+                };
+                format!("- {} : {}", check.path, problem)
+            })
+            .collect();
+        return Err(format!(
+            "Le patch ne correspond pas aux fichiers du jeu:\n{}",
+            report.join("\n")
+        ));
+    }
+
+    let name = format!("patch_{}", chrono::Utc::now().timestamp_millis());
+    package
+        .apply(game_path, &name)
+        .map_err(|e| format!("Impossible d'appliquer le patch: {}", e))
+}
+
+/// Revert a patch applied with `apply_patch_package`
+///
+/// Returns the number of restored or removed files.
+#[tauri::command]
+pub fn revert_patch_package(
+    game_path: String,
+    package_path: String,
+    backup_id: String,
+) -> Result<usize, String> {
+    let game_path = Path::new(&game_path);
+    let package = open_package_for_game(game_path, Path::new(&package_path))?;
+    apply::revert_patch(game_path, package.manifest(), &backup_id)
+        .map_err(|e| format!("Impossible d'annuler le patch: {}", e))
+}
+
+/// Open a patch package and check it was made for the engine of the game
+fn open_package_for_game(game_path: &Path, package_path: &Path) -> Result<PatchPackage, String> {
+    if !game_path.is_dir() {
+        return Err(format!(
+            "Le chemin du jeu n'existe pas ou n'est pas un dossier: {}",
+            game_path.display()
+        ));
+    }
+    let handler = EngineFactory::create_handler(game_path)
+        .map_err(|e| format!("Impossible de détecter le moteur de jeu: {}", e))?;
+    let package = PatchPackage::open(package_path)
+        .map_err(|e| format!("Patch invalide {}: {}", package_path.display(), e))?;
+
+    let manifest = package.manifest();
+    if manifest.engine != handler.engine_name() {
+        return Err(format!(
+            "Ce patch a été créé pour {} mais le jeu utilise {}",
+            manifest.engine,
+            handler.engine_name()
+        ));
+    }
+
+    // Injection only modifies the data root
+    let data_root = handler.get_data_root(game_path);
+    if let Some(file) = manifest
+        .files
+        .iter()
+        .find(|file| !game_path.join(&file.path).starts_with(&data_root))
+    {
+        return Err(format!(
+            "Le patch modifie un fichier hors des données du jeu: {}",
+            file.path
+        ));
+    }
+
+    Ok(package)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn request(game_path: &Path, package_path: &Path) -> PatchExportRequest {
+        PatchExportRequest {
+            game_path: game_path.to_string_lossy().to_string(),
+            output_path: None,
+            backup_id: None,
+            package_path: package_path.to_string_lossy().to_string(),
+            delta_min_size: None,
+        }
+    }
+
+    #[test]
+    fn test_export_compares_with_oldest_backup() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path().join("game");
+        let actors = game.join("data/Actors.json");
+        fs::create_dir_all(actors.parent().unwrap()).unwrap();
+        fs::write(game.join("package.json"), "{}").unwrap();
+        fs::write(&actors, "[\"勇者\"]").unwrap();
+
+        // Two in-place injections: the oldest backup holds the original files
+        backup::create_backup(&game, std::slice::from_ref(&actors), Some("inj_1")).unwrap();
+        fs::write(&actors, "[\"Hero\"]").unwrap();
+        backup::create_backup(&game, std::slice::from_ref(&actors), Some("inj_2")).unwrap();
+        fs::write(&actors, "[\"Héros\"]").unwrap();
+
+        let package = temp.path().join("patch.zip");
+        let manifest = export_patch_package(request(&game, &package)).unwrap();
+
+        assert_eq!(manifest.files.len(), 1);
+        assert_eq!(
+            manifest.files[0].original_sha256.as_deref(),
+            Some(patch::sha256_hex("[\"勇者\"]".as_bytes()).as_str())
+        );
+        assert!(package.is_file());
+    }
+
+    #[test]
+    fn test_export_covers_files_of_every_backup() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path().join("game");
+        let actors = game.join("data/Actors.json");
+        let map = game.join("data/Map001.json");
+        fs::create_dir_all(actors.parent().unwrap()).unwrap();
+        fs::write(game.join("package.json"), "{}").unwrap();
+        fs::write(&actors, "[\"勇者\"]").unwrap();
+        fs::write(&map, "{\"displayName\":\"村\"}").unwrap();
+
+        // The first injection only changed Actors.json, the second one the map too
+        backup::create_backup(&game, std::slice::from_ref(&actors), Some("inj_1")).unwrap();
+        fs::write(&actors, "[\"Hero\"]").unwrap();
+        backup::create_backup(&game, &[actors.clone(), map.clone()], Some("inj_2")).unwrap();
+        fs::write(&actors, "[\"Héros\"]").unwrap();
+        fs::write(&map, "{\"displayName\":\"Village\"}").unwrap();
+
+        let package = temp.path().join("patch.zip");
+        let manifest = export_patch_package(request(&game, &package)).unwrap();
+
+        let originals: Vec<(&str, Option<&str>)> = manifest
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.original_sha256.as_deref()))
+            .collect();
+        let actors_sha = patch::sha256_hex("[\"勇者\"]".as_bytes());
+        let map_sha = patch::sha256_hex("{\"displayName\":\"村\"}".as_bytes());
+        assert_eq!(
+            originals,
+            vec![
+                ("data/Actors.json", Some(actors_sha.as_str())),
+                ("data/Map001.json", Some(map_sha.as_str())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export_without_backup_fails() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+        fs::create_dir_all(game.join("data")).unwrap();
+        fs::write(game.join("package.json"), "{}").unwrap();
+
+        let result = export_patch_package(request(game, &game.join("patch.zip")));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_apply_checks_engine_and_reports_mismatches() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path().join("game");
+        let actors = game.join("data/Actors.json");
+        fs::create_dir_all(actors.parent().unwrap()).unwrap();
+        fs::write(game.join("package.json"), "{}").unwrap();
+        fs::write(&actors, "[\"勇者\"]").unwrap();
+        backup::create_backup(&game, std::slice::from_ref(&actors), None).unwrap();
+        fs::write(&actors, "[\"Héros\"]").unwrap();
+        let package = temp.path().join("patch.zip");
+        export_patch_package(request(&game, &package)).unwrap();
+        let package_path = package.to_string_lossy().to_string();
+
+        // A user copy of the game, with a modified Actors.json
+        let user_game = temp.path().join("user_game");
+        fs::create_dir_all(user_game.join("data")).unwrap();
+        fs::write(user_game.join("package.json"), "{}").unwrap();
+        fs::write(user_game.join("data/Actors.json"), "[\"Autre\"]").unwrap();
+        let user_game_path = user_game.to_string_lossy().to_string();
+
+        let error = apply_patch_package(user_game_path.clone(), package_path.clone()).unwrap_err();
+        assert!(error.contains("data/Actors.json"), "{}", error);
+
+        fs::write(user_game.join("data/Actors.json"), "[\"勇者\"]").unwrap();
+        let result = apply_patch_package(user_game_path.clone(), package_path.clone()).unwrap();
+        assert_eq!(result.files_patched, 1);
+        assert_eq!(
+            fs::read_to_string(user_game.join("data/Actors.json")).unwrap(),
+            "[\"Héros\"]"
+        );
+
+        let reverted = revert_patch_package(
+            user_game_path,
+            package_path.clone(),
+            result.backup_id.unwrap(),
+        )
+        .unwrap();
+        assert_eq!(reverted, 1);
+        assert_eq!(
+            fs::read_to_string(user_game.join("data/Actors.json")).unwrap(),
+            "[\"勇者\"]"
+        );
+
+        // An RPG Maker MV game can't take an MZ patch
+        let mv_game = temp.path().join("mv_game");
+        fs::create_dir_all(mv_game.join("www/data")).unwrap();
+        let error =
+            verify_patch_package(mv_game.to_string_lossy().to_string(), package_path).unwrap_err();
+        assert!(error.contains("créé pour"), "{}", error);
+    }
+}
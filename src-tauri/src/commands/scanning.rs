@@ -1,8 +1,9 @@
 // Scanning commands for game file analysis and text extraction
 // Implements the scanning workflow for game localization
 
-use crate::parsers::engine::TextEntry;
+use crate::parsers::engine::{TextEntry, TranslationStatus};
 use crate::parsers::factory::EngineFactory;
+use crate::parsers::text::validation::{ContentValidator, IgnoreRules};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::Mutex;
@@ -120,8 +121,15 @@ pub fn cancel_scan(scan_id: String, state: State<'_, ScanState>) -> Result<(), S
 }
 
 /// Extract texts from a game folder and return them
+///
+/// When a target language is given, texts that don't need translation (technical
+/// tokens, texts matching the project ignore rules, ...) are marked as Ignored.
 #[tauri::command]
-pub fn extract_texts_from_folder(folder_path: String) -> Result<Vec<TextEntry>, String> {
+pub fn extract_texts_from_folder(
+    folder_path: String,
+    target_language: Option<String>,
+    ignore_rules: Option<IgnoreRules>,
+) -> Result<Vec<TextEntry>, String> {
     let path = Path::new(&folder_path);
 
     // Validate folder exists
@@ -132,6 +140,12 @@ pub fn extract_texts_from_folder(folder_path: String) -> Result<Vec<TextEntry>,
         ));
     }
 
+    // Compile user ignore rules before the (long) extraction
+    let ignore_matcher = ignore_rules
+        .map(|rules| rules.compile())
+        .transpose()
+        .map_err(|e| format!("Règles d'exclusion invalides : {}", e))?;
+
     // Detect game engine and create handler
     let handler = EngineFactory::create_handler(path).map_err(|e| {
         format!(
@@ -141,9 +155,24 @@ pub fn extract_texts_from_folder(folder_path: String) -> Result<Vec<TextEntry>,
     })?;
 
     // Extract texts using handler
-    handler
+    let mut entries = handler
         .extract_all_texts(path)
-        .map_err(|e| format!("Erreur lors de l'extraction des textes : {}", e))
+        .map_err(|e| format!("Erreur lors de l'extraction des textes : {}", e))?;
+
+    if let Some(target_language) = target_language {
+        for entry in entries
+            .iter_mut()
+            .filter(|entry| entry.status == TranslationStatus::NotTranslated)
+        {
+            entry.status = ContentValidator::get_initial_status_with_rules(
+                &entry.source_text,
+                &target_language,
+                ignore_matcher.as_ref(),
+            );
+        }
+    }
+
+    Ok(entries)
 }
 
 /// Validate file format compatibility
@@ -348,7 +377,7 @@ mod tests {
             return;
         }
 
-        let result = extract_texts_from_folder(game_path.to_string_lossy().to_string());
+        let result = extract_texts_from_folder(game_path.to_string_lossy().to_string(), None, None);
         assert!(result.is_ok(), "Should extract texts successfully from MV game");
 
         let entries = result.unwrap();
@@ -371,7 +400,7 @@ mod tests {
             return;
         }
 
-        let result = extract_texts_from_folder(game_path.to_string_lossy().to_string());
+        let result = extract_texts_from_folder(game_path.to_string_lossy().to_string(), None, None);
         assert!(result.is_ok(), "Should extract texts successfully from MZ game");
 
         let entries = result.unwrap();
@@ -394,7 +423,7 @@ mod tests {
             return;
         }
 
-        let result = extract_texts_from_folder(game_path.to_string_lossy().to_string());
+        let result = extract_texts_from_folder(game_path.to_string_lossy().to_string(), None, None);
         assert!(result.is_ok(), "Should extract texts successfully from WolfRPG game");
 
         let entries = result.unwrap();
@@ -411,7 +440,7 @@ mod tests {
         let current_dir = env::current_dir().unwrap();
         let invalid_path = current_dir.join("../engines_past/nonexistent");
 
-        let result = extract_texts_from_folder(invalid_path.to_string_lossy().to_string());
+        let result = extract_texts_from_folder(invalid_path.to_string_lossy().to_string(), None, None);
         assert!(result.is_err(), "Should fail for non-existent game path");
     }
 
@@ -1,6 +1,8 @@
 // Translation commands
-// Tauri commands for translation operations using Ollama (local) or RunPod (online)
+// Tauri commands for translation operations using Ollama (local), RunPod (online),
+// any OpenAI-compatible server, or the DeepL / LibreTranslate machine translation APIs
 
+use crate::translation::common::types::{SequentialProgress, SequentialStatus, SingleTranslationRequest as OllamaSingleRequest, TranslationSuggestion};
 use crate::translation::common::types::{SequentialTranslationRequest as OllamaSequentialRequest, TranslationText as OllamaTranslationText};
 use crate::translation::ollama::{
     OllamaClient, OllamaConfig, SequentialTranslationManager as OllamaSequentialManager,
@@ -11,6 +13,19 @@ use crate::translation::runpod::{
     RunPodClient, RunPodConfig, SequentialTranslationManager as RunPodSequentialManager,
     SingleTranslationManager as RunPodSingleManager,
 };
+use crate::translation::openai_compat::{
+    OpenAiCompatClient, OpenAiCompatConfig,
+    SequentialTranslationManager as OpenAiCompatSequentialManager,
+    SingleTranslationManager as OpenAiCompatSingleManager,
+};
+use crate::translation::deepl::{DeepLClient, DeepLConfig};
+use crate::translation::libretranslate::{LibreTranslateClient, LibreTranslateConfig};
+use crate::translation::machine::{
+    MachineTranslationClient, SequentialTranslationManager as MachineSequentialManager,
+    SingleTranslationManager as MachineSingleManager,
+};
+use crate::translation::memory::lookup_memory_matches;
+use crate::parsers::text::length_budget::LengthBudget;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -22,6 +37,26 @@ use tauri::AppHandle;
 pub enum TranslationProvider {
     Ollama,
     RunPod,
+    OpenAiCompat,
+    DeepL,
+    LibreTranslate,
+}
+
+impl TranslationProvider {
+    /// Parse the provider name sent by the frontend
+    pub fn parse(provider: &str) -> Result<Self, String> {
+        match provider {
+            "ollama" => Ok(Self::Ollama),
+            "runpod" => Ok(Self::RunPod),
+            "openai_compat" => Ok(Self::OpenAiCompat),
+            "deepl" => Ok(Self::DeepL),
+            "libretranslate" => Ok(Self::LibreTranslate),
+            _ => Err(format!(
+                "Invalid provider: {}. Must be 'ollama', 'runpod', 'openai_compat', 'deepl' or 'libretranslate'",
+                provider
+            )),
+        }
+    }
 }
 
 // Global Ollama managers (local)
@@ -71,6 +106,86 @@ async fn get_runpod_managers(
     (sequential_manager, single_manager)
 }
 
+// Global OpenAI-compatible managers
+// Cache managers by configuration so sessions persist across command calls
+type OpenAiCompatManagers = (Arc<OpenAiCompatSequentialManager>, Arc<OpenAiCompatSingleManager>);
+static OPENAI_COMPAT_MANAGERS_CACHE: Lazy<Arc<Mutex<HashMap<String, OpenAiCompatManagers>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+
+/// Helper function to get or create OpenAI-compatible managers for a configuration
+/// Changing any setting (URL, key, model, sampling) yields a fresh pair of managers
+async fn get_openai_compat_managers(config: OpenAiCompatConfig) -> OpenAiCompatManagers {
+    let key = serde_json::to_string(&config).unwrap_or_else(|_| config.base_url.clone());
+    let mut managers = OPENAI_COMPAT_MANAGERS_CACHE.lock().await;
+
+    if let Some(existing) = managers.get(&key) {
+        return (Arc::clone(&existing.0), Arc::clone(&existing.1));
+    }
+
+    let client = Arc::new(OpenAiCompatClient::new(config));
+    let single_manager = Arc::new(OpenAiCompatSingleManager::new(Arc::clone(&client)));
+    let sequential_manager =
+        Arc::new(OpenAiCompatSequentialManager::new(Arc::clone(&single_manager)));
+
+    let managers_tuple = (Arc::clone(&sequential_manager), Arc::clone(&single_manager));
+    managers.insert(key, managers_tuple.clone());
+
+    managers_tuple
+}
+
+// Global machine translation managers (DeepL, LibreTranslate)
+// Cached by configuration like the OpenAI-compatible ones
+type MachineManagers<C> = (Arc<MachineSequentialManager<C>>, Arc<MachineSingleManager<C>>);
+type MachineManagersCache<C> = Lazy<Arc<Mutex<HashMap<String, MachineManagers<C>>>>>;
+static DEEPL_MANAGERS_CACHE: MachineManagersCache<DeepLClient> =
+    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+static LIBRETRANSLATE_MANAGERS_CACHE: MachineManagersCache<LibreTranslateClient> =
+    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+
+/// Helper function to get or create machine translation managers for a configuration key
+async fn get_machine_managers<C: MachineTranslationClient>(
+    cache: &Mutex<HashMap<String, MachineManagers<C>>>,
+    key: String,
+    create_client: impl FnOnce() -> C,
+) -> MachineManagers<C> {
+    let mut managers = cache.lock().await;
+
+    if let Some(existing) = managers.get(&key) {
+        return (Arc::clone(&existing.0), Arc::clone(&existing.1));
+    }
+
+    let single_manager = Arc::new(MachineSingleManager::new(Arc::new(create_client())));
+    let sequential_manager = Arc::new(MachineSequentialManager::new(Arc::clone(&single_manager)));
+
+    let managers_tuple = (Arc::clone(&sequential_manager), Arc::clone(&single_manager));
+    managers.insert(key, managers_tuple.clone());
+
+    managers_tuple
+}
+
+/// Helper function to get or create DeepL managers from the frontend configuration
+async fn get_deepl_managers(config: Option<DeepLConfig>) -> Result<MachineManagers<DeepLClient>, String> {
+    let config = config.ok_or_else(|| "deepl configuration is required for DeepL provider".to_string())?;
+    let key = serde_json::to_string(&config).unwrap_or_else(|_| config.api_key.clone());
+    Ok(get_machine_managers(&DEEPL_MANAGERS_CACHE, key, || DeepLClient::new(config)).await)
+}
+
+/// Helper function to get or create LibreTranslate managers from the frontend configuration
+async fn get_libretranslate_managers(
+    config: Option<LibreTranslateConfig>,
+) -> Result<MachineManagers<LibreTranslateClient>, String> {
+    let config = config.ok_or_else(|| {
+        "libretranslate configuration is required for LibreTranslate provider".to_string()
+    })?;
+    let key = serde_json::to_string(&config).unwrap_or_else(|_| config.base_url.clone());
+    Ok(get_machine_managers(&LIBRETRANSLATE_MANAGERS_CACHE, key, || LibreTranslateClient::new(config)).await)
+}
+
+/// Helper function to get the OpenAI-compatible configuration sent by the frontend
+fn require_openai_compat(config: Option<OpenAiCompatConfig>) -> Result<OpenAiCompatConfig, String> {
+    config.ok_or_else(|| "openai_compat configuration is required for OpenAI-compatible provider".to_string())
+}
+
 /// Helper function to convert Ollama TranslationText to RunPod TranslationText
 fn convert_texts_ollama_to_runpod(texts: Vec<OllamaTranslationText>) -> Vec<RunPodTranslationText> {
     texts
@@ -80,6 +195,7 @@ fn convert_texts_ollama_to_runpod(texts: Vec<OllamaTranslationText>) -> Vec<RunP
             source_text: t.source_text,
             context: t.context,
             text_type: t.text_type,
+            location: t.location,
         })
         .collect()
 }
@@ -94,10 +210,44 @@ fn convert_texts_runpod_to_ollama(texts: Vec<RunPodTranslationText>) -> Vec<Olla
             source_text: t.source_text,
             context: t.context,
             text_type: t.text_type,
+            location: t.location,
         })
         .collect()
 }
 
+/// Serialize sequential progress into the shape expected by the frontend
+fn sequential_progress_json(progress: SequentialProgress) -> serde_json::Value {
+    serde_json::json!({
+        "session_id": progress.session_id,
+        "current_entry": progress.current_entry,
+        "processed_count": progress.processed_count,
+        "total_count": progress.total_count,
+        "status": match progress.status {
+            SequentialStatus::Idle => "idle",
+            SequentialStatus::Running => "running",
+            SequentialStatus::Paused => "paused",
+            SequentialStatus::Completed => "completed",
+            SequentialStatus::Error => "error",
+        },
+        "estimated_time_remaining": progress.estimated_time_remaining,
+        "errors": progress.errors.iter().map(|e| serde_json::json!({
+            "entry_id": e.entry_id,
+            "error_message": e.error_message,
+            "timestamp": e.timestamp,
+            "kind": e.kind,
+            "attempts": e.attempts
+        })).collect::<Vec<_>>(),
+        "successful_translations": progress.successful_translations.iter().map(|t| serde_json::json!({
+            "entry_id": t.entry_id,
+            "translated_text": t.translated_text,
+            "timestamp": t.timestamp,
+            "over_budget": t.over_budget
+        })).collect::<Vec<_>>(),
+        "throughput": progress.throughput,
+        "latency_by_model": progress.latency_by_model
+    })
+}
+
 /// Check Ollama availability and get server information (local)
 /// This command delegates to the ollama module for the actual logic
 #[tauri::command]
@@ -108,6 +258,29 @@ pub async fn check_ollama_status(
     crate::translation::ollama::check_ollama_status(host, port).await
 }
 
+/// Check OpenAI-compatible server availability and list its models
+/// This command delegates to the openai_compat module for the actual logic
+#[tauri::command]
+pub async fn check_openai_compat_status(
+    config: OpenAiCompatConfig,
+) -> Result<serde_json::Value, String> {
+    crate::translation::openai_compat::check_openai_compat_status(config).await
+}
+
+/// Check DeepL availability and list supported target languages
+#[tauri::command]
+pub async fn check_deepl_status(config: DeepLConfig) -> Result<serde_json::Value, String> {
+    crate::translation::deepl::check_deepl_status(config).await
+}
+
+/// Check LibreTranslate availability and list supported languages
+#[tauri::command]
+pub async fn check_libretranslate_status(
+    config: LibreTranslateConfig,
+) -> Result<serde_json::Value, String> {
+    crate::translation::libretranslate::check_libretranslate_status(config).await
+}
+
 /// Check RunPod availability and get server information (online)
 /// This command delegates to the runpod module for the actual logic
 #[tauri::command]
@@ -120,18 +293,29 @@ pub async fn check_runpod_status(pod_id: String) -> Result<serde_json::Value, St
 }
 
 /// Start sequential translation session
-/// Routes to Ollama (local) or RunPod (online) based on provider parameter
+/// Routes to Ollama (local), RunPod (online), an OpenAI-compatible server or an MT engine based on provider parameter
 #[tauri::command]
 pub async fn start_sequential_translation(
     app: AppHandle,
-    provider: String, // "ollama" or "runpod"
+    provider: String, // "ollama", "runpod", "openai_compat", "deepl" or "libretranslate"
     project_id: i64,
     texts: Vec<OllamaTranslationText>, // Common format from frontend
     start_from: Option<i32>,
     source_language: Option<String>,
     target_language: Option<String>,
     model: Option<String>,
+    length_budgets: Option<Vec<LengthBudget>>, // Checked on each translation by text location
+    retranslate_over_budget: Option<bool>,     // Ask once for a shorter translation when over budget
+    prompt_batch_size: Option<u32>,            // Short entries packed into one prompt (LLM providers)
+    context_lines: Option<u32>,                // Previous dialogue lines given as context (LLM providers)
+    parallel_requests: Option<u32>,            // Requests in flight at once (LLM providers)
+    use_translation_memory: Option<bool>,      // Reuse remembered translations (default true)
+    deduplicate: Option<bool>,                 // Translate identical source texts once (default true)
+    deduplicate_excluded_types: Option<Vec<String>>, // Text types never grouped (default dialogue)
     pod_id: Option<String>, // Required for RunPod provider
+    openai_compat: Option<OpenAiCompatConfig>, // Required for OpenAI-compatible provider
+    deepl: Option<DeepLConfig>,                // Required for DeepL provider
+    libretranslate: Option<LibreTranslateConfig>, // Required for LibreTranslate provider
 ) -> Result<serde_json::Value, String> {
     println!(
         "🚀 [Rust] Starting translation for project {} with {} texts using provider: {}",
@@ -140,16 +324,7 @@ pub async fn start_sequential_translation(
         provider
     );
 
-    let provider_enum = match provider.as_str() {
-        "ollama" => TranslationProvider::Ollama,
-        "runpod" => TranslationProvider::RunPod,
-        _ => {
-            return Err(format!(
-                "Invalid provider: {}. Must be 'ollama' or 'runpod'",
-                provider
-            ))
-        }
-    };
+    let provider_enum = TranslationProvider::parse(&provider)?;
 
     match provider_enum {
         TranslationProvider::Ollama => {
@@ -161,6 +336,14 @@ pub async fn start_sequential_translation(
                 target_language,
                 model,
                 pause_settings: None, // Sera configuré depuis les settings utilisateur
+                length_budgets,
+                retranslate_over_budget,
+                prompt_batch_size,
+                context_lines,
+                parallel_requests,
+                use_translation_memory,
+                deduplicate,
+                deduplicate_excluded_types: deduplicate_excluded_types.clone(),
             };
 
             match OLLAMA_SEQUENTIAL_MANAGER.start_session(app, request).await {
@@ -186,6 +369,14 @@ pub async fn start_sequential_translation(
                 target_language,
                 model,
                 pause_settings: None, // Sera configuré depuis les settings utilisateur
+                length_budgets,
+                retranslate_over_budget,
+                prompt_batch_size,
+                context_lines,
+                parallel_requests,
+                use_translation_memory,
+                deduplicate,
+                deduplicate_excluded_types: deduplicate_excluded_types.clone(),
             };
 
             let (sequential_manager, _) = get_runpod_managers(pod_id_str).await;
@@ -199,55 +390,118 @@ pub async fn start_sequential_translation(
                 Err(e) => Err(format!("Failed to start RunPod translation: {}", e)),
             }
         }
+        TranslationProvider::OpenAiCompat => {
+            let config = require_openai_compat(openai_compat)?;
+
+            let request = OllamaSequentialRequest {
+                project_id,
+                texts: texts.clone(),
+                start_from,
+                source_language,
+                target_language,
+                model,
+                pause_settings: None, // Sera configuré depuis les settings utilisateur
+                length_budgets,
+                retranslate_over_budget,
+                prompt_batch_size,
+                context_lines,
+                parallel_requests,
+                use_translation_memory,
+                deduplicate,
+                deduplicate_excluded_types: deduplicate_excluded_types.clone(),
+            };
+
+            let (sequential_manager, _) = get_openai_compat_managers(config).await;
+            match sequential_manager.start_session(app, request).await {
+                Ok(session_id) => Ok(serde_json::json!({
+                    "session_id": session_id,
+                    "status": "started",
+                    "total_entries": texts.len(),
+                    "provider": "openai_compat"
+                })),
+                Err(e) => Err(format!("Failed to start OpenAI-compatible translation: {}", e)),
+            }
+        }
+        TranslationProvider::DeepL => {
+            let (sequential_manager, _) = get_deepl_managers(deepl).await?;
+            let request = OllamaSequentialRequest {
+                project_id,
+                texts: texts.clone(),
+                start_from,
+                source_language,
+                target_language,
+                model,
+                pause_settings: None,
+                length_budgets,
+                retranslate_over_budget,
+                prompt_batch_size,
+                context_lines,
+                parallel_requests,
+                use_translation_memory,
+                deduplicate,
+                deduplicate_excluded_types: deduplicate_excluded_types.clone(),
+            };
+
+            match sequential_manager.start_session(app, request).await {
+                Ok(session_id) => Ok(serde_json::json!({
+                    "session_id": session_id,
+                    "status": "started",
+                    "total_entries": texts.len(),
+                    "provider": "deepl"
+                })),
+                Err(e) => Err(format!("Failed to start DeepL translation: {}", e)),
+            }
+        }
+        TranslationProvider::LibreTranslate => {
+            let (sequential_manager, _) = get_libretranslate_managers(libretranslate).await?;
+            let request = OllamaSequentialRequest {
+                project_id,
+                texts: texts.clone(),
+                start_from,
+                source_language,
+                target_language,
+                model,
+                pause_settings: None,
+                length_budgets,
+                retranslate_over_budget,
+                prompt_batch_size,
+                context_lines,
+                parallel_requests,
+                use_translation_memory,
+                deduplicate,
+                deduplicate_excluded_types: deduplicate_excluded_types.clone(),
+            };
+
+            match sequential_manager.start_session(app, request).await {
+                Ok(session_id) => Ok(serde_json::json!({
+                    "session_id": session_id,
+                    "status": "started",
+                    "total_entries": texts.len(),
+                    "provider": "libretranslate"
+                })),
+                Err(e) => Err(format!("Failed to start LibreTranslate translation: {}", e)),
+            }
+        }
     }
 }
 
 /// Get sequential translation progress
-/// Routes to Ollama or RunPod based on provider parameter
+/// Routes to Ollama, RunPod, an OpenAI-compatible server or an MT engine based on provider parameter
 #[tauri::command]
 pub async fn get_sequential_progress(
     session_id: String,
-    provider: String,       // "ollama" or "runpod"
+    provider: String,       // "ollama", "runpod", "openai_compat", "deepl" or "libretranslate"
     pod_id: Option<String>, // Required for RunPod
+    openai_compat: Option<OpenAiCompatConfig>, // Required for OpenAI-compatible provider
+    deepl: Option<DeepLConfig>,                // Required for DeepL provider
+    libretranslate: Option<LibreTranslateConfig>, // Required for LibreTranslate provider
 ) -> Result<serde_json::Value, String> {
-    let provider_enum = match provider.as_str() {
-        "ollama" => TranslationProvider::Ollama,
-        "runpod" => TranslationProvider::RunPod,
-        _ => {
-            return Err(format!(
-                "Invalid provider: {}. Must be 'ollama' or 'runpod'",
-                provider
-            ))
-        }
-    };
+    let provider_enum = TranslationProvider::parse(&provider)?;
 
     match provider_enum {
         TranslationProvider::Ollama => {
             match OLLAMA_SEQUENTIAL_MANAGER.get_progress(&session_id).await {
-                Some(progress) => Ok(serde_json::json!({
-                    "session_id": progress.session_id,
-                    "current_entry": progress.current_entry,
-                    "processed_count": progress.processed_count,
-                    "total_count": progress.total_count,
-                    "status": match progress.status {
-                        crate::translation::ollama::SequentialStatus::Idle => "idle",
-                        crate::translation::ollama::SequentialStatus::Running => "running",
-                        crate::translation::ollama::SequentialStatus::Paused => "paused",
-                        crate::translation::ollama::SequentialStatus::Completed => "completed",
-                        crate::translation::ollama::SequentialStatus::Error => "error",
-                    },
-                    "estimated_time_remaining": progress.estimated_time_remaining,
-                    "errors": progress.errors.iter().map(|e| serde_json::json!({
-                        "entry_id": e.entry_id,
-                        "error_message": e.error_message,
-                        "timestamp": e.timestamp
-                    })).collect::<Vec<_>>(),
-                    "successful_translations": progress.successful_translations.iter().map(|t| serde_json::json!({
-                        "entry_id": t.entry_id,
-                        "translated_text": t.translated_text,
-                        "timestamp": t.timestamp
-                    })).collect::<Vec<_>>()
-                })),
+                Some(progress) => Ok(sequential_progress_json(progress)),
                 None => Err(format!("Session {} not found", session_id)),
             }
         }
@@ -255,30 +509,29 @@ pub async fn get_sequential_progress(
             let pod_id_str = pod_id.ok_or_else(|| "pod_id is required for RunPod".to_string())?;
             let (sequential_manager, _) = get_runpod_managers(pod_id_str).await;
             match sequential_manager.get_progress(&session_id).await {
-                Some(progress) => Ok(serde_json::json!({
-                    "session_id": progress.session_id,
-                    "current_entry": progress.current_entry,
-                    "processed_count": progress.processed_count,
-                    "total_count": progress.total_count,
-                    "status": match progress.status {
-                        crate::translation::runpod::SequentialStatus::Idle => "idle",
-                        crate::translation::runpod::SequentialStatus::Running => "running",
-                        crate::translation::runpod::SequentialStatus::Paused => "paused",
-                        crate::translation::runpod::SequentialStatus::Completed => "completed",
-                        crate::translation::runpod::SequentialStatus::Error => "error",
-                    },
-                    "estimated_time_remaining": progress.estimated_time_remaining,
-                    "errors": progress.errors.iter().map(|e| serde_json::json!({
-                        "entry_id": e.entry_id,
-                        "error_message": e.error_message,
-                        "timestamp": e.timestamp
-                    })).collect::<Vec<_>>(),
-                    "successful_translations": progress.successful_translations.iter().map(|t| serde_json::json!({
-                        "entry_id": t.entry_id,
-                        "translated_text": t.translated_text,
-                        "timestamp": t.timestamp
-                    })).collect::<Vec<_>>()
-                })),
+                Some(progress) => Ok(sequential_progress_json(progress)),
+                None => Err(format!("Session {} not found", session_id)),
+            }
+        }
+        TranslationProvider::OpenAiCompat => {
+            let config = require_openai_compat(openai_compat)?;
+            let (sequential_manager, _) = get_openai_compat_managers(config).await;
+            match sequential_manager.get_progress(&session_id).await {
+                Some(progress) => Ok(sequential_progress_json(progress)),
+                None => Err(format!("Session {} not found", session_id)),
+            }
+        }
+        TranslationProvider::DeepL => {
+            let (sequential_manager, _) = get_deepl_managers(deepl).await?;
+            match sequential_manager.get_progress(&session_id).await {
+                Some(progress) => Ok(sequential_progress_json(progress)),
+                None => Err(format!("Session {} not found", session_id)),
+            }
+        }
+        TranslationProvider::LibreTranslate => {
+            let (sequential_manager, _) = get_libretranslate_managers(libretranslate).await?;
+            match sequential_manager.get_progress(&session_id).await {
+                Some(progress) => Ok(sequential_progress_json(progress)),
                 None => Err(format!("Session {} not found", session_id)),
             }
         }
@@ -286,18 +539,17 @@ pub async fn get_sequential_progress(
 }
 
 /// Pause sequential translation session
-/// Routes to Ollama or RunPod based on provider parameter
+/// Routes to Ollama, RunPod, an OpenAI-compatible server or an MT engine based on provider parameter
 #[tauri::command]
 pub async fn pause_sequential_session(
     session_id: String,
     provider: String,
     pod_id: Option<String>, // Required for RunPod
+    openai_compat: Option<OpenAiCompatConfig>, // Required for OpenAI-compatible provider
+    deepl: Option<DeepLConfig>,                // Required for DeepL provider
+    libretranslate: Option<LibreTranslateConfig>, // Required for LibreTranslate provider
 ) -> Result<(), String> {
-    let provider_enum = match provider.as_str() {
-        "ollama" => TranslationProvider::Ollama,
-        "runpod" => TranslationProvider::RunPod,
-        _ => return Err(format!("Invalid provider: {}", provider)),
-    };
+    let provider_enum = TranslationProvider::parse(&provider)?;
 
     match provider_enum {
         TranslationProvider::Ollama => OLLAMA_SEQUENTIAL_MANAGER.pause_session(&session_id).await,
@@ -306,22 +558,34 @@ pub async fn pause_sequential_session(
             let (sequential_manager, _) = get_runpod_managers(pod_id_str).await;
             sequential_manager.pause_session(&session_id).await
         }
+        TranslationProvider::OpenAiCompat => {
+            let config = require_openai_compat(openai_compat)?;
+            let (sequential_manager, _) = get_openai_compat_managers(config).await;
+            sequential_manager.pause_session(&session_id).await
+        }
+        TranslationProvider::DeepL => {
+            let (sequential_manager, _) = get_deepl_managers(deepl).await?;
+            sequential_manager.pause_session(&session_id).await
+        }
+        TranslationProvider::LibreTranslate => {
+            let (sequential_manager, _) = get_libretranslate_managers(libretranslate).await?;
+            sequential_manager.pause_session(&session_id).await
+        }
     }
 }
 
 /// Resume sequential translation session
-/// Routes to Ollama or RunPod based on provider parameter
+/// Routes to Ollama, RunPod, an OpenAI-compatible server or an MT engine based on provider parameter
 #[tauri::command]
 pub async fn resume_sequential_session(
     session_id: String,
     provider: String,
     pod_id: Option<String>, // Required for RunPod
+    openai_compat: Option<OpenAiCompatConfig>, // Required for OpenAI-compatible provider
+    deepl: Option<DeepLConfig>,                // Required for DeepL provider
+    libretranslate: Option<LibreTranslateConfig>, // Required for LibreTranslate provider
 ) -> Result<(), String> {
-    let provider_enum = match provider.as_str() {
-        "ollama" => TranslationProvider::Ollama,
-        "runpod" => TranslationProvider::RunPod,
-        _ => return Err(format!("Invalid provider: {}", provider)),
-    };
+    let provider_enum = TranslationProvider::parse(&provider)?;
 
     match provider_enum {
         TranslationProvider::Ollama => OLLAMA_SEQUENTIAL_MANAGER.resume_session(&session_id).await,
@@ -330,22 +594,34 @@ pub async fn resume_sequential_session(
             let (sequential_manager, _) = get_runpod_managers(pod_id_str).await;
             sequential_manager.resume_session(&session_id).await
         }
+        TranslationProvider::OpenAiCompat => {
+            let config = require_openai_compat(openai_compat)?;
+            let (sequential_manager, _) = get_openai_compat_managers(config).await;
+            sequential_manager.resume_session(&session_id).await
+        }
+        TranslationProvider::DeepL => {
+            let (sequential_manager, _) = get_deepl_managers(deepl).await?;
+            sequential_manager.resume_session(&session_id).await
+        }
+        TranslationProvider::LibreTranslate => {
+            let (sequential_manager, _) = get_libretranslate_managers(libretranslate).await?;
+            sequential_manager.resume_session(&session_id).await
+        }
     }
 }
 
 /// Stop sequential translation session
-/// Routes to Ollama or RunPod based on provider parameter
+/// Routes to Ollama, RunPod, an OpenAI-compatible server or an MT engine based on provider parameter
 #[tauri::command]
 pub async fn stop_sequential_session(
     session_id: String,
     provider: String,
     pod_id: Option<String>, // Required for RunPod
+    openai_compat: Option<OpenAiCompatConfig>, // Required for OpenAI-compatible provider
+    deepl: Option<DeepLConfig>,                // Required for DeepL provider
+    libretranslate: Option<LibreTranslateConfig>, // Required for LibreTranslate provider
 ) -> Result<(), String> {
-    let provider_enum = match provider.as_str() {
-        "ollama" => TranslationProvider::Ollama,
-        "runpod" => TranslationProvider::RunPod,
-        _ => return Err(format!("Invalid provider: {}", provider)),
-    };
+    let provider_enum = TranslationProvider::parse(&provider)?;
 
     match provider_enum {
         TranslationProvider::Ollama => OLLAMA_SEQUENTIAL_MANAGER.stop_session(&session_id).await,
@@ -354,119 +630,173 @@ pub async fn stop_sequential_session(
             let (sequential_manager, _) = get_runpod_managers(pod_id_str).await;
             sequential_manager.stop_session(&session_id).await
         }
+        TranslationProvider::OpenAiCompat => {
+            let config = require_openai_compat(openai_compat)?;
+            let (sequential_manager, _) = get_openai_compat_managers(config).await;
+            sequential_manager.stop_session(&session_id).await
+        }
+        TranslationProvider::DeepL => {
+            let (sequential_manager, _) = get_deepl_managers(deepl).await?;
+            sequential_manager.stop_session(&session_id).await
+        }
+        TranslationProvider::LibreTranslate => {
+            let (sequential_manager, _) = get_libretranslate_managers(libretranslate).await?;
+            sequential_manager.stop_session(&session_id).await
+        }
     }
 }
 
-/// Get active sequential sessions for project
+/// Requeue the failed entries of a sequential session and resume it
+/// Routes to Ollama, RunPod, an OpenAI-compatible server or an MT engine based on provider parameter
+/// Returns the number of entries put back in the queue
 #[tauri::command]
-pub async fn get_project_sessions(_project_id: i64) -> Result<serde_json::Value, String> {
-    // For now, return empty array since we don't persist sessions by project yet
-    // This prevents the "Unknown error" while we implement proper session persistence
-    let project_sessions: Vec<serde_json::Value> = Vec::new();
-
-    // TODO: Implement proper session filtering by project_id when session persistence is added
-    // let sessions = SEQUENTIAL_MANAGER.get_active_sessions().await;
-    // let project_sessions: Vec<_> = sessions.into_iter()
-    //     .filter(|s| s.project_id == project_id) // Filter by actual project_id when available
-    //     .map(|s| serde_json::json!({...}))
-    //     .collect();
-
-    Ok(serde_json::json!(project_sessions))
+pub async fn requeue_sequential_errors(
+    session_id: String,
+    provider: String,
+    pod_id: Option<String>, // Required for RunPod
+    openai_compat: Option<OpenAiCompatConfig>, // Required for OpenAI-compatible provider
+    deepl: Option<DeepLConfig>,                // Required for DeepL provider
+    libretranslate: Option<LibreTranslateConfig>, // Required for LibreTranslate provider
+) -> Result<usize, String> {
+    let provider_enum = TranslationProvider::parse(&provider)?;
+
+    match provider_enum {
+        TranslationProvider::Ollama => OLLAMA_SEQUENTIAL_MANAGER.requeue_errors(&session_id).await,
+        TranslationProvider::RunPod => {
+            let pod_id_str = pod_id.ok_or_else(|| "pod_id is required for RunPod".to_string())?;
+            let (sequential_manager, _) = get_runpod_managers(pod_id_str).await;
+            sequential_manager.requeue_errors(&session_id).await
+        }
+        TranslationProvider::OpenAiCompat => {
+            let config = require_openai_compat(openai_compat)?;
+            let (sequential_manager, _) = get_openai_compat_managers(config).await;
+            sequential_manager.requeue_errors(&session_id).await
+        }
+        TranslationProvider::DeepL => {
+            let (sequential_manager, _) = get_deepl_managers(deepl).await?;
+            sequential_manager.requeue_errors(&session_id).await
+        }
+        TranslationProvider::LibreTranslate => {
+            let (sequential_manager, _) = get_libretranslate_managers(libretranslate).await?;
+            sequential_manager.requeue_errors(&session_id).await
+        }
+    }
 }
 
 /// Get translation suggestions for text
-/// Routes to Ollama or RunPod based on provider parameter
+/// Routes to Ollama, RunPod, an OpenAI-compatible server or an MT engine based on provider parameter
+/// Similar texts found in the translation memory come first, with source "memory"
 #[tauri::command]
 pub async fn get_translation_suggestions(
     app: AppHandle,
-    provider: String, // "ollama" or "runpod"
+    provider: String, // "ollama", "runpod", "openai_compat", "deepl" or "libretranslate"
     source_text: String,
     context: Option<String>,
+    source_language: Option<String>, // Language pair of the translation memory lookup
+    target_language: Option<String>,
     pod_id: Option<String>, // Required for RunPod
+    openai_compat: Option<OpenAiCompatConfig>, // Required for OpenAI-compatible provider
+    deepl: Option<DeepLConfig>,                // Required for DeepL provider
+    libretranslate: Option<LibreTranslateConfig>, // Required for LibreTranslate provider
 ) -> Result<serde_json::Value, String> {
-    let provider_enum = match provider.as_str() {
-        "ollama" => TranslationProvider::Ollama,
-        "runpod" => TranslationProvider::RunPod,
-        _ => {
-            return Err(format!(
-                "Invalid provider: {}. Must be 'ollama' or 'runpod'",
-                provider
-            ))
-        }
-    };
+    let provider_enum = TranslationProvider::parse(&provider)?;
 
-    match provider_enum {
-        TranslationProvider::Ollama => {
-            match OLLAMA_SINGLE_MANAGER
-                .get_suggestions(Some(&app), &source_text, context.as_deref(), 3)
-                .await
-            {
-                Ok(suggestions) => {
-                    let suggestions_json: Vec<_> = suggestions
-                        .into_iter()
-                        .map(|s| {
-                            serde_json::json!({
-                                "suggestion": s.suggestion,
-                                "confidence": s.confidence,
-                                "source": s.source
-                            })
-                        })
-                        .collect();
-                    Ok(serde_json::json!(suggestions_json))
-                }
-                Err(e) => Err(format!("Failed to get Ollama suggestions: {}", e)),
-            }
-        }
+    let provider_suggestions = match provider_enum {
+        TranslationProvider::Ollama => OLLAMA_SINGLE_MANAGER
+            .get_suggestions(Some(&app), &source_text, context.as_deref(), 3)
+            .await
+            .map_err(|e| format!("Failed to get Ollama suggestions: {}", e)),
         TranslationProvider::RunPod => {
             let pod_id_str =
                 pod_id.ok_or_else(|| "pod_id is required for RunPod provider".to_string())?;
             let (_, single_manager) = get_runpod_managers(pod_id_str).await;
-            match single_manager
+            single_manager
                 .get_suggestions(Some(&app), &source_text, context.as_deref(), 3)
                 .await
-            {
-                Ok(suggestions) => {
-                    let suggestions_json: Vec<_> = suggestions
-                        .into_iter()
-                        .map(|s| {
-                            serde_json::json!({
-                                "suggestion": s.suggestion,
-                                "confidence": s.confidence,
-                                "source": s.source
-                            })
-                        })
-                        .collect();
-                    Ok(serde_json::json!(suggestions_json))
-                }
-                Err(e) => Err(format!("Failed to get RunPod suggestions: {}", e)),
-            }
+                .map_err(|e| format!("Failed to get RunPod suggestions: {}", e))
+        }
+        TranslationProvider::OpenAiCompat => {
+            let config = require_openai_compat(openai_compat)?;
+            let (_, single_manager) = get_openai_compat_managers(config).await;
+            single_manager
+                .get_suggestions(Some(&app), &source_text, context.as_deref(), 3)
+                .await
+                .map_err(|e| format!("Failed to get OpenAI-compatible suggestions: {}", e))
+        }
+        TranslationProvider::DeepL => {
+            let (_, single_manager) = get_deepl_managers(deepl).await?;
+            single_manager
+                .get_suggestions(&source_text, context.as_deref(), 3)
+                .await
         }
+        TranslationProvider::LibreTranslate => {
+            let (_, single_manager) = get_libretranslate_managers(libretranslate).await?;
+            single_manager
+                .get_suggestions(&source_text, context.as_deref(), 3)
+                .await
+        }
+    };
+
+    let mut suggestions = match lookup_memory_matches(
+        &app,
+        &source_text,
+        source_language.as_deref().unwrap_or("ja"),
+        target_language.as_deref().unwrap_or("fr"),
+        3,
+    )
+    .await
+    {
+        Ok(matches) => matches
+            .into_iter()
+            .map(|m| TranslationSuggestion {
+                suggestion: m.translated_text,
+                confidence: m.similarity as f32,
+                source: "memory".to_string(),
+            })
+            .collect(),
+        Err(e) => {
+            log::warn!("Failed to lookup translation memory: {}", e);
+            Vec::new()
+        }
+    };
+
+    // Memory matches are still worth showing when the provider is unavailable
+    match provider_suggestions {
+        Ok(provider_suggestions) => suggestions.extend(provider_suggestions),
+        Err(e) if suggestions.is_empty() => return Err(e),
+        Err(e) => log::warn!("{}", e),
     }
+
+    let suggestions_json: Vec<_> = suggestions
+        .into_iter()
+        .map(|s| {
+            serde_json::json!({
+                "suggestion": s.suggestion,
+                "confidence": s.confidence,
+                "source": s.source
+            })
+        })
+        .collect();
+    Ok(serde_json::json!(suggestions_json))
 }
 
 /// Translate a single text entry
-/// Routes to Ollama or RunPod based on provider parameter
+/// Routes to Ollama, RunPod, an OpenAI-compatible server or an MT engine based on provider parameter
 #[tauri::command]
 pub async fn translate_single_text(
     app: AppHandle,
-    provider: String, // "ollama" or "runpod"
+    provider: String, // "ollama", "runpod", "openai_compat", "deepl" or "libretranslate"
     source_text: String,
     source_language: Option<String>,
     target_language: Option<String>,
     context: Option<String>,
     model: Option<String>,
     pod_id: Option<String>, // Required for RunPod
+    openai_compat: Option<OpenAiCompatConfig>, // Required for OpenAI-compatible provider
+    deepl: Option<DeepLConfig>,                // Required for DeepL provider
+    libretranslate: Option<LibreTranslateConfig>, // Required for LibreTranslate provider
 ) -> Result<serde_json::Value, String> {
-    let provider_enum = match provider.as_str() {
-        "ollama" => TranslationProvider::Ollama,
-        "runpod" => TranslationProvider::RunPod,
-        _ => {
-            return Err(format!(
-                "Invalid provider: {}. Must be 'ollama' or 'runpod'",
-                provider
-            ))
-        }
-    };
+    let provider_enum = TranslationProvider::parse(&provider)?;
 
     match provider_enum {
         TranslationProvider::Ollama => {
@@ -480,6 +810,7 @@ pub async fn translate_single_text(
                 model,
                 project_id: None,
                 text_type: None,
+                max_length: None,
             };
 
             match OLLAMA_SINGLE_MANAGER.translate(&app, request).await {
@@ -513,6 +844,7 @@ pub async fn translate_single_text(
                 model,
                 project_id: None,
                 text_type: None,
+                max_length: None,
             };
 
             println!(
@@ -541,6 +873,79 @@ pub async fn translate_single_text(
                 }
             }
         }
+        TranslationProvider::OpenAiCompat => {
+            use crate::translation::openai_compat::SingleTranslationRequest;
+
+            let config = require_openai_compat(openai_compat)?;
+
+            let request = SingleTranslationRequest {
+                source_text,
+                source_language,
+                target_language,
+                context,
+                model,
+                project_id: None,
+                text_type: None,
+                max_length: None,
+            };
+
+            let (_, single_manager) = get_openai_compat_managers(config).await;
+            match single_manager.translate(&app, request).await {
+                Ok(result) => Ok(serde_json::json!({
+                    "translated_text": result.translated_text,
+                    "model_used": result.model_used,
+                    "confidence": result.confidence,
+                    "processing_time_ms": result.processing_time_ms
+                })),
+                Err(e) => Err(format!("Failed to translate text with OpenAI-compatible server: {}", e)),
+            }
+        }
+        TranslationProvider::DeepL => {
+            let (_, single_manager) = get_deepl_managers(deepl).await?;
+            let request = OllamaSingleRequest {
+                source_text,
+                source_language,
+                target_language,
+                context,
+                model,
+                project_id: None,
+                text_type: None,
+                max_length: None,
+            };
+
+            match single_manager.translate(request).await {
+                Ok(result) => Ok(serde_json::json!({
+                    "translated_text": result.translated_text,
+                    "model_used": result.model_used,
+                    "confidence": result.confidence,
+                    "processing_time_ms": result.processing_time_ms
+                })),
+                Err(e) => Err(format!("Failed to translate text with DeepL: {}", e)),
+            }
+        }
+        TranslationProvider::LibreTranslate => {
+            let (_, single_manager) = get_libretranslate_managers(libretranslate).await?;
+            let request = OllamaSingleRequest {
+                source_text,
+                source_language,
+                target_language,
+                context,
+                model,
+                project_id: None,
+                text_type: None,
+                max_length: None,
+            };
+
+            match single_manager.translate(request).await {
+                Ok(result) => Ok(serde_json::json!({
+                    "translated_text": result.translated_text,
+                    "model_used": result.model_used,
+                    "confidence": result.confidence,
+                    "processing_time_ms": result.processing_time_ms
+                })),
+                Err(e) => Err(format!("Failed to translate text with LibreTranslate: {}", e)),
+            }
+        }
     }
 }
 
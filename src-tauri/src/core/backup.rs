@@ -0,0 +1,418 @@
+// Injection backups
+// Snapshots of the game files taken before an injection, restorable in one step
+
+use crate::core::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory holding backup sets, relative to the game root
+pub const BACKUP_DIR_NAME: &str = ".ludolingo_backups";
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Backup sets kept per game by [`create_backup`], besides those holding original files
+pub const MAX_BACKUPS: usize = 10;
+
+/// File saved in a backup set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Path relative to the game root, with '/' separators
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Manifest written at the root of each backup set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub id: String,
+    pub created_at: String,
+    /// Injection that created the backup
+    pub injection_id: Option<String>,
+    pub files: Vec<BackupFile>,
+}
+
+/// Summary of a backup set shown to the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub id: String,
+    pub created_at: String,
+    pub injection_id: Option<String>,
+    pub file_count: usize,
+    pub size_bytes: u64,
+}
+
+impl From<&BackupManifest> for BackupInfo {
+    fn from(manifest: &BackupManifest) -> Self {
+        Self {
+            id: manifest.id.clone(),
+            created_at: manifest.created_at.clone(),
+            injection_id: manifest.injection_id.clone(),
+            file_count: manifest.files.len(),
+            size_bytes: manifest.files.iter().map(|f| f.size_bytes).sum(),
+        }
+    }
+}
+
+/// Directory containing all backup sets of a game
+pub fn backups_root(game_path: &Path) -> PathBuf {
+    game_path.join(BACKUP_DIR_NAME)
+}
+
+/// Copy files of the game into a new timestamped backup set
+///
+/// Files must be inside `game_path`; missing files are skipped. Older backup sets
+/// beyond [`MAX_BACKUPS`] are then deleted (see [`prune_backups`]).
+pub fn create_backup(
+    game_path: &Path,
+    files: &[PathBuf],
+    injection_id: Option<&str>,
+) -> AppResult<BackupInfo> {
+    let now = chrono::Utc::now();
+    let root = backups_root(game_path);
+    let base_id = now.format("%Y%m%d-%H%M%S-%3f").to_string();
+
+    // Two backups in the same millisecond get a numeric suffix
+    let mut id = base_id.clone();
+    let mut suffix = 1;
+    while root.join(&id).exists() {
+        id = format!("{}-{}", base_id, suffix);
+        suffix += 1;
+    }
+    let backup_dir = root.join(&id);
+    fs::create_dir_all(&backup_dir)?;
+
+    let mut backup_files = Vec::new();
+    for file in files {
+        if !file.is_file() {
+            continue;
+        }
+        let relative = file.strip_prefix(game_path).map_err(|_| {
+            AppError::FileSystem(format!(
+                "File is outside the game folder: {}",
+                file.display()
+            ))
+        })?;
+
+        let destination = backup_dir.join(relative);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let size_bytes = fs::copy(file, &destination)?;
+
+        backup_files.push(BackupFile {
+            path: relative_to_string(relative),
+            size_bytes,
+        });
+    }
+
+    let manifest = BackupManifest {
+        id,
+        created_at: now.to_rfc3339(),
+        injection_id: injection_id.map(str::to_string),
+        files: backup_files,
+    };
+    fs::write(
+        backup_dir.join(MANIFEST_FILE_NAME),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    // The new backup is saved: failing to clean up older ones is not an error
+    if let Err(e) = prune_backups(game_path, MAX_BACKUPS) {
+        log::warn!("Failed to prune old backups of {}: {}", game_path.display(), e);
+    }
+
+    Ok(BackupInfo::from(&manifest))
+}
+
+/// Delete the backup sets older than the newest `keep` ones
+///
+/// A backup set holding the original copy of a file (see [`original_files`]) is never
+/// deleted: injections start again from those copies, and the game can always be
+/// restored to its state before the first injection. Returns the ids of the deleted sets.
+pub fn prune_backups(game_path: &Path, keep: usize) -> AppResult<Vec<String>> {
+    let backups = list_backups(game_path)?;
+
+    // Oldest first: a backup holds an original when it saved a file no older one has
+    let mut saved = HashSet::new();
+    let mut holding_originals = HashSet::new();
+    for backup in backups.iter().rev() {
+        let (_, files) = backup_contents(game_path, &backup.id)?;
+        for relative in files {
+            if saved.insert(relative) {
+                holding_originals.insert(backup.id.as_str());
+            }
+        }
+    }
+
+    let mut deleted = Vec::new();
+    for backup in backups.iter().skip(keep) {
+        if holding_originals.contains(backup.id.as_str()) {
+            continue;
+        }
+        fs::remove_dir_all(backup_dir(game_path, &backup.id)?)?;
+        deleted.push(backup.id.clone());
+    }
+    Ok(deleted)
+}
+
+/// List the backup sets of a game, newest first
+///
+/// Directories without a readable manifest are ignored.
+pub fn list_backups(game_path: &Path) -> AppResult<Vec<BackupInfo>> {
+    let root = backups_root(game_path);
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(&root)?.flatten() {
+        if !entry.path().is_dir() {
+            continue;
+        }
+        if let Ok(manifest) = read_manifest(&entry.path()) {
+            backups.push(BackupInfo::from(&manifest));
+        }
+    }
+
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    Ok(backups)
+}
+
+/// Restore every file of a backup set over the game files
+///
+/// Returns the number of restored files.
+pub fn restore_backup(game_path: &Path, backup_id: &str) -> AppResult<usize> {
+    let backup_dir = backup_dir(game_path, backup_id)?;
+    let manifest = read_manifest(&backup_dir)?;
+
+    for file in &manifest.files {
+        let source = backup_dir.join(&file.path);
+        let destination = game_path.join(&file.path);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&source, &destination)
+            .map_err(|e| AppError::FileSystem(format!("Failed to restore {}: {}", file.path, e)))?;
+    }
+
+    Ok(manifest.files.len())
+}
+
+/// Directory of a backup set and the files it holds, relative to the game root
+pub fn backup_contents(game_path: &Path, backup_id: &str) -> AppResult<(PathBuf, Vec<PathBuf>)> {
+    let backup_dir = backup_dir(game_path, backup_id)?;
+    let manifest = read_manifest(&backup_dir)?;
+    let files = manifest.files.iter().map(|f| PathBuf::from(&f.path)).collect();
+    Ok((backup_dir, files))
+}
+
+/// Original copy of every file saved across the backup sets of a game
+///
+/// A file injected several times is in several backups: the copy in the oldest one is
+/// the original. Returns each path relative to the game root with the path of that
+/// copy, sorted by relative path.
+pub fn original_files(game_path: &Path) -> AppResult<Vec<(PathBuf, PathBuf)>> {
+    let mut originals = BTreeMap::new();
+    for backup in list_backups(game_path)?.iter().rev() {
+        let (backup_dir, files) = backup_contents(game_path, &backup.id)?;
+        for relative in files {
+            let copy = backup_dir.join(&relative);
+            originals.entry(relative).or_insert(copy);
+        }
+    }
+    Ok(originals.into_iter().collect())
+}
+
+/// Resolve the directory of a backup set, rejecting ids that escape the backup root
+fn backup_dir(game_path: &Path, backup_id: &str) -> AppResult<PathBuf> {
+    let is_plain_name = !backup_id.is_empty()
+        && backup_id != "."
+        && backup_id != ".."
+        && !backup_id.contains(['/', '\\']);
+    if !is_plain_name {
+        return Err(AppError::Validation(format!(
+            "Invalid backup id: {}",
+            backup_id
+        )));
+    }
+
+    let dir = backups_root(game_path).join(backup_id);
+    if !dir.is_dir() {
+        return Err(AppError::FileSystem(format!(
+            "Backup not found: {}",
+            backup_id
+        )));
+    }
+    Ok(dir)
+}
+
+fn read_manifest(backup_dir: &Path) -> AppResult<BackupManifest> {
+    let content = fs::read_to_string(backup_dir.join(MANIFEST_FILE_NAME))?;
+    let manifest: BackupManifest = serde_json::from_str(&content)?;
+
+    // Manifest paths are joined to the game root on restore
+    if manifest
+        .files
+        .iter()
+        .any(|f| Path::new(&f.path).is_absolute() || f.path.split('/').any(|s| s == ".."))
+    {
+        return Err(AppError::Validation(format!(
+            "Backup manifest contains an invalid path: {}",
+            backup_dir.display()
+        )));
+    }
+    Ok(manifest)
+}
+
+fn relative_to_string(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_game_file(game: &Path, relative: &str, content: &str) -> PathBuf {
+        let path = game.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_backup_and_restore() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+        let actors = write_game_file(game, "data/Actors.json", "[null,{\"name\":\"勇者\"}]");
+        let map = write_game_file(game, "data/Map001.json", "{\"events\":[]}");
+
+        let backup = create_backup(game, &[actors.clone(), map.clone()], Some("inj_1")).unwrap();
+        assert_eq!(backup.file_count, 2);
+        assert_eq!(
+            backup.size_bytes,
+            fs::metadata(&actors).unwrap().len() + fs::metadata(&map).unwrap().len()
+        );
+
+        fs::write(&actors, "[null,{\"name\":\"Hero\"}]").unwrap();
+        fs::write(&map, "").unwrap();
+
+        assert_eq!(restore_backup(game, &backup.id).unwrap(), 2);
+        assert_eq!(
+            fs::read_to_string(&actors).unwrap(),
+            "[null,{\"name\":\"勇者\"}]"
+        );
+        assert_eq!(fs::read_to_string(&map).unwrap(), "{\"events\":[]}");
+    }
+
+    #[test]
+    fn test_list_backups_newest_first() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+        let file = write_game_file(game, "dump/db/DataBase.json", "{}");
+
+        assert!(list_backups(game).unwrap().is_empty());
+
+        let first = create_backup(game, std::slice::from_ref(&file), None).unwrap();
+        let second = create_backup(game, &[file], Some("inj_2")).unwrap();
+        assert_ne!(first.id, second.id);
+
+        let backups = list_backups(game).unwrap();
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0].id, second.id);
+        assert_eq!(backups[0].injection_id.as_deref(), Some("inj_2"));
+        assert_eq!(backups[1].id, first.id);
+    }
+
+    #[test]
+    fn test_original_files_take_the_oldest_copy() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+        let actors = write_game_file(game, "data/Actors.json", "[\"勇者\"]");
+        let map = write_game_file(game, "data/Map001.json", "{\"displayName\":\"村\"}");
+
+        let first = create_backup(game, std::slice::from_ref(&actors), None).unwrap();
+        fs::write(&actors, "[\"Hero\"]").unwrap();
+        let second = create_backup(game, &[actors, map], None).unwrap();
+
+        let originals = original_files(game).unwrap();
+        let root = backups_root(game);
+        assert_eq!(
+            originals,
+            vec![
+                (
+                    PathBuf::from("data/Actors.json"),
+                    root.join(&first.id).join("data/Actors.json")
+                ),
+                (
+                    PathBuf::from("data/Map001.json"),
+                    root.join(&second.id).join("data/Map001.json")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prune_backups_keeps_original_copies() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+        let actors = write_game_file(game, "data/Actors.json", "[\"勇者\"]");
+        let map = write_game_file(game, "data/Map001.json", "{\"displayName\":\"村\"}");
+
+        let first = create_backup(game, std::slice::from_ref(&actors), None).unwrap();
+        fs::write(&actors, "[\"Hero\"]").unwrap();
+        let second = create_backup(game, std::slice::from_ref(&actors), None).unwrap();
+        let third = create_backup(game, &[actors.clone(), map], None).unwrap();
+        let fourth = create_backup(game, &[actors], None).unwrap();
+        let originals = original_files(game).unwrap();
+
+        // The first backup holds the original Actors.json, the third the original Map001.json
+        assert_eq!(prune_backups(game, 1).unwrap(), vec![second.id]);
+
+        let kept: Vec<String> = list_backups(game).unwrap().into_iter().map(|b| b.id).collect();
+        assert_eq!(kept, vec![fourth.id, third.id, first.id]);
+        assert_eq!(original_files(game).unwrap(), originals);
+        assert_eq!(prune_backups(game, 0).unwrap(), vec![kept[0].clone()]);
+    }
+
+    #[test]
+    fn test_create_backup_prunes_beyond_retention_limit() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+        let actors = write_game_file(game, "data/Actors.json", "[\"勇者\"]");
+
+        let first = create_backup(game, std::slice::from_ref(&actors), None).unwrap();
+        for _ in 0..MAX_BACKUPS + 2 {
+            create_backup(game, std::slice::from_ref(&actors), None).unwrap();
+        }
+
+        let backups = list_backups(game).unwrap();
+        assert_eq!(backups.len(), MAX_BACKUPS + 1);
+        assert_eq!(backups.last().unwrap().id, first.id);
+    }
+
+    #[test]
+    fn test_missing_files_are_skipped() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+        let missing = game.join("data/Missing.json");
+
+        let backup = create_backup(game, &[missing], None).unwrap();
+        assert_eq!(backup.file_count, 0);
+        assert_eq!(backup.size_bytes, 0);
+    }
+
+    #[test]
+    fn test_restore_rejects_invalid_ids() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+
+        assert!(restore_backup(game, "unknown").is_err());
+        assert!(restore_backup(game, "..").is_err());
+        assert!(restore_backup(game, "../data").is_err());
+    }
+}
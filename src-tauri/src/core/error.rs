@@ -45,3 +45,9 @@ impl From<serde_json::Error> for AppError {
         AppError::Parsing(err.to_string())
     }
 }
+
+impl From<zip::result::ZipError> for AppError {
+    fn from(err: zip::result::ZipError) -> Self {
+        AppError::FileSystem(err.to_string())
+    }
+}
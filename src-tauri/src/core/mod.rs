@@ -1,7 +1,10 @@
 // Core module exports
 // This module contains core functionality used across the application
 
+pub mod backup;
 pub mod error;
+pub mod patch;
+pub mod staging;
 
 // Re-export core types
 pub use error::*;
@@ -0,0 +1,394 @@
+// Patch application
+// Verifies a patch package against a game folder, then applies or reverts it
+
+use super::{
+    delta, sha256_hex, PatchFile, PatchFileKind, PatchManifest, MANIFEST_ENTRY,
+    PATCH_FORMAT_VERSION,
+};
+use crate::core::backup;
+use crate::core::error::{AppError, AppResult};
+use crate::core::staging::StagedFiles;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+/// State of a game file compared with the patch manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileCheckStatus {
+    /// Matches the original the patch applies to (or is absent for an added file)
+    Ready,
+    /// Already matches the patched content
+    AlreadyPatched,
+    /// The patch expects an original file that doesn't exist
+    Missing,
+    /// Differs from both the original and the patched content
+    Modified,
+}
+
+/// Result of checking one game file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileCheck {
+    pub path: String,
+    pub status: FileCheckStatus,
+    /// Checksum of the original the patch expects (none for added files)
+    pub expected_sha256: Option<String>,
+    /// Checksum of the game file (none when absent)
+    pub actual_sha256: Option<String>,
+}
+
+/// Per-file report of a patch verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchVerification {
+    pub engine: String,
+    pub files: Vec<FileCheck>,
+    /// Every file is ready or already patched, and at least one is ready
+    pub applicable: bool,
+    /// Every file already matches the patched content
+    pub already_applied: bool,
+}
+
+impl PatchVerification {
+    /// Files preventing the patch from being applied
+    pub fn problems(&self) -> impl Iterator<Item = &FileCheck> {
+        self.files.iter().filter(|check| {
+            matches!(
+                check.status,
+                FileCheckStatus::Missing | FileCheckStatus::Modified
+            )
+        })
+    }
+}
+
+/// Result of applying a patch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchApplyResult {
+    pub files_patched: usize,
+    /// Backup of the replaced files, used to revert the patch (none when nothing was written)
+    pub backup_id: Option<String>,
+}
+
+/// Patch package opened for verification or application
+pub struct PatchPackage {
+    manifest: PatchManifest,
+    archive: ZipArchive<File>,
+}
+
+impl PatchPackage {
+    /// Open a package and check its manifest
+    pub fn open(package_path: &Path) -> AppResult<Self> {
+        let mut archive = ZipArchive::new(File::open(package_path)?)?;
+        let mut content = String::new();
+        archive
+            .by_name(MANIFEST_ENTRY)?
+            .read_to_string(&mut content)?;
+        let manifest: PatchManifest = serde_json::from_str(&content)?;
+
+        if manifest.format_version != PATCH_FORMAT_VERSION {
+            return Err(AppError::Validation(format!(
+                "Unsupported patch format version: {}",
+                manifest.format_version
+            )));
+        }
+        // Manifest paths are joined to the game root when applying
+        if let Some(file) = manifest
+            .files
+            .iter()
+            .find(|f| !is_safe_relative_path(&f.path))
+        {
+            return Err(AppError::Validation(format!(
+                "Patch manifest contains an invalid path: {}",
+                file.path
+            )));
+        }
+
+        Ok(Self { manifest, archive })
+    }
+
+    pub fn manifest(&self) -> &PatchManifest {
+        &self.manifest
+    }
+
+    /// Compare the game files with the originals the patch expects
+    pub fn verify(&self, game_path: &Path) -> AppResult<PatchVerification> {
+        let mut files = Vec::new();
+        for file in &self.manifest.files {
+            let path = game_path.join(&file.path);
+            let actual_sha256 = if path.is_file() {
+                Some(sha256_hex(&fs::read(&path)?))
+            } else {
+                None
+            };
+
+            let status = if actual_sha256.as_deref() == Some(file.patched_sha256.as_str()) {
+                FileCheckStatus::AlreadyPatched
+            } else if actual_sha256 == file.original_sha256 {
+                FileCheckStatus::Ready
+            } else if actual_sha256.is_none() {
+                FileCheckStatus::Missing
+            } else {
+                FileCheckStatus::Modified
+            };
+
+            files.push(FileCheck {
+                path: file.path.clone(),
+                status,
+                expected_sha256: file.original_sha256.clone(),
+                actual_sha256,
+            });
+        }
+
+        let all_usable = files.iter().all(|check| {
+            matches!(
+                check.status,
+                FileCheckStatus::Ready | FileCheckStatus::AlreadyPatched
+            )
+        });
+        let any_ready = files
+            .iter()
+            .any(|check| check.status == FileCheckStatus::Ready);
+
+        Ok(PatchVerification {
+            engine: self.manifest.engine.clone(),
+            applicable: all_usable && any_ready,
+            already_applied: all_usable && !any_ready,
+            files,
+        })
+    }
+
+    /// Apply the patch to the files that are ready for it
+    ///
+    /// Patched contents are rebuilt and checked before anything is written, then
+    /// committed through a staging copy after backing up the replaced files.
+    pub fn apply(&mut self, game_path: &Path, name: &str) -> AppResult<PatchApplyResult> {
+        let verification = self.verify(game_path)?;
+        if verification.already_applied {
+            return Ok(PatchApplyResult {
+                files_patched: 0,
+                backup_id: None,
+            });
+        }
+        if !verification.applicable {
+            let paths: Vec<&str> = verification.problems().map(|c| c.path.as_str()).collect();
+            return Err(AppError::Validation(format!(
+                "Game files don't match the patch: {}",
+                paths.join(", ")
+            )));
+        }
+
+        let ready: Vec<PatchFile> = self
+            .manifest
+            .files
+            .iter()
+            .zip(&verification.files)
+            .filter(|(_, check)| check.status == FileCheckStatus::Ready)
+            .map(|(file, _)| file.clone())
+            .collect();
+
+        let mut contents = Vec::new();
+        for file in &ready {
+            contents.push(self.patched_content(game_path, file)?);
+        }
+
+        let targets: Vec<PathBuf> = ready.iter().map(|f| game_path.join(&f.path)).collect();
+        let backup = backup::create_backup(game_path, &targets, None)?;
+
+        let mut staged = StagedFiles::stage(game_path, &[], name)?;
+        for (file, content) in ready.iter().zip(&contents) {
+            staged.write_file(Path::new(&file.path), content)?;
+        }
+        let changed = staged.changed_files()?;
+        staged.sync(&changed)?;
+        if let Err(e) = staged.commit(&changed) {
+            // Undo the files already replaced
+            backup::restore_backup(game_path, &backup.id)?;
+            remove_added_files(game_path, &ready)?;
+            return Err(e);
+        }
+
+        Ok(PatchApplyResult {
+            files_patched: ready.len(),
+            backup_id: Some(backup.id),
+        })
+    }
+
+    /// Rebuild the patched content of a file and check its size and checksum
+    fn patched_content(&mut self, game_path: &Path, file: &PatchFile) -> AppResult<Vec<u8>> {
+        let mut entry = Vec::new();
+        self.archive
+            .by_name(&file.entry_name())?
+            .read_to_end(&mut entry)?;
+
+        let content = match file.kind {
+            PatchFileKind::Full => entry,
+            PatchFileKind::Delta => {
+                let original = fs::read(game_path.join(&file.path))?;
+                delta::apply_delta(&original, &entry)?
+            }
+        };
+
+        if content.len() as u64 != file.patched_size
+            || sha256_hex(&content) != file.patched_sha256
+        {
+            return Err(AppError::Validation(format!(
+                "Corrupted patch content: {}",
+                file.path
+            )));
+        }
+        Ok(content)
+    }
+}
+
+/// Undo an applied patch: restore the replaced files and remove the added ones
+///
+/// Returns the number of restored or removed files.
+pub fn revert_patch(
+    game_path: &Path,
+    manifest: &PatchManifest,
+    backup_id: &str,
+) -> AppResult<usize> {
+    let restored = backup::restore_backup(game_path, backup_id)?;
+    let removed = remove_added_files(game_path, &manifest.files)?;
+    Ok(restored + removed)
+}
+
+/// Remove the files a patch added, when they still hold the patched content
+fn remove_added_files(game_path: &Path, files: &[PatchFile]) -> AppResult<usize> {
+    let mut removed = 0;
+    for file in files.iter().filter(|f| f.original_sha256.is_none()) {
+        let path = game_path.join(&file.path);
+        if path.is_file() && sha256_hex(&fs::read(&path)?) == file.patched_sha256 {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn is_safe_relative_path(path: &str) -> bool {
+    !path.is_empty()
+        && !Path::new(path).is_absolute()
+        && !path.starts_with(['/', '\\'])
+        && path.split(['/', '\\']).all(|s| s != ".." && !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{export_patch, PatchExportOptions};
+    use super::*;
+    use tempfile::TempDir;
+
+    const MAP: &str = "{\"displayName\":\"村\",\"events\":[]}";
+
+    fn write_file(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// Game folder, translated copy and the package made from them
+    fn create_patch(temp: &TempDir, delta_min_size: Option<u64>) -> (PathBuf, PathBuf) {
+        let game = temp.path().join("game");
+        let translated = temp.path().join("translated");
+        write_file(&game, "data/Actors.json", "[\"勇者\"]");
+        write_file(&game, "data/Map001.json", MAP);
+        write_file(&translated, "data/Actors.json", "[\"Héros\"]");
+        write_file(
+            &translated,
+            "data/Map001.json",
+            &MAP.replace("村", "Village"),
+        );
+        write_file(&translated, "data/Fonts.json", "{}");
+
+        let files: Vec<PathBuf> = ["data/Actors.json", "data/Map001.json", "data/Fonts.json"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        let package = temp.path().join("patch.zip");
+        let options = PatchExportOptions {
+            engine: "RPG Maker MZ".to_string(),
+            delta_min_size,
+        };
+        export_patch(&game, &translated, &files, &package, &options).unwrap();
+        (game, package)
+    }
+
+    #[test]
+    fn test_apply_and_revert_patch() {
+        let temp = TempDir::new().unwrap();
+        let (game, package_path) = create_patch(&temp, Some(0));
+        let mut package = PatchPackage::open(&package_path).unwrap();
+
+        let verification = package.verify(&game).unwrap();
+        assert!(verification.applicable);
+        assert!(verification
+            .files
+            .iter()
+            .all(|check| check.status == FileCheckStatus::Ready));
+
+        let result = package.apply(&game, "patch_1").unwrap();
+        assert_eq!(result.files_patched, 3);
+        assert_eq!(
+            fs::read_to_string(game.join("data/Actors.json")).unwrap(),
+            "[\"Héros\"]"
+        );
+        assert!(fs::read_to_string(game.join("data/Map001.json"))
+            .unwrap()
+            .contains("Village"));
+        assert!(game.join("data/Fonts.json").is_file());
+        assert!(package.verify(&game).unwrap().already_applied);
+
+        let reverted = revert_patch(&game, package.manifest(), &result.backup_id.unwrap()).unwrap();
+        assert_eq!(reverted, 3);
+        assert_eq!(
+            fs::read_to_string(game.join("data/Actors.json")).unwrap(),
+            "[\"勇者\"]"
+        );
+        assert_eq!(
+            fs::read_to_string(game.join("data/Map001.json")).unwrap(),
+            MAP
+        );
+        assert!(!game.join("data/Fonts.json").exists());
+    }
+
+    #[test]
+    fn test_apply_refuses_modified_files() {
+        let temp = TempDir::new().unwrap();
+        let (game, package_path) = create_patch(&temp, None);
+        write_file(&game, "data/Actors.json", "[\"Another\"]");
+        fs::remove_file(game.join("data/Map001.json")).unwrap();
+        let mut package = PatchPackage::open(&package_path).unwrap();
+
+        let verification = package.verify(&game).unwrap();
+        assert!(!verification.applicable);
+        let problems: Vec<(&str, FileCheckStatus)> = verification
+            .problems()
+            .map(|check| (check.path.as_str(), check.status))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                ("data/Actors.json", FileCheckStatus::Modified),
+                ("data/Map001.json", FileCheckStatus::Missing),
+            ]
+        );
+
+        assert!(package.apply(&game, "patch_2").is_err());
+        assert_eq!(
+            fs::read_to_string(game.join("data/Actors.json")).unwrap(),
+            "[\"Another\"]"
+        );
+        assert!(!game.join("data/Fonts.json").exists());
+        assert!(!game.join(backup::BACKUP_DIR_NAME).exists());
+    }
+
+    #[test]
+    fn test_safe_relative_paths() {
+        assert!(is_safe_relative_path("data/Actors.json"));
+        assert!(!is_safe_relative_path("../data/Actors.json"));
+        assert!(!is_safe_relative_path("/etc/passwd"));
+        assert!(!is_safe_relative_path("data//Actors.json"));
+        assert!(!is_safe_relative_path(""));
+    }
+}
@@ -0,0 +1,219 @@
+// Binary deltas
+// Compact copy/insert encoding of a file against its original, in the spirit of
+// bsdiff/xdelta, so patches don't have to ship whole data files
+
+use crate::core::error::{AppError, AppResult};
+use std::collections::HashMap;
+
+const MAGIC: &[u8] = b"LLDELTA1";
+/// Size of the source blocks matched against the target
+const BLOCK_SIZE: usize = 32;
+
+const OP_COPY: u8 = 0;
+const OP_INSERT: u8 = 1;
+
+/// Encode `target` as copies of `source` ranges and inserted bytes
+///
+/// Source blocks are indexed on block boundaries, then every target position is
+/// looked up: a match is extended forwards and backwards as far as the bytes agree.
+pub fn create_delta(source: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut blocks: HashMap<&[u8], usize> = HashMap::new();
+    for (index, block) in source.chunks_exact(BLOCK_SIZE).enumerate() {
+        blocks.entry(block).or_insert(index * BLOCK_SIZE);
+    }
+
+    let mut delta = MAGIC.to_vec();
+    write_varint(&mut delta, target.len() as u64);
+
+    let mut insert_start = 0;
+    let mut position = 0;
+    while position + BLOCK_SIZE <= target.len() {
+        let Some(&block_offset) = blocks.get(&target[position..position + BLOCK_SIZE]) else {
+            position += 1;
+            continue;
+        };
+
+        // Extend backwards over the pending inserted bytes
+        let mut start = position;
+        let mut source_start = block_offset;
+        while start > insert_start
+            && source_start > 0
+            && target[start - 1] == source[source_start - 1]
+        {
+            start -= 1;
+            source_start -= 1;
+        }
+
+        let mut end = position + BLOCK_SIZE;
+        let mut source_end = block_offset + BLOCK_SIZE;
+        while end < target.len() && source_end < source.len() && target[end] == source[source_end] {
+            end += 1;
+            source_end += 1;
+        }
+
+        write_insert(&mut delta, &target[insert_start..start]);
+        delta.push(OP_COPY);
+        write_varint(&mut delta, source_start as u64);
+        write_varint(&mut delta, (end - start) as u64);
+
+        insert_start = end;
+        position = end;
+    }
+    write_insert(&mut delta, &target[insert_start..]);
+
+    delta
+}
+
+/// Rebuild the target file from its original and a delta made by `create_delta`
+pub fn apply_delta(source: &[u8], delta: &[u8]) -> AppResult<Vec<u8>> {
+    let invalid = |reason: &str| AppError::Validation(format!("Invalid delta: {}", reason));
+
+    let mut reader = delta
+        .strip_prefix(MAGIC)
+        .ok_or_else(|| invalid("unknown format"))?;
+    let target_len = read_varint(&mut reader).ok_or_else(|| invalid("truncated header"))? as usize;
+
+    // The header is untrusted: only reserve what the delta can plausibly produce
+    let mut target = Vec::with_capacity(target_len.min(source.len() + delta.len()));
+    while let Some((&op, rest)) = reader.split_first() {
+        reader = rest;
+        match op {
+            OP_COPY => {
+                let offset = read_varint(&mut reader).ok_or_else(|| invalid("truncated copy"))?;
+                let len = read_varint(&mut reader).ok_or_else(|| invalid("truncated copy"))?;
+                let range = usize::try_from(offset)
+                    .ok()
+                    .zip(usize::try_from(len).ok())
+                    .and_then(|(offset, len)| Some(offset..offset.checked_add(len)?))
+                    .filter(|range| range.end <= source.len())
+                    .ok_or_else(|| invalid("copy outside the original file"))?;
+                target.extend_from_slice(&source[range]);
+            }
+            OP_INSERT => {
+                let len = read_varint(&mut reader).ok_or_else(|| invalid("truncated insert"))?;
+                let len = usize::try_from(len)
+                    .ok()
+                    .filter(|len| *len <= reader.len())
+                    .ok_or_else(|| invalid("truncated insert"))?;
+                target.extend_from_slice(&reader[..len]);
+                reader = &reader[len..];
+            }
+            _ => return Err(invalid("unknown operation")),
+        }
+        if target.len() > target_len {
+            return Err(invalid("unexpected length"));
+        }
+    }
+
+    if target.len() != target_len {
+        return Err(invalid("unexpected length"));
+    }
+    Ok(target)
+}
+
+fn write_insert(delta: &mut Vec<u8>, bytes: &[u8]) {
+    if bytes.is_empty() {
+        return;
+    }
+    delta.push(OP_INSERT);
+    write_varint(delta, bytes.len() as u64);
+    delta.extend_from_slice(bytes);
+}
+
+/// LEB128 encoding
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input.split_first()?;
+        *input = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_file() -> Vec<u8> {
+        (0..200)
+            .map(|i| {
+                format!(
+                    "{{\"id\":{},\"name\":\"アイテム{}\",\"price\":{}}}\n",
+                    i,
+                    i,
+                    i * 10
+                )
+            })
+            .collect::<String>()
+            .into_bytes()
+    }
+
+    #[test]
+    fn test_delta_round_trip() {
+        let source = sample_file();
+        let target = String::from_utf8(source.clone())
+            .unwrap()
+            .replace("アイテム42", "Potion de soin")
+            .replace("\"price\":1000", "\"price\":5")
+            .into_bytes();
+
+        let delta = create_delta(&source, &target);
+        assert!(delta.len() < target.len() / 10);
+        assert_eq!(apply_delta(&source, &delta).unwrap(), target);
+    }
+
+    #[test]
+    fn test_delta_handles_unrelated_and_empty_files() {
+        let source = sample_file();
+        for target in [Vec::new(), b"short".to_vec(), vec![0xff; 1000]] {
+            let delta = create_delta(&source, &target);
+            assert_eq!(apply_delta(&source, &delta).unwrap(), target);
+        }
+        let delta = create_delta(&[], &source);
+        assert_eq!(apply_delta(&[], &delta).unwrap(), source);
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_delta() {
+        let source = sample_file();
+        let delta = create_delta(&source, &source);
+
+        assert!(apply_delta(&source, b"not a delta").is_err());
+        assert!(apply_delta(&source, &delta[..delta.len() - 1]).is_err());
+        // Copies reaching past a shorter original
+        assert!(apply_delta(&source[..100], &delta).is_err());
+    }
+
+    #[test]
+    fn test_apply_rejects_length_mismatch() {
+        let source = sample_file();
+
+        // Header announcing far more bytes than the operations produce
+        let mut delta = MAGIC.to_vec();
+        write_varint(&mut delta, u64::MAX >> 1);
+        write_insert(&mut delta, b"short");
+        assert!(matches!(apply_delta(&source, &delta), Err(AppError::Validation(_))));
+
+        // Operations producing more bytes than the header announces
+        let mut delta = MAGIC.to_vec();
+        write_varint(&mut delta, 2);
+        write_insert(&mut delta, b"short");
+        assert!(matches!(apply_delta(&source, &delta), Err(AppError::Validation(_))));
+    }
+}
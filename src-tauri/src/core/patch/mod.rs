@@ -0,0 +1,307 @@
+// Patch packages
+// Zip archives carrying the files changed by an injection, with the checksums of the
+// original files they apply to
+
+pub mod apply;
+pub mod delta;
+
+use crate::core::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Version of the package layout, bumped on incompatible changes
+pub const PATCH_FORMAT_VERSION: u32 = 1;
+/// Manifest entry at the root of the archive
+pub const MANIFEST_ENTRY: &str = "manifest.json";
+
+/// How a file is stored in the package
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchFileKind {
+    /// Whole patched file, under `files/`
+    Full,
+    /// Binary delta against the original file, under `deltas/`
+    Delta,
+}
+
+/// File changed by the patch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchFile {
+    /// Path relative to the game root, with '/' separators
+    pub path: String,
+    /// Checksum of the file the patch applies to (none for files the patch adds)
+    pub original_sha256: Option<String>,
+    pub original_size: Option<u64>,
+    pub patched_sha256: String,
+    pub patched_size: u64,
+    pub kind: PatchFileKind,
+}
+
+impl PatchFile {
+    /// Archive entry holding the file content or delta
+    pub fn entry_name(&self) -> String {
+        match self.kind {
+            PatchFileKind::Full => format!("files/{}", self.path),
+            PatchFileKind::Delta => format!("deltas/{}.delta", self.path),
+        }
+    }
+}
+
+/// Manifest written at the root of each package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchManifest {
+    pub format_version: u32,
+    pub created_at: String,
+    /// Engine of the game the patch was made from
+    pub engine: String,
+    pub files: Vec<PatchFile>,
+}
+
+/// Options of a patch export
+#[derive(Debug, Clone, Default)]
+pub struct PatchExportOptions {
+    /// Engine name recorded in the manifest
+    pub engine: String,
+    /// Files at least this large are stored as deltas when it makes them smaller
+    /// (no deltas when unset)
+    pub delta_min_size: Option<u64>,
+}
+
+/// Package the files of `patched_root` that differ from `original_root`
+///
+/// `files` are relative to both roots; unchanged files are left out and files missing
+/// from `original_root` are added by the patch. The archive is written next to
+/// `package_path` and renamed once complete.
+pub fn export_patch(
+    original_root: &Path,
+    patched_root: &Path,
+    files: &[PathBuf],
+    package_path: &Path,
+    options: &PatchExportOptions,
+) -> AppResult<PatchManifest> {
+    let originals: Vec<(PathBuf, PathBuf)> = files
+        .iter()
+        .map(|relative| (relative.clone(), original_root.join(relative)))
+        .collect();
+    export_patch_from_originals(&originals, patched_root, package_path, options)
+}
+
+/// Package the files of `patched_root` that differ from their original copy
+///
+/// `originals` pairs each file, relative to `patched_root`, with the path of its
+/// original copy, wherever it is; otherwise works as `export_patch`.
+pub fn export_patch_from_originals(
+    originals: &[(PathBuf, PathBuf)],
+    patched_root: &Path,
+    package_path: &Path,
+    options: &PatchExportOptions,
+) -> AppResult<PatchManifest> {
+    let mut manifest = PatchManifest {
+        format_version: PATCH_FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        engine: options.engine.clone(),
+        files: Vec::new(),
+    };
+    let mut contents = Vec::new();
+
+    for (relative, original_path) in originals {
+        let patched = fs::read(patched_root.join(relative))?;
+        let original = if original_path.is_file() {
+            Some(fs::read(original_path)?)
+        } else {
+            None
+        };
+        if original.as_deref() == Some(patched.as_slice()) {
+            continue;
+        }
+
+        let delta = match (&original, options.delta_min_size) {
+            (Some(original), Some(min_size)) if patched.len() as u64 >= min_size => {
+                Some(delta::create_delta(original, &patched)).filter(|d| d.len() < patched.len())
+            }
+            _ => None,
+        };
+
+        manifest.files.push(PatchFile {
+            path: relative_to_string(relative),
+            original_sha256: original.as_deref().map(sha256_hex),
+            original_size: original.as_ref().map(|o| o.len() as u64),
+            patched_sha256: sha256_hex(&patched),
+            patched_size: patched.len() as u64,
+            kind: if delta.is_some() {
+                PatchFileKind::Delta
+            } else {
+                PatchFileKind::Full
+            },
+        });
+        contents.push(delta.unwrap_or(patched));
+    }
+
+    if manifest.files.is_empty() {
+        return Err(AppError::Validation(
+            "No modified file to package".to_string(),
+        ));
+    }
+
+    let partial_path = package_path.with_extension("partial");
+    let written = write_package(&partial_path, &manifest, &contents)
+        .and_then(|()| fs::rename(&partial_path, package_path).map_err(AppError::from));
+    if written.is_err() {
+        let _ = fs::remove_file(&partial_path);
+    }
+    written.map(|()| manifest)
+}
+
+fn write_package(path: &Path, manifest: &PatchManifest, contents: &[Vec<u8>]) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut writer = ZipWriter::new(File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    writer.start_file(MANIFEST_ENTRY, options)?;
+    writer.write_all(serde_json::to_string_pretty(manifest)?.as_bytes())?;
+    for (file, content) in manifest.files.iter().zip(contents) {
+        writer.start_file(file.entry_name(), options)?;
+        writer.write_all(content)?;
+    }
+
+    writer.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// Hex-encoded SHA-256 of a file content
+pub fn sha256_hex(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+fn relative_to_string(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+    use zip::ZipArchive;
+
+    fn write_file(root: &Path, relative: &str, content: &[u8]) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn read_entry(package: &Path, name: &str) -> Vec<u8> {
+        let mut archive = ZipArchive::new(File::open(package).unwrap()).unwrap();
+        let mut content = Vec::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_end(&mut content)
+            .unwrap();
+        content
+    }
+
+    #[test]
+    fn test_export_packages_only_changed_files() {
+        let temp = TempDir::new().unwrap();
+        let original = temp.path().join("original");
+        let patched = temp.path().join("patched");
+        write_file(&original, "data/Actors.json", "[\"勇者\"]".as_bytes());
+        write_file(&patched, "data/Actors.json", "[\"Héros\"]".as_bytes());
+        write_file(&original, "data/Items.json", b"[]");
+        write_file(&patched, "data/Items.json", b"[]");
+        write_file(&patched, "data/Extra.json", b"{}");
+
+        let files: Vec<PathBuf> = ["data/Actors.json", "data/Items.json", "data/Extra.json"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        let package = temp.path().join("out/patch.zip");
+        let options = PatchExportOptions {
+            engine: "RPG Maker MZ".to_string(),
+            delta_min_size: None,
+        };
+
+        let manifest = export_patch(&original, &patched, &files, &package, &options).unwrap();
+
+        let paths: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["data/Actors.json", "data/Extra.json"]);
+        let actors = &manifest.files[0];
+        assert_eq!(
+            actors.original_sha256.as_deref(),
+            Some(sha256_hex("[\"勇者\"]".as_bytes()).as_str())
+        );
+        assert_eq!(actors.kind, PatchFileKind::Full);
+        assert!(manifest.files[1].original_sha256.is_none());
+
+        assert_eq!(
+            read_entry(&package, "files/data/Actors.json"),
+            "[\"Héros\"]".as_bytes()
+        );
+        let stored: PatchManifest =
+            serde_json::from_slice(&read_entry(&package, MANIFEST_ENTRY)).unwrap();
+        assert_eq!(stored.engine, "RPG Maker MZ");
+        assert!(!package.with_extension("partial").exists());
+    }
+
+    #[test]
+    fn test_export_stores_large_files_as_deltas() {
+        let temp = TempDir::new().unwrap();
+        let original = temp.path().join("original");
+        let patched = temp.path().join("patched");
+        let map: String = (0..500)
+            .map(|i| format!("{{\"x\":{},\"text\":\"村\"}},", i))
+            .collect();
+        write_file(&original, "data/Map001.json", map.as_bytes());
+        let translated = map.replacen("村", "Village", 1);
+        write_file(&patched, "data/Map001.json", translated.as_bytes());
+
+        let package = temp.path().join("patch.zip");
+        let options = PatchExportOptions {
+            engine: "RPG Maker MZ".to_string(),
+            delta_min_size: Some(1024),
+        };
+        let manifest = export_patch(
+            &original,
+            &patched,
+            &[PathBuf::from("data/Map001.json")],
+            &package,
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.files[0].kind, PatchFileKind::Delta);
+        let delta = read_entry(&package, "deltas/data/Map001.json.delta");
+        assert_eq!(
+            delta::apply_delta(map.as_bytes(), &delta).unwrap(),
+            translated.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_export_without_changes_fails() {
+        let temp = TempDir::new().unwrap();
+        write_file(temp.path(), "data/Actors.json", b"[]");
+        let package = temp.path().join("patch.zip");
+
+        let result = export_patch(
+            temp.path(),
+            temp.path(),
+            &[PathBuf::from("data/Actors.json")],
+            &package,
+            &PatchExportOptions::default(),
+        );
+        assert!(result.is_err());
+        assert!(!package.exists());
+    }
+}
@@ -0,0 +1,305 @@
+// Staged injection
+// Injection writes into a staging copy of the game files; they replace the target files
+// (the originals, or those of an output directory) only once every file has been written
+// and synced
+
+use crate::core::error::{AppError, AppResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory holding staging copies, relative to the game root
+pub const STAGING_DIR_NAME: &str = ".ludolingo_staging";
+
+/// Copy of the game files that an injection writes into
+///
+/// The staging directory lives inside the target folder so that files are renamed into
+/// place on the same filesystem. It is removed when the value is dropped.
+pub struct StagedFiles {
+    /// Folder receiving the committed files (the game folder or an output directory)
+    target_path: PathBuf,
+    staging_root: PathBuf,
+    /// Staged files, relative to the game root
+    files: Vec<PathBuf>,
+}
+
+impl StagedFiles {
+    /// Copy `files` (inside `game_path`) into a new staging directory, to commit them
+    /// back over the game files
+    pub fn stage(game_path: &Path, files: &[PathBuf], name: &str) -> AppResult<Self> {
+        Self::stage_to(game_path, game_path, files, name)
+    }
+
+    /// Copy `files` (inside `game_path`) into a new staging directory, to commit them
+    /// into `target_path` at the same relative paths
+    pub fn stage_to(
+        game_path: &Path,
+        target_path: &Path,
+        files: &[PathBuf],
+        name: &str,
+    ) -> AppResult<Self> {
+        let staging_root = target_path.join(STAGING_DIR_NAME).join(name);
+        if staging_root.exists() {
+            fs::remove_dir_all(&staging_root)?;
+        }
+        fs::create_dir_all(&staging_root)?;
+
+        let mut staged = Self {
+            target_path: target_path.to_path_buf(),
+            staging_root,
+            files: Vec::new(),
+        };
+        for file in files {
+            let relative = file.strip_prefix(game_path).map_err(|_| {
+                AppError::FileSystem(format!(
+                    "File is outside the game folder: {}",
+                    file.display()
+                ))
+            })?;
+            let destination = staged.staging_root.join(relative);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(file, &destination)?;
+            staged.files.push(relative.to_path_buf());
+        }
+        Ok(staged)
+    }
+
+    /// Replace the staged files that have an original copy by that copy
+    ///
+    /// `originals` pairs paths relative to the game root with the copy of the file taken
+    /// before its first injection (see `backup::original_files`). Injecting into them
+    /// starts from the game as it was before any injection, as its extracted ids expect.
+    pub fn restore_originals(&self, originals: &[(PathBuf, PathBuf)]) -> AppResult<()> {
+        for (relative, original) in originals {
+            if self.files.contains(relative) {
+                fs::copy(original, self.staging_root.join(relative))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a file into the staging directory, staging it if it wasn't already
+    ///
+    /// `relative` is relative to the game root; the file may not exist in the target yet.
+    pub fn write_file(&mut self, relative: &Path, content: &[u8]) -> AppResult<()> {
+        let destination = self.staging_root.join(relative);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&destination, content)?;
+        if !self.files.iter().any(|file| file == relative) {
+            self.files.push(relative.to_path_buf());
+        }
+        Ok(())
+    }
+
+    /// Game root to hand to the engine handler instead of the real one
+    pub fn root(&self) -> &Path {
+        &self.staging_root
+    }
+
+    /// Staged files whose content differs from the target files (or that the target
+    /// lacks), relative to the game root
+    pub fn changed_files(&self) -> AppResult<Vec<PathBuf>> {
+        let mut changed = Vec::new();
+        for relative in &self.files {
+            let staged = fs::read(self.staging_root.join(relative))?;
+            let current = self.target_path.join(relative);
+            if !current.is_file() || fs::read(&current)? != staged {
+                changed.push(relative.clone());
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Flush the changed files to disk
+    pub fn sync(&self, changed: &[PathBuf]) -> AppResult<()> {
+        for relative in changed {
+            let path = self.staging_root.join(relative);
+            fs::OpenOptions::new()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.sync_all())
+                .map_err(|e| {
+                    AppError::FileSystem(format!("Failed to sync {}: {}", path.display(), e))
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Rename the changed files over the target files
+    ///
+    /// Returns the number of committed files. If a rename fails, the files already
+    /// renamed are left in place: restore the injection backup to undo them.
+    pub fn commit(&self, changed: &[PathBuf]) -> AppResult<usize> {
+        for relative in changed {
+            let destination = self.target_path.join(relative);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(self.staging_root.join(relative), &destination).map_err(|e| {
+                AppError::FileSystem(format!(
+                    "Failed to replace {}: {}",
+                    destination.display(),
+                    e
+                ))
+            })?;
+        }
+        Ok(changed.len())
+    }
+
+    /// Delete the target files under `data_root` that are not staged
+    ///
+    /// An output folder mirrors the data root of the game: files removed from the game
+    /// since the previous injection are removed from the output too. `data_root` is
+    /// relative to the game root. Returns the deleted files, relative to the game root.
+    pub fn remove_unstaged(&self, data_root: &Path) -> AppResult<Vec<PathBuf>> {
+        let mut removed = Vec::new();
+        let staging_dir = self.target_path.join(STAGING_DIR_NAME);
+        let mut pending = vec![self.target_path.join(data_root)];
+        while let Some(current) = pending.pop() {
+            for entry in fs::read_dir(&current).into_iter().flatten().flatten() {
+                let path = entry.path();
+                if path.starts_with(&staging_dir) {
+                    continue;
+                }
+                match entry.file_type() {
+                    Ok(t) if t.is_dir() => pending.push(path),
+                    Ok(_) => {
+                        let Ok(relative) = path.strip_prefix(&self.target_path) else {
+                            continue;
+                        };
+                        if !self.files.iter().any(|file| file == relative) {
+                            fs::remove_file(&path)?;
+                            removed.push(relative.to_path_buf());
+                        }
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
+        removed.sort();
+        Ok(removed)
+    }
+}
+
+impl Drop for StagedFiles {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.staging_root);
+        // Remove the staging parent directory once no injection uses it
+        if let Some(parent) = self.staging_root.parent() {
+            let _ = fs::remove_dir(parent);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_game_file(game: &Path, relative: &str, content: &str) -> PathBuf {
+        let path = game.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_commit_replaces_only_changed_files() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+        let actors = write_game_file(game, "data/Actors.json", "[\"勇者\"]");
+        let items = write_game_file(game, "data/Items.json", "[\"薬草\"]");
+
+        let staged = StagedFiles::stage(game, &[actors.clone(), items.clone()], "inj_1").unwrap();
+        fs::write(staged.root().join("data/Actors.json"), "[\"Héros\"]").unwrap();
+        // Untouched game files until commit
+        assert_eq!(fs::read_to_string(&actors).unwrap(), "[\"勇者\"]");
+
+        let changed = staged.changed_files().unwrap();
+        assert_eq!(changed, vec![PathBuf::from("data/Actors.json")]);
+        staged.sync(&changed).unwrap();
+        assert_eq!(staged.commit(&changed).unwrap(), 1);
+
+        assert_eq!(fs::read_to_string(&actors).unwrap(), "[\"Héros\"]");
+        assert_eq!(fs::read_to_string(&items).unwrap(), "[\"薬草\"]");
+
+        drop(staged);
+        assert!(!game.join(STAGING_DIR_NAME).exists());
+    }
+
+    #[test]
+    fn test_dropping_without_commit_leaves_game_untouched() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path();
+        let actors = write_game_file(game, "data/Actors.json", "[\"勇者\"]");
+
+        let staged = StagedFiles::stage(game, std::slice::from_ref(&actors), "inj_2").unwrap();
+        fs::write(staged.root().join("data/Actors.json"), "[\"Héros\"]").unwrap();
+        drop(staged);
+
+        assert_eq!(fs::read_to_string(&actors).unwrap(), "[\"勇者\"]");
+        assert!(!game.join(STAGING_DIR_NAME).exists());
+    }
+
+    #[test]
+    fn test_stage_to_output_directory_is_incremental() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path().join("game");
+        let output = temp.path().join("output");
+        let actors = write_game_file(&game, "data/Actors.json", "[\"勇者\"]");
+        let items = write_game_file(&game, "data/Items.json", "[\"薬草\"]");
+        let files = vec![actors.clone(), items];
+
+        // First run creates every file in the output
+        let staged = StagedFiles::stage_to(&game, &output, &files, "inj_1").unwrap();
+        fs::write(staged.root().join("data/Actors.json"), "[\"Héros\"]").unwrap();
+        let changed = staged.changed_files().unwrap();
+        assert_eq!(changed.len(), 2);
+        staged.commit(&changed).unwrap();
+        drop(staged);
+
+        assert_eq!(fs::read_to_string(&actors).unwrap(), "[\"勇者\"]");
+        assert_eq!(
+            fs::read_to_string(output.join("data/Actors.json")).unwrap(),
+            "[\"Héros\"]"
+        );
+        assert_eq!(
+            fs::read_to_string(output.join("data/Items.json")).unwrap(),
+            "[\"薬草\"]"
+        );
+
+        // Second run only rewrites what changed
+        let staged = StagedFiles::stage_to(&game, &output, &files, "inj_2").unwrap();
+        fs::write(staged.root().join("data/Actors.json"), "[\"Héros\"]").unwrap();
+        assert!(staged.changed_files().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_remove_unstaged_deletes_files_removed_from_the_game() {
+        let temp = TempDir::new().unwrap();
+        let game = temp.path().join("game");
+        let output = temp.path().join("output");
+        let actors = write_game_file(&game, "data/Actors.json", "[\"勇者\"]");
+        let items = write_game_file(&game, "data/Items.json", "[\"薬草\"]");
+        let readme = write_game_file(&output, "README.txt", "Traduction");
+
+        let files = vec![actors.clone(), items];
+        let staged = StagedFiles::stage_to(&game, &output, &files, "inj_1").unwrap();
+        staged.commit(&staged.changed_files().unwrap()).unwrap();
+        drop(staged);
+
+        // Items.json is gone from the game: the next injection removes it from the output
+        let staged = StagedFiles::stage_to(&game, &output, &[actors], "inj_2").unwrap();
+        assert_eq!(
+            staged.remove_unstaged(Path::new("data")).unwrap(),
+            vec![PathBuf::from("data/Items.json")]
+        );
+        assert!(output.join("data/Actors.json").is_file());
+        assert!(!output.join("data/Items.json").exists());
+        // Files outside the data root are not the game's
+        assert!(readme.is_file());
+    }
+}
@@ -51,12 +51,15 @@ pub fn run() {
             commands::extract_texts_from_folder,
             commands::check_ollama_status,
             commands::check_runpod_status,
+            commands::check_openai_compat_status,
+            commands::check_deepl_status,
+            commands::check_libretranslate_status,
             commands::start_sequential_translation,
             commands::get_sequential_progress,
             commands::pause_sequential_session,
             commands::resume_sequential_session,
             commands::stop_sequential_session,
-            commands::get_project_sessions,
+            commands::requeue_sequential_errors,
             commands::get_translation_suggestions,
             commands::translate_single_text,
             commands::update_translation_entry,
@@ -65,6 +68,14 @@ pub fn run() {
             commands::cancel_injection,
             commands::get_injection_result,
             commands::validate_injection,
+            commands::dry_run_injection,
+            commands::list_injection_backups,
+            commands::rollback_injection,
+            commands::export_patch_package,
+            commands::verify_patch_package,
+            commands::apply_patch_package,
+            commands::revert_patch_package,
+            commands::get_default_length_budgets,
             #[cfg(desktop)]
             commands::check_updates,
         ])
@@ -13,5 +13,19 @@ pub fn get_migrations() -> Vec<Migration> {
             sql: include_str!("../migrations/001_initial_schema.sql"),
             kind: MigrationKind::Up,
         },
+        // Migration 2: Persistent translation sessions
+        Migration {
+            version: 2,
+            description: "create_translation_sessions",
+            sql: include_str!("../migrations/002_translation_sessions.sql"),
+            kind: MigrationKind::Up,
+        },
+        // Migration 3: Translation memory
+        Migration {
+            version: 3,
+            description: "create_translation_memory",
+            sql: include_str!("../migrations/003_translation_memory.sql"),
+            kind: MigrationKind::Up,
+        },
     ]
 }
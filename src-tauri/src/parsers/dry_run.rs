@@ -0,0 +1,225 @@
+// Dry-run injection report
+// Describes what an injection would change without writing into the game
+
+use crate::parsers::engine::{TextEntry, TranslationEntry};
+use crate::parsers::round_trip::{align_entries, joined_lines, AlignedText};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Text that an injection would replace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextChange {
+    pub entry_id: String,
+    pub location: String,
+    pub old_text: String,
+    pub new_text: String,
+}
+
+/// Changes of one game file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChanges {
+    /// Path relative to the game root
+    pub file_path: String,
+    pub changes: Vec<TextChange>,
+}
+
+/// Extracted text that no translation covers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UntranslatedEntry {
+    pub entry_id: String,
+    pub location: String,
+    pub file_path: Option<String>,
+    pub source_text: String,
+}
+
+/// Result of a dry-run injection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InjectionReport {
+    /// Files that would change, sorted by path
+    pub files: Vec<FileChanges>,
+    /// Ids of translations matching no extracted location
+    pub orphan_translations: Vec<String>,
+    /// Extracted locations without a translation
+    pub untranslated_entries: Vec<UntranslatedEntry>,
+}
+
+impl InjectionReport {
+    /// Total number of changed locations
+    pub fn change_count(&self) -> usize {
+        self.files.iter().map(|file| file.changes.len()).sum()
+    }
+}
+
+/// Build the report from the texts extracted before and after injecting into a copy
+///
+/// Only locations present on both sides are compared, paired with `align_entries`: a
+/// message run that word wrap redistributed over other lines is one change, at its
+/// first line.
+pub fn build_injection_report(
+    before: &[TextEntry],
+    after: &[TextEntry],
+    translations: &[TranslationEntry],
+) -> InjectionReport {
+    let translated_ids: HashSet<&str> = translations
+        .iter()
+        .filter(|t| !t.translated_text.is_empty())
+        .map(|t| t.id.as_str())
+        .collect();
+    let extracted_ids: HashSet<&str> = before.iter().map(|entry| entry.id.as_str()).collect();
+
+    let mut files: BTreeMap<String, Vec<TextChange>> = BTreeMap::new();
+    for AlignedText { entries, after } in align_entries(before, after) {
+        let Some(new_text) = after else {
+            continue;
+        };
+        let old_text = joined_lines(&entries, |entry| &entry.source_text);
+        if new_text != old_text {
+            let entry = entries[0];
+            files
+                .entry(entry.file_path.clone().unwrap_or_default())
+                .or_default()
+                .push(TextChange {
+                    entry_id: entry.id.clone(),
+                    location: entry.location.clone(),
+                    old_text,
+                    new_text,
+                });
+        }
+    }
+
+    let mut untranslated_entries = Vec::new();
+    for entry in before {
+        if !translated_ids.contains(entry.id.as_str()) {
+            untranslated_entries.push(UntranslatedEntry {
+                entry_id: entry.id.clone(),
+                location: entry.location.clone(),
+                file_path: entry.file_path.clone(),
+                source_text: entry.source_text.clone(),
+            });
+        }
+    }
+
+    let orphan_translations = translations
+        .iter()
+        .filter(|t| !extracted_ids.contains(t.id.as_str()))
+        .map(|t| t.id.clone())
+        .collect();
+
+    InjectionReport {
+        files: files
+            .into_iter()
+            .map(|(file_path, changes)| FileChanges { file_path, changes })
+            .collect(),
+        orphan_translations,
+        untranslated_entries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::engine::{PromptType, TranslationStatus};
+
+    fn entry(id: &str, file: &str, text: &str) -> TextEntry {
+        TextEntry {
+            id: id.to_string(),
+            source_text: text.to_string(),
+            source_quotes: None,
+            translated_text: String::new(),
+            field_type: String::new(),
+            status: TranslationStatus::NotTranslated,
+            text_type: PromptType::General,
+            location: id.replace('_', ":"),
+            entry_type: String::new(),
+            file_path: Some(file.to_string()),
+        }
+    }
+
+    fn translation(id: &str, text: &str) -> TranslationEntry {
+        TranslationEntry {
+            id: id.to_string(),
+            translated_text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_report_groups_changes_by_file() {
+        let before = vec![
+            entry("item_1_name", "data/Items.json", "薬草"),
+            entry("actor_1_name", "data/Actors.json", "勇者"),
+            entry("actor_2_name", "data/Actors.json", "魔法使い"),
+        ];
+        let after = vec![
+            entry("item_1_name", "data/Items.json", "Herbe"),
+            entry("actor_1_name", "data/Actors.json", "Héros"),
+            entry("actor_2_name", "data/Actors.json", "魔法使い"),
+        ];
+        let translations = vec![
+            translation("item_1_name", "Herbe"),
+            translation("actor_1_name", "Héros"),
+            translation("actor_9_name", "Fantôme"),
+        ];
+
+        let report = build_injection_report(&before, &after, &translations);
+
+        assert_eq!(report.change_count(), 2);
+        assert_eq!(report.files[0].file_path, "data/Actors.json");
+        assert_eq!(
+            report.files[0].changes,
+            vec![TextChange {
+                entry_id: "actor_1_name".to_string(),
+                location: "actor:1:name".to_string(),
+                old_text: "勇者".to_string(),
+                new_text: "Héros".to_string(),
+            }]
+        );
+        assert_eq!(report.files[1].file_path, "data/Items.json");
+        assert_eq!(report.orphan_translations, vec!["actor_9_name".to_string()]);
+        assert_eq!(report.untranslated_entries.len(), 1);
+        assert_eq!(report.untranslated_entries[0].entry_id, "actor_2_name");
+    }
+
+    #[test]
+    fn test_report_pairs_redistributed_messages_in_order() {
+        let file = "data/Map001.json";
+        let before = vec![
+            entry("map_1_event_1_message_1", file, "こんにちは"),
+            entry("map_1_event_1_message_3", file, "またね"),
+        ];
+        // The first message was split over two windows
+        let after = vec![
+            entry("map_1_event_1_message_1", file, "Hello there"),
+            entry("map_1_event_1_message_3", file, "my friend"),
+            entry("map_1_event_1_message_4", file, "See you"),
+        ];
+        let translations = vec![
+            translation("map_1_event_1_message_1", "Hello there my friend"),
+            translation("map_1_event_1_message_3", "See you"),
+        ];
+
+        let report = build_injection_report(&before, &after, &translations);
+
+        assert_eq!(
+            report.files[0].changes,
+            vec![TextChange {
+                entry_id: "map_1_event_1_message_1".to_string(),
+                location: "map:1:event:1:message:1".to_string(),
+                old_text: "こんにちは\nまたね".to_string(),
+                new_text: "Hello there\nmy friend\nSee you".to_string(),
+            }]
+        );
+        assert!(report.orphan_translations.is_empty());
+        assert!(report.untranslated_entries.is_empty());
+    }
+
+    #[test]
+    fn test_empty_translation_counts_as_untranslated() {
+        let before = vec![entry("actor_1_name", "data/Actors.json", "勇者")];
+        let translations = vec![translation("actor_1_name", "")];
+
+        let report = build_injection_report(&before, &before, &translations);
+        assert!(report.files.is_empty());
+        assert!(report.orphan_translations.is_empty());
+        assert_eq!(report.untranslated_entries.len(), 1);
+    }
+}
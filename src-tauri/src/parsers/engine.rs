@@ -52,6 +52,10 @@ pub struct TextUnit {
     pub id: String,
     /// Original source text
     pub source_text: String,
+    /// Opening marks of the source quote pairs, recorded before their normalization
+    /// (see `source_quote_marks`); `None` when the source has no corner brackets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_quotes: Option<String>,
     /// Translated text (empty if not translated)
     pub translated_text: String,
     /// Field type and location information
@@ -66,12 +70,13 @@ pub struct TextUnit {
     /// Structured location identifier for parser_id reconstruction
     /// Format: "object_type:object_id:field" (e.g., "actor:1:name", "map:9:event:1:message:12")
     /// This is stored in the database as the `location` field and used to reconstruct the `parser_id` for injection
-    /// parser_id = location.replace(':', '_') → "actor_1_name" or "map_9_event_1_message_12"
+    /// parser_id = location.replace(':', '_') → "actor_1_name", except for map event
+    /// messages and choices, whose parser_id repeats the event id
     ///
     /// Examples:
     ///   - Actors: "actor:1:name" → parser_id: "actor_1_name"
     ///   - Items: "item:5:description" → parser_id: "item_5_description"
-    ///   - Map events: "map:9:event:1:message:12" → parser_id: "map_9_event_1_message_12"
+    ///   - Map events: "map:9:event:1:message:12" → parser_id: "map_9_event_1_1_message_12"
     ///   - System: "system:game_title" → parser_id: "system_game_title"
     pub location: String,
     /// Entry type (for backward compatibility with TextEntry)
@@ -85,6 +90,7 @@ impl Default for TextUnit {
         Self {
             id: String::new(),
             source_text: String::new(),
+            source_quotes: None,
             translated_text: String::new(),
             field_type: String::new(),
             status: TranslationStatus::NotTranslated,
@@ -1,9 +1,41 @@
 // Game Engine Handler Trait
 // Defines the common interface for all game engine handlers
 
+use crate::core::backup;
+use crate::core::staging::StagedFiles;
+use crate::parsers::dry_run::{build_injection_report, InjectionReport};
 use crate::parsers::engine::{TextEntry, TranslationEntry};
+use crate::parsers::text::length_budget::{BudgetViolation, LengthBudget};
+use crate::parsers::text::word_wrap::WordWrapSettings;
 use std::path::{Path, PathBuf};
 
+/// Options controlling how translations are written back into game files
+///
+/// Engines ignore the options they don't support.
+#[derive(Debug, Clone, Default)]
+pub struct InjectionOptions {
+    /// Re-flow translated dialogue to the message window width (RPG Maker only)
+    pub word_wrap: Option<WordWrapSettings>,
+    /// Only inject into these files, relative to the game root with '/' separators
+    /// (all files when unset)
+    pub files: Option<Vec<String>>,
+}
+
+impl InjectionOptions {
+    /// Whether the file at `relative_path` (relative to the game root) is injected
+    pub fn includes_file(&self, relative_path: &str) -> bool {
+        let relative_path = relative_path.replace('\\', "/");
+        self.files
+            .as_ref()
+            .map_or(true, |files| files.contains(&relative_path))
+    }
+}
+
+/// Callback run after each file is injected, with its path relative to the game root
+///
+/// Returning an error stops the injection before the next file.
+pub type FileInjectedCallback<'a> = dyn FnMut(&str) -> Result<(), String> + 'a;
+
 /// Result of project structure validation
 /// Contains detailed errors and warnings about the project structure
 #[derive(Debug, Clone)]
@@ -79,7 +111,7 @@ impl ValidationResult {
 /// let texts = handler.extract_all_texts(game_path)?;
 /// 
 /// // Inject translations
-/// handler.inject_all_texts(game_path, &translations)?;
+/// handler.inject_all_texts(game_path, &translations, &InjectionOptions::default())?;
 /// ```
 pub trait GameEngineHandler: Send + Sync {
     /// Returns the human-readable name of the game engine
@@ -160,6 +192,7 @@ pub trait GameEngineHandler: Send + Sync {
     /// 
     /// * `game_path` - Path to the game project root directory
     /// * `translations` - Slice of translation entries to inject
+    /// * `options` - Injection options (word wrap, ...)
     /// 
     /// # Returns
     /// 
@@ -177,15 +210,67 @@ pub trait GameEngineHandler: Send + Sync {
     ///     // ... more translations
     /// ];
     /// 
-    /// handler.inject_all_texts(game_path, &translations)?;
+    /// handler.inject_all_texts(game_path, &translations, &InjectionOptions::default())?;
     /// println!("Translations injected successfully");
     /// ```
     fn inject_all_texts(
         &self,
         game_path: &Path,
         translations: &[TranslationEntry],
+        options: &InjectionOptions,
     ) -> Result<(), String>;
 
+    /// Injects translations like `inject_all_texts`, reporting each injected file
+    /// 
+    /// `on_file` is called once a file has been written; an error it returns (e.g.
+    /// when the injection is cancelled) stops the injection before the next file.
+    /// The default implementation injects every file at once, then reports the
+    /// files to process.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,no_run
+    /// handler.inject_all_texts_with_progress(game_path, &translations, &options, &mut |file| {
+    ///     println!("Injected {}", file);
+    ///     Ok(())
+    /// })?;
+    /// ```
+    fn inject_all_texts_with_progress(
+        &self,
+        game_path: &Path,
+        translations: &[TranslationEntry],
+        options: &InjectionOptions,
+        on_file: &mut FileInjectedCallback<'_>,
+    ) -> Result<(), String> {
+        self.inject_all_texts(game_path, translations, options)?;
+        for file in self.list_files_to_process(game_path) {
+            let relative = file.strip_prefix(game_path).unwrap_or(&file);
+            on_file(&relative.to_string_lossy())?;
+        }
+        Ok(())
+    }
+
+    /// Lists the files that will be processed during extraction/injection
+    /// 
+    /// Injection rewrites these files, so they are the ones saved in a backup
+    /// before writing. Missing directories yield an empty list.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `game_path` - Path to the game project root directory
+    /// 
+    /// # Returns
+    /// 
+    /// The paths of the files that will be processed, sorted
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,no_run
+    /// let files = handler.list_files_to_process(game_path);
+    /// let backup = create_backup(game_path, &files, None)?;
+    /// ```
+    fn list_files_to_process(&self, game_path: &Path) -> Vec<PathBuf>;
+
     /// Counts the number of files that will be processed during extraction/injection
     /// 
     /// This method provides an estimate of how many files will be processed, which is useful
@@ -205,7 +290,9 @@ pub trait GameEngineHandler: Send + Sync {
     /// let file_count = handler.count_files_to_process(game_path);
     /// println!("Will process {} files", file_count);
     /// ```
-    fn count_files_to_process(&self, game_path: &Path) -> usize;
+    fn count_files_to_process(&self, game_path: &Path) -> usize {
+        self.list_files_to_process(game_path).len()
+    }
 
     /// Returns the data root directory path for the game engine
     /// 
@@ -231,5 +318,175 @@ pub trait GameEngineHandler: Send + Sync {
     /// println!("Data root: {}", data_root.display());
     /// ```
     fn get_data_root(&self, game_path: &Path) -> PathBuf;
+
+    /// Lists every file under the data root, recursively
+    /// 
+    /// Used to mirror the data root into an output directory: unlike
+    /// `list_files_to_process`, files the engine doesn't translate are included.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `game_path` - Path to the game project root directory
+    /// 
+    /// # Returns
+    /// 
+    /// Paths of the data root files, sorted
+    fn list_data_root_files(&self, game_path: &Path) -> Vec<PathBuf> {
+        list_files_recursive(&self.get_data_root(game_path))
+    }
+
+    /// Returns the default length budgets of the engine's database fields
+    /// 
+    /// Budgets limit the display width of texts shown in fixed-size menus
+    /// (item names, skill descriptions, ...). Engines without known limits return
+    /// an empty list.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,no_run
+    /// let budgets = handler.default_length_budgets();
+    /// let violation = check_length_budget("item:1:name", "Potion", &budgets);
+    /// ```
+    fn default_length_budgets(&self) -> Vec<LengthBudget> {
+        Vec::new()
+    }
+
+    /// Finds the translated messages that would overflow their message window
+    /// 
+    /// Word wrap continues a translated message re-flowed to more lines than its window
+    /// shows in a new window; such messages are reported here as length budget violations.
+    /// Nothing is written into the game. Engines without word wrap return an empty list.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `game_path` - Path to the game project root directory
+    /// * `translations` - Slice of translation entries to inject
+    /// * `word_wrap` - Word wrap settings the injection will use
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(Vec<(String, BudgetViolation)>)` - ID and violation of the overflowing messages,
+    ///   at their first translated line
+    /// * `Err(String)` - Error occurred while reading the game files
+    fn find_message_overflows(
+        &self,
+        _game_path: &Path,
+        _translations: &[TranslationEntry],
+        _word_wrap: &WordWrapSettings,
+    ) -> Result<Vec<(String, BudgetViolation)>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Simulates an injection and reports what it would change, without writing into the game
+    /// 
+    /// Translations are injected into a staging copy of the game files, made in a temporary
+    /// directory outside the game, which is then extracted again and compared with the
+    /// original texts. The copy is deleted afterwards.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `game_path` - Path to the game project root directory
+    /// * `translations` - Slice of translation entries to inject
+    /// * `options` - Injection options (word wrap, ...)
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(InjectionReport)` - Changed locations per file, orphan translations and untranslated locations
+    /// * `Err(String)` - Error occurred during extraction or injection
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,no_run
+    /// let report = handler.dry_run_inject_all_texts(game_path, &translations, &InjectionOptions::default())?;
+    /// for file in &report.files {
+    ///     println!("{}: {} change(s)", file.file_path, file.changes.len());
+    /// }
+    /// ```
+    fn dry_run_inject_all_texts(
+        &self,
+        game_path: &Path,
+        translations: &[TranslationEntry],
+        options: &InjectionOptions,
+    ) -> Result<InjectionReport, String> {
+        let staging_dir = tempfile::TempDir::new()
+            .map_err(|e| format!("Impossible de créer le dossier temporaire: {}", e))?;
+        let staged = stage_game_files(self, game_path, staging_dir.path(), "dry_run")?;
+
+        let before = self.extract_all_texts(staged.root())?;
+        self.inject_all_texts(staged.root(), translations, options)?;
+        let after = self.extract_all_texts(staged.root())?;
+
+        Ok(build_injection_report(&before, &after, translations))
+    }
+}
+
+/// Copies the files to process into a staging directory under `target_path` that the
+/// handler can extract from or inject into
+/// 
+/// Files are staged as they were before any injection into the game folder, from the
+/// original copies in its backups: the extracted ids are those of the original game.
+/// Engines validate their directory layout before extracting, so the folders of the
+/// data root are recreated even when empty. The copy is deleted when dropped.
+pub fn stage_game_files<H: GameEngineHandler + ?Sized>(
+    handler: &H,
+    game_path: &Path,
+    target_path: &Path,
+    name: &str,
+) -> Result<StagedFiles, String> {
+    let files = handler.list_files_to_process(game_path);
+    let staged = StagedFiles::stage_to(game_path, target_path, &files, name)
+        .and_then(|staged| {
+            staged.restore_originals(&backup::original_files(game_path)?)?;
+            Ok(staged)
+        })
+        .map_err(|e| format!("Impossible de préparer les fichiers: {}", e))?;
+
+    let data_root = handler.get_data_root(game_path);
+    if let Ok(relative_root) = data_root.strip_prefix(game_path) {
+        let staged_root = staged.root().join(relative_root);
+        let _ = std::fs::create_dir_all(&staged_root);
+        for entry in std::fs::read_dir(&data_root).into_iter().flatten().flatten() {
+            if entry.path().is_dir() {
+                let _ = std::fs::create_dir_all(staged_root.join(entry.file_name()));
+            }
+        }
+    }
+    Ok(staged)
+}
+
+/// Lists the JSON files directly inside a directory, sorted by path
+/// 
+/// Returns an empty list when the directory doesn't exist.
+pub fn list_json_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
 }
 
+/// Lists the files inside a directory and its subdirectories, sorted by path
+/// 
+/// Returns an empty list when the directory doesn't exist.
+pub fn list_files_recursive(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in std::fs::read_dir(&current).into_iter().flatten().flatten() {
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => pending.push(entry.path()),
+                Ok(t) if t.is_file() => files.push(entry.path()),
+                _ => {}
+            }
+        }
+    }
+    files.sort();
+    files
+}
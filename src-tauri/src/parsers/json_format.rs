@@ -0,0 +1,577 @@
+// Format-preserving JSON serialization
+// Writes injected game data back in the style of the original file
+
+//! RPG Maker writes compact JSON (one database entry per line) while dump tools write
+//! indented JSON. Re-serializing with `to_string_pretty` makes compact files grow several
+//! times and turns every line into a diff. [`to_string_preserving`] instead splices the
+//! updated values into the original text: unchanged values keep their original bytes,
+//! object keys keep their order and only changed values are re-serialized.
+
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::Value;
+
+// Indentation used when a pretty file has no indented line to copy
+const DEFAULT_INDENT: &str = "  ";
+
+/// Overall formatting style of a JSON file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonStyle {
+    /// Indentation unit (None = compact, single line)
+    pub indent: Option<String>,
+    /// Line ending ("\n" or "\r\n")
+    pub newline: &'static str,
+    /// Whether the file ends with a line ending
+    pub trailing_newline: bool,
+}
+
+impl JsonStyle {
+    /// Detect the style of a JSON document
+    pub fn detect(content: &str) -> Self {
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let trailing_newline = content.ends_with('\n');
+
+        let indent = if content.trim_end().contains('\n') {
+            let indent = content
+                .lines()
+                .skip(1)
+                .map(|line| &line[..line.len() - line.trim_start().len()])
+                .find(|leading| !leading.is_empty())
+                .unwrap_or(DEFAULT_INDENT);
+            Some(indent.to_string())
+        } else {
+            None
+        };
+
+        Self {
+            indent,
+            newline,
+            trailing_newline,
+        }
+    }
+
+    /// Serialize a value in this style
+    pub fn to_string<T: Serialize>(&self, value: &T) -> serde_json::Result<String> {
+        let mut output = match &self.indent {
+            Some(indent) => {
+                let mut buffer = Vec::new();
+                let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+                let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+                value.serialize(&mut serializer)?;
+                // serde_json only writes valid UTF-8
+                String::from_utf8(buffer).unwrap_or_default()
+            }
+            None => serde_json::to_string(value)?,
+        };
+
+        if self.newline != "\n" {
+            output = output.replace('\n', self.newline);
+        }
+        if self.trailing_newline {
+            output.push_str(self.newline);
+        }
+        Ok(output)
+    }
+}
+
+/// Serialize `value` in the style of `original`, the JSON document it was read from
+///
+/// Object keys are matched by name: keys of `original` keep their position (and their
+/// bytes when unchanged), keys missing from `original` are defaults filled in by typed
+/// deserialization and are not written. Arrays that changed length (event command lists
+/// where word wrap added 401 lines or a new 101 window) are laid out like the original
+/// array. If `original` can't be parsed, the value is written with the detected [`JsonStyle`].
+pub fn to_string_preserving<T: Serialize>(original: &str, value: &T) -> serde_json::Result<String> {
+    let value = serde_json::to_value(value)?;
+    let style = JsonStyle::detect(original);
+
+    let Some(root) = Scanner::new(original).parse_document() else {
+        return style.to_string(&value);
+    };
+
+    let writer = Writer {
+        text: original,
+        newline: style.newline,
+        indent_unit: style
+            .indent
+            .clone()
+            .unwrap_or_else(|| DEFAULT_INDENT.to_string()),
+    };
+    let root_layout = Layout {
+        multiline: style.indent.is_some(),
+        child_indent: String::new(),
+        close_indent: String::new(),
+        colon: if style.indent.is_some() { ": " } else { ":" }.to_string(),
+    };
+
+    let mut output = String::with_capacity(original.len());
+    output.push_str(&original[..root.start]);
+    writer.write_node(&root, &value, &root_layout, &mut output)?;
+    output.push_str(&original[root.end..]);
+    Ok(output)
+}
+
+/// Value of the original document with its byte span
+struct Node {
+    start: usize,
+    end: usize,
+    kind: NodeKind,
+}
+
+enum NodeKind {
+    Scalar,
+    Array(Vec<Node>),
+    Object(Vec<Member>),
+}
+
+struct Member {
+    key: String,
+    key_start: usize,
+    key_end: usize,
+    value: Node,
+}
+
+/// Minimal JSON scanner recording the span of every value
+struct Scanner<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            bytes: text.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn parse_document(mut self) -> Option<Node> {
+        let root = self.parse_value()?;
+        self.skip_whitespace();
+        (self.pos == self.bytes.len()).then_some(root)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\r' | b'\n')) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.skip_whitespace();
+        (self.peek()? == byte).then(|| self.pos += 1)
+    }
+
+    fn parse_value(&mut self) -> Option<Node> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let kind = match self.peek()? {
+            b'{' => NodeKind::Object(self.parse_members()?),
+            b'[' => NodeKind::Array(self.parse_items()?),
+            b'"' => {
+                self.skip_string()?;
+                NodeKind::Scalar
+            }
+            _ => {
+                while !matches!(
+                    self.peek(),
+                    None | Some(b',' | b']' | b'}' | b' ' | b'\t' | b'\r' | b'\n')
+                ) {
+                    self.pos += 1;
+                }
+                if self.pos == start {
+                    return None;
+                }
+                NodeKind::Scalar
+            }
+        };
+        Some(Node {
+            start,
+            end: self.pos,
+            kind,
+        })
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        self.pos += 1; // Opening quote
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                b'\\' => self.pos += 2,
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn parse_members(&mut self) -> Option<Vec<Member>> {
+        self.pos += 1; // '{'
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == b'}' {
+            self.pos += 1;
+            return Some(members);
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key_start = self.pos;
+            if self.peek()? != b'"' {
+                return None;
+            }
+            self.skip_string()?;
+            let key_end = self.pos;
+            let key = serde_json::from_str(&self.text[key_start..key_end]).ok()?;
+            self.expect(b':')?;
+            let value = self.parse_value()?;
+            members.push(Member {
+                key,
+                key_start,
+                key_end,
+                value,
+            });
+
+            self.skip_whitespace();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b'}' => {
+                    self.pos += 1;
+                    return Some(members);
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn parse_items(&mut self) -> Option<Vec<Node>> {
+        self.pos += 1; // '['
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == b']' {
+            self.pos += 1;
+            return Some(items);
+        }
+
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b']' => {
+                    self.pos += 1;
+                    return Some(items);
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// How the members of a container are laid out
+#[derive(Clone)]
+struct Layout {
+    multiline: bool,
+    /// Whitespace before each member on its line
+    child_indent: String,
+    /// Whitespace before the closing bracket on its line
+    close_indent: String,
+    /// Separator between a key and its value (":" or ": ")
+    colon: String,
+}
+
+struct Writer<'a> {
+    text: &'a str,
+    newline: &'static str,
+    indent_unit: String,
+}
+
+impl Writer<'_> {
+    /// Write `value` at the position of `node`, reusing the original text where possible
+    fn write_node(
+        &self,
+        node: &Node,
+        value: &Value,
+        parent: &Layout,
+        out: &mut String,
+    ) -> serde_json::Result<()> {
+        match (&node.kind, value) {
+            (NodeKind::Object(members), Value::Object(map)) => {
+                let layout = self.layout_of(node, parent);
+                let mut cursor = node.start;
+                for member in members {
+                    if let Some(member_value) = map.get(&member.key) {
+                        out.push_str(&self.text[cursor..member.value.start]);
+                        self.write_node(&member.value, member_value, &layout, out)?;
+                        cursor = member.value.end;
+                    }
+                }
+                out.push_str(&self.text[cursor..node.end]);
+            }
+            (NodeKind::Array(items), Value::Array(values)) if items.len() == values.len() => {
+                let layout = self.layout_of(node, parent);
+                let mut cursor = node.start;
+                for (item, item_value) in items.iter().zip(values) {
+                    out.push_str(&self.text[cursor..item.start]);
+                    self.write_node(item, item_value, &layout, out)?;
+                    cursor = item.end;
+                }
+                out.push_str(&self.text[cursor..node.end]);
+            }
+            (NodeKind::Scalar, _) if !value.is_object() && !value.is_array() => {
+                let original = &self.text[node.start..node.end];
+                let unchanged = serde_json::from_str::<Value>(original).is_ok_and(|v| v == *value);
+                if unchanged {
+                    out.push_str(original);
+                } else {
+                    out.push_str(&serde_json::to_string(value)?);
+                }
+            }
+            _ => self.write_new(value, Some(node), parent, out)?,
+        }
+        Ok(())
+    }
+
+    /// Write a value that has no exact counterpart in the original text
+    ///
+    /// `template` is the original node at this position or a sibling; its layout and key
+    /// order are copied, and its children are reused where they still match.
+    fn write_new(
+        &self,
+        value: &Value,
+        template: Option<&Node>,
+        parent: &Layout,
+        out: &mut String,
+    ) -> serde_json::Result<()> {
+        let layout = match template {
+            Some(node) if !matches!(node.kind, NodeKind::Scalar) => self.layout_of(node, parent),
+            _ => self.nested_layout(parent),
+        };
+
+        match value {
+            Value::Array(values) => {
+                let items: &[Node] = match template.map(|node| &node.kind) {
+                    Some(NodeKind::Array(items)) => items,
+                    _ => &[],
+                };
+                out.push('[');
+                for (index, item_value) in values.iter().enumerate() {
+                    self.write_separator(index, &layout, out);
+                    match items.get(index) {
+                        Some(item) => self.write_node(item, item_value, &layout, out)?,
+                        None => self.write_new(item_value, items.last(), &layout, out)?,
+                    }
+                }
+                self.write_closing(!values.is_empty(), &layout, out);
+                out.push(']');
+            }
+            Value::Object(map) => {
+                let members: &[Member] = match template.map(|node| &node.kind) {
+                    Some(NodeKind::Object(members)) => members,
+                    _ => &[],
+                };
+                // Template keys first, in their original order
+                let mut keys: Vec<&String> = members
+                    .iter()
+                    .map(|member| &member.key)
+                    .filter(|key| map.contains_key(*key))
+                    .collect();
+                keys.extend(
+                    map.keys()
+                        .filter(|key| !keys.contains(key))
+                        .collect::<Vec<_>>(),
+                );
+
+                out.push('{');
+                for (index, key) in keys.iter().enumerate() {
+                    self.write_separator(index, &layout, out);
+                    out.push_str(&serde_json::to_string(key)?);
+                    out.push_str(&layout.colon);
+                    match members.iter().find(|member| &member.key == *key) {
+                        Some(member) => self.write_node(&member.value, &map[*key], &layout, out)?,
+                        None => self.write_new(&map[*key], None, &layout, out)?,
+                    }
+                }
+                self.write_closing(!keys.is_empty(), &layout, out);
+                out.push('}');
+            }
+            _ => out.push_str(&serde_json::to_string(value)?),
+        }
+        Ok(())
+    }
+
+    fn write_separator(&self, index: usize, layout: &Layout, out: &mut String) {
+        if index > 0 {
+            out.push(',');
+        }
+        if layout.multiline {
+            out.push_str(self.newline);
+            out.push_str(&layout.child_indent);
+        }
+    }
+
+    fn write_closing(&self, has_members: bool, layout: &Layout, out: &mut String) {
+        if layout.multiline && has_members {
+            out.push_str(self.newline);
+            out.push_str(&layout.close_indent);
+        }
+    }
+
+    /// Layout of an original container
+    fn layout_of(&self, node: &Node, parent: &Layout) -> Layout {
+        let (first_child, colon) = match &node.kind {
+            NodeKind::Object(members) => (
+                members.first().map(|m| m.key_start),
+                members
+                    .first()
+                    .map(|m| self.text[m.key_end..m.value.start].to_string()),
+            ),
+            NodeKind::Array(items) => (items.first().map(|item| item.start), None),
+            NodeKind::Scalar => (None, None),
+        };
+        let colon = colon.unwrap_or_else(|| parent.colon.clone());
+
+        if !self.text[node.start..node.end].contains('\n') {
+            return Layout {
+                multiline: false,
+                child_indent: String::new(),
+                close_indent: String::new(),
+                colon,
+            };
+        }
+
+        let close_indent = line_indent(&self.text[node.start..node.end - 1]);
+        let child_indent = match first_child {
+            Some(start) => line_indent(&self.text[node.start..start]),
+            None => format!("{}{}", close_indent, self.indent_unit),
+        };
+
+        Layout {
+            multiline: true,
+            child_indent,
+            close_indent,
+            colon,
+        }
+    }
+
+    /// Layout of a new container inside `parent`
+    fn nested_layout(&self, parent: &Layout) -> Layout {
+        if !parent.multiline {
+            return parent.clone();
+        }
+        Layout {
+            multiline: true,
+            child_indent: format!("{}{}", parent.child_indent, self.indent_unit),
+            close_indent: parent.child_indent.clone(),
+            colon: parent.colon.clone(),
+        }
+    }
+}
+
+/// Whitespace after the last line break of `text`
+fn line_indent(text: &str) -> String {
+    match text.rfind('\n') {
+        Some(newline) => text[newline + 1..].to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_unchanged_documents_are_byte_identical() {
+        let documents = [
+            // RPG Maker database: one compact entry per line
+            "[\nnull,\n{\"id\":1,\"name\":\"ポーション\",\"price\":50,\"rate\":1.0},\n{\"id\":2,\"name\":\"\\u30a8\\u30fc\\u30c6\\u30eb\",\"note\":\"\"}\n]",
+            // Indented dump with CRLF line endings and a trailing newline
+            "{\r\n    \"zeta\": [1, 2.50, 3e2],\r\n    \"alpha\": {\"b\": null, \"a\": true}\r\n}\r\n",
+            // Compact single-line map
+            "{\"displayName\":\"村\",\"data\":[0,0,1536],\"events\":[null]}",
+        ];
+        for document in documents {
+            let value: Value = serde_json::from_str(document).unwrap();
+            assert_eq!(to_string_preserving(document, &value).unwrap(), document);
+        }
+    }
+
+    #[test]
+    fn test_only_changed_strings_differ() {
+        let original = "[\nnull,\n{\"id\":1,\"name\":\"ポーション\",\"description\":\"HPを回復\",\"price\":50}\n]";
+        let mut value: Value = serde_json::from_str(original).unwrap();
+        value[1]["name"] = json!("Potion \"bleue\"");
+
+        assert_eq!(
+            to_string_preserving(original, &value).unwrap(),
+            "[\nnull,\n{\"id\":1,\"name\":\"Potion \\\"bleue\\\"\",\"description\":\"HPを回復\",\"price\":50}\n]"
+        );
+    }
+
+    #[test]
+    fn test_keys_keep_order_and_defaults_are_not_added() {
+        #[derive(Serialize)]
+        struct Entry {
+            name: String,
+            note: String, // Not in the original file
+        }
+        let original = "{\"name\":\"勇者\"}";
+        let entry = Entry {
+            name: "Héros".to_string(),
+            note: String::new(),
+        };
+        assert_eq!(
+            to_string_preserving(original, &entry).unwrap(),
+            "{\"name\":\"Héros\"}"
+        );
+    }
+
+    #[test]
+    fn test_grown_array_follows_sibling_layout() {
+        let original = "{\n  \"list\": [\n    {\"code\":101,\"parameters\":[\"\"]},\n    {\"code\":401,\"parameters\":[\"こんにちは\"]}\n  ]\n}";
+        let mut value: Value = serde_json::from_str(original).unwrap();
+        // Word wrap spreads the message over an extra 401 line, then a new 101 window
+        value["list"][1]["parameters"][0] = json!("Hello,");
+        let list = value["list"].as_array_mut().unwrap();
+        list.push(json!({"code": 401, "parameters": ["dear"]}));
+        list.push(json!({"code": 101, "parameters": [""]}));
+        list.push(json!({"code": 401, "parameters": ["world"]}));
+
+        assert_eq!(
+            to_string_preserving(original, &value).unwrap(),
+            "{\n  \"list\": [\n    {\"code\":101,\"parameters\":[\"\"]},\n    {\"code\":401,\"parameters\":[\"Hello,\"]},\n    {\"code\":401,\"parameters\":[\"dear\"]},\n    {\"code\":101,\"parameters\":[\"\"]},\n    {\"code\":401,\"parameters\":[\"world\"]}\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn test_detect_style() {
+        assert_eq!(JsonStyle::detect("{\"a\":1}").indent, None);
+
+        let style = JsonStyle::detect("{\r\n\t\"a\": 1\r\n}\r\n");
+        assert_eq!(style.indent.as_deref(), Some("\t"));
+        assert_eq!(style.newline, "\r\n");
+        assert!(style.trailing_newline);
+        assert_eq!(
+            style.to_string(&json!({"a": 1})).unwrap(),
+            "{\r\n\t\"a\": 1\r\n}\r\n"
+        );
+    }
+
+    #[test]
+    fn test_unparsable_original_falls_back_to_detected_style() {
+        let output = to_string_preserving("{\"a\":", &json!({"a": 1})).unwrap();
+        assert_eq!(output, "{\"a\":1}");
+    }
+}
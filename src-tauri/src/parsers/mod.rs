@@ -1,9 +1,12 @@
 // Parsers module exports
 // This module contains game engine parsers and text processing
 
+pub mod dry_run;
 pub mod engine;
 pub mod factory;
 pub mod handler;
+pub mod json_format;
+pub mod round_trip;
 pub mod rpg_maker;
 pub mod text;
 pub mod wolfrpg;
@@ -0,0 +1,376 @@
+// Post-injection round-trip check
+// Compares the texts extracted from an injected game with the original texts and the
+// translations that were injected
+
+use crate::parsers::engine::TextEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Difference found between the injected game and what the injection should produce
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RoundTripMismatch {
+    /// The location of a translation can no longer be extracted
+    MissingLocation { entry_id: String },
+    /// The location doesn't hold the injected translation
+    WrongTranslation {
+        entry_id: String,
+        expected: String,
+        actual: String,
+    },
+    /// A location without translation changed
+    UnexpectedChange {
+        entry_id: String,
+        before: String,
+        after: String,
+    },
+}
+
+/// Mismatch with the location and file of the entry it concerns
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocatedMismatch {
+    pub location: String,
+    pub file_path: Option<String>,
+    #[serde(flatten)]
+    pub mismatch: RoundTripMismatch,
+}
+
+/// Original entries paired with the text an injected copy holds at their place
+#[derive(Debug, Clone)]
+pub struct AlignedText<'a> {
+    /// Original entries, in extraction order (several for a re-flowed message run)
+    pub entries: Vec<&'a TextEntry>,
+    /// Text read back after injection, lines of a run joined by line breaks; `None` when
+    /// the location can no longer be extracted
+    pub after: Option<String>,
+}
+
+/// Pair the entries extracted before and after an injection
+///
+/// Entries are paired by id, except those of event command lists (locations with a
+/// `message` or `choice` part). Word wrap redistributes messages over new commands
+/// there, which shifts the command index in the ids that follow, so each list is paired
+/// in order: a run of consecutive message lines goes with the run at the same place
+/// after injection, line by line when both runs have as many lines, or as a whole
+/// otherwise. Each choice goes with the choice at the same place.
+pub fn align_entries<'a>(before: &'a [TextEntry], after: &'a [TextEntry]) -> Vec<AlignedText<'a>> {
+    let after_by_id: HashMap<&str, &TextEntry> = after
+        .iter()
+        .map(|entry| (entry.id.as_str(), entry))
+        .collect();
+    let after_runs = command_list_runs(after);
+    let before_runs = command_list_runs(before);
+
+    let mut aligned = Vec::new();
+    let mut aligned_lists = HashSet::new();
+    for entry in before {
+        let Some(list) = command_list(&entry.location) else {
+            aligned.push(AlignedText {
+                entries: vec![entry],
+                after: after_by_id.get(entry.id.as_str()).map(|e| e.source_text.clone()),
+            });
+            continue;
+        };
+        if !aligned_lists.insert(list) {
+            continue;
+        }
+
+        let injected_runs = after_runs.get(list).map(Vec::as_slice).unwrap_or_default();
+        for (position, run) in before_runs[list].iter().enumerate() {
+            match injected_runs.get(position) {
+                Some(injected) if injected.len() == run.len() => {
+                    aligned.extend(run.iter().zip(injected).map(|(entry, injected)| {
+                        AlignedText {
+                            entries: vec![*entry],
+                            after: Some(injected.source_text.clone()),
+                        }
+                    }));
+                }
+                Some(injected) => aligned.push(AlignedText {
+                    entries: run.clone(),
+                    after: Some(joined_lines(injected, |e| &e.source_text)),
+                }),
+                None => aligned.extend(run.iter().map(|entry| AlignedText {
+                    entries: vec![*entry],
+                    after: None,
+                })),
+            }
+        }
+    }
+    aligned
+}
+
+/// Event command list of a location: the part before its `message` or `choice` part
+fn command_list(location: &str) -> Option<&str> {
+    [":message:", ":choice:"]
+        .iter()
+        .filter_map(|marker| location.find(marker))
+        .min()
+        .map(|end| &location[..end])
+}
+
+/// Entries of each event command list, grouped into runs of consecutive message lines
+/// and single choices, in extraction order
+fn command_list_runs(entries: &[TextEntry]) -> HashMap<&str, Vec<Vec<&TextEntry>>> {
+    let mut runs: HashMap<&str, Vec<Vec<&TextEntry>>> = HashMap::new();
+    for entry in entries {
+        let Some(list) = command_list(&entry.location) else {
+            continue;
+        };
+        let is_message = entry.location[list.len()..].starts_with(":message:");
+        let list_runs = runs.entry(list).or_default();
+        let continues_run = is_message
+            && list_runs.last().and_then(|run| run.last()).is_some_and(|last| {
+                last.location[list.len()..].starts_with(":message:")
+            });
+        match list_runs.last_mut() {
+            Some(run) if continues_run => run.push(entry),
+            _ => list_runs.push(vec![entry]),
+        }
+    }
+    runs
+}
+
+/// Texts of a run of entries, one per line
+pub fn joined_lines<'a>(
+    entries: &[&'a TextEntry],
+    text: impl Fn(&'a TextEntry) -> &'a str,
+) -> String {
+    entries.iter().map(|entry| text(entry)).collect::<Vec<_>>().join("\n")
+}
+
+/// Check the texts extracted after injection against the original ones
+///
+/// `expected` maps entry ids to the injected translation, as the extraction should
+/// read it back; other locations must keep their original text. Entries are paired
+/// with `align_entries`. Translations may be read back with other line breaks, as
+/// word wrap reflows them: a break matches a space of the translation, or nothing when
+/// the line was cut inside a word. Other characters and spacing runs must be the same.
+/// A message run redistributed over other lines is compared as a whole and reported
+/// on its first translated line.
+pub fn check_round_trip(
+    before: &[TextEntry],
+    after: &[TextEntry],
+    expected: &HashMap<String, String>,
+) -> Vec<LocatedMismatch> {
+    let mut mismatches = Vec::new();
+    for AlignedText { entries, after } in align_entries(before, after) {
+        let translated = entries
+            .iter()
+            .find(|entry| expected.contains_key(&entry.id))
+            .copied();
+        let (entry, mismatch) = match (translated, after) {
+            (_, None) => {
+                mismatches.extend(entries.iter().map(|entry| LocatedMismatch {
+                    location: entry.location.clone(),
+                    file_path: entry.file_path.clone(),
+                    mismatch: RoundTripMismatch::MissingLocation {
+                        entry_id: entry.id.clone(),
+                    },
+                }));
+                continue;
+            }
+            (Some(entry), Some(actual)) => {
+                let translation = joined_lines(&entries, |e| {
+                    expected.get(&e.id).map_or(e.source_text.as_str(), String::as_str)
+                });
+                let reflowed = matches_reflowed(&actual, &translation);
+                let mismatch = (!reflowed).then(|| RoundTripMismatch::WrongTranslation {
+                    entry_id: entry.id.clone(),
+                    expected: translation,
+                    actual,
+                });
+                (entry, mismatch)
+            }
+            (None, Some(actual)) => {
+                let original = joined_lines(&entries, |e| &e.source_text);
+                let mismatch = (actual != original).then(|| RoundTripMismatch::UnexpectedChange {
+                    entry_id: entries[0].id.clone(),
+                    before: original,
+                    after: actual,
+                });
+                (entries[0], mismatch)
+            }
+        };
+
+        if let Some(mismatch) = mismatch {
+            mismatches.push(LocatedMismatch {
+                location: entry.location.clone(),
+                file_path: entry.file_path.clone(),
+                mismatch,
+            });
+        }
+    }
+    mismatches
+}
+
+/// Whether a text read back after injection is the translation, up to its line breaks
+fn matches_reflowed(read_back: &str, translation: &str) -> bool {
+    let read_back = normalize_lines(read_back);
+    let translation = normalize_lines(translation).replace('\n', " ");
+    let mut expected = translation.chars().peekable();
+    for c in read_back.chars() {
+        if c == '\n' {
+            // Wrapped at a space, or inside a word
+            expected.next_if_eq(&' ');
+        } else if expected.next() != Some(c) {
+            return false;
+        }
+    }
+    expected.next().is_none()
+}
+
+/// Comparable form of a text: extraction placeholders for line breaks and Japanese
+/// quotes are folded like the formatter folds them, spacing is collapsed and trimmed
+/// around line breaks
+fn normalize_lines(text: &str) -> String {
+    text.replace("[CTRL_NEWLINE]", "\n")
+        .replace(['「', '」'], "\"")
+        .replace(['『', '』'], "'")
+        .split('\n')
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::engine::{PromptType, TranslationStatus};
+
+    fn entry(id: &str, text: &str) -> TextEntry {
+        TextEntry {
+            id: id.to_string(),
+            source_text: text.to_string(),
+            source_quotes: None,
+            translated_text: String::new(),
+            field_type: String::new(),
+            status: TranslationStatus::NotTranslated,
+            text_type: PromptType::General,
+            location: id.replace('_', ":"),
+            entry_type: String::new(),
+            file_path: Some("data/Actors.json".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_accepts_reflowed_translations() {
+        let before = vec![entry("actor_1_name", "勇者"), entry("actor_2_name", "村人")];
+        let after = vec![
+            entry("actor_1_name", "Le héros[CTRL_NEWLINE]courageux"),
+            entry("actor_2_name", "村人"),
+        ];
+        let expected =
+            HashMap::from([("actor_1_name".to_string(), "Le héros courageux".to_string())]);
+
+        assert!(check_round_trip(&before, &after, &expected).is_empty());
+    }
+
+    #[test]
+    fn test_round_trip_accepts_lines_cut_inside_words() {
+        let before = vec![entry("map_1_event_1_1_message_1", "ようこそ")];
+        let after = vec![entry(
+            "map_1_event_1_1_message_1",
+            "Anticonstitution[CTRL_NEWLINE]nellement  vôtre",
+        )];
+        let expected = HashMap::from([(
+            "map_1_event_1_1_message_1".to_string(),
+            "Anticonstitutionnellement vôtre".to_string(),
+        )]);
+        assert!(check_round_trip(&before, &after, &expected).is_empty());
+
+        // Line breaks don't hide other differences
+        for actual in [
+            "Anticonstitution[CTRL_NEWLINE]nelle ment vôtre",
+            "Anticonstitutionnellementvôtre",
+        ] {
+            let after = vec![entry("map_1_event_1_1_message_1", actual)];
+            assert_eq!(check_round_trip(&before, &after, &expected).len(), 1, "{}", actual);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_pairs_redistributed_messages_in_order() {
+        let before = vec![
+            entry("map_1_event_1_message_1", "こんにちは"),
+            entry("map_1_event_1_message_2", "元気ですか"),
+            entry("map_1_event_1_choice_3_0", "はい"),
+            entry("map_1_event_1_message_5", "さようなら"),
+            entry("actor_1_name", "勇者"),
+        ];
+        let expected = HashMap::from([
+            ("map_1_event_1_message_1".to_string(), "Hello there my friend".to_string()),
+            ("map_1_event_1_message_2".to_string(), "How are you?".to_string()),
+            ("map_1_event_1_choice_3_0".to_string(), "Yes".to_string()),
+            ("map_1_event_1_message_5".to_string(), "Goodbye".to_string()),
+        ]);
+        // Three lines over two windows: every later command index moved
+        let after = |goodbye: &str| {
+            vec![
+                entry("map_1_event_1_message_1", "Hello there"),
+                entry("map_1_event_1_message_2", "my friend"),
+                entry("map_1_event_1_message_4", "How are you?"),
+                entry("map_1_event_1_choice_5_0", "Yes"),
+                entry("map_1_event_1_message_7", goodbye),
+                entry("actor_1_name", "勇者"),
+            ]
+        };
+
+        assert!(check_round_trip(&before, &after("Goodbye"), &expected).is_empty());
+
+        let mismatches: Vec<RoundTripMismatch> =
+            check_round_trip(&before, &after("Au revoir"), &expected)
+                .into_iter()
+                .map(|m| m.mismatch)
+                .collect();
+        assert_eq!(
+            mismatches,
+            vec![RoundTripMismatch::WrongTranslation {
+                entry_id: "map_1_event_1_message_5".to_string(),
+                expected: "Goodbye".to_string(),
+                actual: "Au revoir".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_round_trip_reports_mismatches() {
+        let before = vec![
+            entry("actor_1_name", "勇者"),
+            entry("actor_2_name", "村人"),
+            entry("actor_3_name", "王様"),
+        ];
+        let after = vec![
+            entry("actor_1_name", "勇者"),
+            entry("actor_2_name", "Villageois"),
+        ];
+        let expected = HashMap::from([
+            ("actor_1_name".to_string(), "Héros".to_string()),
+            ("actor_3_name".to_string(), "Roi".to_string()),
+        ]);
+
+        let mismatches: Vec<RoundTripMismatch> = check_round_trip(&before, &after, &expected)
+            .into_iter()
+            .map(|m| m.mismatch)
+            .collect();
+        assert_eq!(
+            mismatches,
+            vec![
+                RoundTripMismatch::WrongTranslation {
+                    entry_id: "actor_1_name".to_string(),
+                    expected: "Héros".to_string(),
+                    actual: "勇者".to_string(),
+                },
+                RoundTripMismatch::UnexpectedChange {
+                    entry_id: "actor_2_name".to_string(),
+                    before: "村人".to_string(),
+                    after: "Villageois".to_string(),
+                },
+                RoundTripMismatch::MissingLocation {
+                    entry_id: "actor_3_name".to_string(),
+                },
+            ]
+        );
+    }
+}
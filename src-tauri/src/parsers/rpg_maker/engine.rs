@@ -2,7 +2,12 @@
 // Handles version detection and orchestrates file parsing
 
 use crate::parsers::engine::{GameEngine, TextEntry, TranslationEntry};
-use crate::parsers::rpg_maker::files::handler::{extract_all_texts, inject_all_texts};
+use crate::parsers::handler::{FileInjectedCallback, InjectionOptions};
+use crate::parsers::rpg_maker::files::handler::{
+    extract_all_texts, find_message_overflows, inject_all_texts, inject_all_texts_with_progress,
+};
+use crate::parsers::text::length_budget::BudgetViolation;
+use crate::parsers::text::word_wrap::WordWrapSettings;
 use std::path::{Path, PathBuf};
 
 /// RPG Maker engine handler
@@ -74,9 +79,31 @@ impl RpgMakerEngine {
         game_path: &Path,
         translations: &[TranslationEntry],
         version: GameEngine,
+        options: &InjectionOptions,
     ) -> Result<(), String> {
         // Use the centralized handler to inject into all supported files
-        inject_all_texts(game_path, version, translations)
+        inject_all_texts(game_path, version, translations, options)
+    }
+
+    /// Inject translations back into game files, calling `on_file` after each file
+    pub fn inject_all_with_progress(
+        game_path: &Path,
+        translations: &[TranslationEntry],
+        version: GameEngine,
+        options: &InjectionOptions,
+        on_file: &mut FileInjectedCallback<'_>,
+    ) -> Result<(), String> {
+        inject_all_texts_with_progress(game_path, version, translations, options, on_file)
+    }
+
+    /// Find the translated messages that overflow their window once re-flowed
+    pub fn find_message_overflows(
+        game_path: &Path,
+        translations: &[TranslationEntry],
+        version: GameEngine,
+        word_wrap: &WordWrapSettings,
+    ) -> Result<Vec<(String, BudgetViolation)>, String> {
+        find_message_overflows(game_path, version, translations, word_wrap)
     }
 }
 
@@ -310,6 +310,7 @@ mod tests {
         let text_unit_1 = TextUnit {
             id: "actor_1_name".to_string(),
             source_text: "たえちゃん".to_string(),
+            source_quotes: None,
             translated_text: "Tae-chan".to_string(),
             field_type: "name:www/data/Actors.json:1".to_string(),
             status: TranslationStatus::Translated,
@@ -321,6 +322,7 @@ mod tests {
         let text_unit_2 = TextUnit {
             id: "actor_2_name".to_string(),
             source_text: "お兄ちゃん".to_string(),
+            source_quotes: None,
             translated_text: "Onii-chan".to_string(),
             field_type: "name:www/data/Actors.json:2".to_string(),
             status: TranslationStatus::Translated,
@@ -262,6 +262,7 @@ mod tests {
             .map(|t| TextUnit {
                 id: t.id.clone(),
                 source_text: String::new(),
+                source_quotes: None,
                 translated_text: t.translated_text.clone(),
                 field_type: String::new(),
                 status: TranslationStatus::Translated,
@@ -1,9 +1,13 @@
 use crate::core::error::{AppError, AppResult};
 use crate::parsers::engine::{PromptType, TextUnit, TranslationStatus};
+use crate::parsers::json_format::to_string_preserving;
 use crate::parsers::rpg_maker::RpgMakerTextValidator;
 use crate::parsers::text::formatter::EngineFormatter;
 use crate::parsers::text::formatter::RpgMakerFormatter;
-use std::collections::HashMap;
+use crate::parsers::text::formatter::source_quote_marks;
+use crate::parsers::text::length_budget::BudgetViolation;
+use crate::parsers::text::word_wrap::{measure_width, wrap_text, WordWrapSettings};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
@@ -72,6 +76,7 @@ pub fn extract_text_units_for_object(
         units.push(TextUnit {
             id: format!("{}_{}_{}", object_type, object_id, field),
             source_text: prepared_text, // Formatted text ready for translation
+            source_quotes: source_quote_marks(value),
             translated_text: String::new(),
             field_type: format!("{}:{}:{}", field, file_path, index),
             status: TranslationStatus::NotTranslated,
@@ -173,7 +178,7 @@ where
 /// 1. Parsing JSON into Vec<Option<T>>
 /// 2. Creating a HashMap for text unit lookup
 /// 3. Updating each object with translated text
-/// 4. Serializing back to JSON
+/// 4. Serializing back to JSON in the original file's style
 ///
 /// # Arguments
 ///
@@ -236,8 +241,8 @@ where
         }
     }
 
-    // Serialize the updated objects back to JSON
-    let updated_content = serde_json::to_string_pretty(&objects)
+    // Serialize the updated objects back to JSON, keeping the original formatting
+    let updated_content = to_string_preserving(&content, &objects)
         .map_err(|e| AppError::Parsing(format!("Failed to serialize {}: {}", file_name, e)))?;
 
     // Write the updated content back to the file
@@ -356,6 +361,7 @@ pub fn extract_text_units_from_event_commands(
                                     object_type, object_id, command_index
                                 ),
                                 source_text: prepared_text, // Formatted text ready for translation
+                                source_quotes: source_quote_marks(text),
                                 translated_text: String::new(),
                                 field_type: format!(
                                     "message:{}:{}:{}",
@@ -405,6 +411,7 @@ pub fn extract_text_units_from_event_commands(
                                             object_type, object_id, command_index, choice_index
                                         ),
                                         source_text: prepared_text, // Formatted text ready for translation
+                                        source_quotes: source_quote_marks(choice_text),
                                         translated_text: String::new(),
                                         field_type: format!(
                                             "choice:{}:{}:{}:{}",
@@ -460,12 +467,17 @@ pub fn extract_text_units_from_event_commands(
 /// * `text_unit_map` - HashMap of text units for lookup
 ///
 /// Updates the command parameters with translated text if available.
+///
+/// # Returns
+/// * `HashSet<usize>` - Indices of the 401 (message) commands that received a translation
 pub fn inject_text_units_into_event_commands(
     object_type: &str,
     object_id: i32,
     commands: &mut [EventCommand],
     text_unit_map: &HashMap<String, &TextUnit>,
-) {
+) -> HashSet<usize> {
+    let mut injected_messages = HashSet::new();
+
     for (command_index, command) in commands.iter_mut().enumerate() {
         match command.code {
             401 => {
@@ -496,6 +508,7 @@ pub fn inject_text_units_into_event_commands(
                                     restored_text
                                 );
                                 *text_param = serde_json::Value::String(restored_text);
+                                injected_messages.insert(command_index);
                             } else {
                                 log::debug!(
                                     "Event command text unit has empty translation, skipping"
@@ -574,4 +587,298 @@ pub fn inject_text_units_into_event_commands(
             }
         }
     }
+
+    injected_messages
+}
+
+/// Re-flows translated Show Text blocks to the message window width
+///
+/// A Show Text block is a 101 command (window attributes) followed by its 401 lines.
+/// Only blocks where at least one line was translated are touched. Each translated
+/// line is wrapped on its own (authored line breaks such as speaker names are kept),
+/// using the narrower width when the 101 command shows a face graphic. The displayed
+/// lines are then redistributed one per 401 command, reusing the block's commands
+/// first; when they exceed `max_lines`, the overflow continues in a new message
+/// window with a copy of the 101 command.
+///
+/// Commands are inserted, so this must run after all text units were injected into
+/// the list (command indices of later commands shift). Blocks continued in a new
+/// window are returned as budget violations, with the text unit ID and location of
+/// their first translated line (as extracted, before wrapping).
+///
+/// # Arguments
+/// * `object_type` - Type of object owning the list (e.g., "common_event")
+/// * `object_id` - ID of the object
+/// * `commands` - Event command list to update
+/// * `injected_messages` - Indices of translated 401 commands (see `inject_text_units_into_event_commands`)
+/// * `settings` - Word wrap settings
+///
+/// # Returns
+/// * `Vec<(String, BudgetViolation)>` - Text unit ID and violation of the blocks displaying
+///   more lines than `max_lines`
+pub fn wrap_message_commands(
+    object_type: &str,
+    object_id: i32,
+    commands: &mut Vec<EventCommand>,
+    injected_messages: &HashSet<usize>,
+    settings: &WordWrapSettings,
+) -> Vec<(String, BudgetViolation)> {
+    let mut violations = Vec::new();
+    if injected_messages.is_empty() {
+        return violations;
+    }
+
+    let max_lines = settings.max_lines.max(1);
+    let metrics = settings.font_metrics.as_ref();
+    let original = std::mem::take(commands);
+    let mut index = 0;
+
+    while index < original.len() {
+        let command = &original[index];
+        if command.code != 101 {
+            commands.push(command.clone());
+            index += 1;
+            continue;
+        }
+
+        // The 401 lines following the Show Text command
+        let block_end = original[index + 1..]
+            .iter()
+            .position(|c| c.code != 401)
+            .map_or(original.len(), |offset| index + 1 + offset);
+        let block_lines = &original[index + 1..block_end];
+        let Some(first_translated) =
+            (index + 1..block_end).find(|i| injected_messages.contains(i))
+        else {
+            commands.extend_from_slice(&original[index..block_end]);
+            index = block_end;
+            continue;
+        };
+
+        // Face graphic name is the first parameter of the 101 command
+        let has_face = command
+            .parameters
+            .first()
+            .and_then(|p| p.as_str())
+            .is_some_and(|face| !face.is_empty());
+        let width = settings.line_width(has_face);
+
+        let mut displayed_lines = Vec::new();
+        for (line_index, line) in block_lines.iter().enumerate() {
+            let text = line
+                .parameters
+                .first()
+                .and_then(|p| p.as_str())
+                .unwrap_or_default();
+            if injected_messages.contains(&(index + 1 + line_index)) {
+                displayed_lines.extend(wrap_text(text, width, metrics));
+            } else {
+                displayed_lines.push(text.to_string());
+            }
+        }
+
+        if displayed_lines.len() > max_lines {
+            let unit_id = format!("{}_{}_message_{}", object_type, object_id, first_translated);
+            let violation = BudgetViolation {
+                location: format!("{}:{}:message:{}", object_type, object_id, first_translated),
+                pattern: "*:*:message:*".to_string(),
+                max_cells: width,
+                max_lines: Some(max_lines),
+                actual_cells: displayed_lines
+                    .iter()
+                    .map(|line| measure_width(line, metrics))
+                    .max()
+                    .unwrap_or(0),
+                actual_lines: displayed_lines.len(),
+            };
+            violations.push((unit_id, violation));
+        }
+
+        // Every line is at least one displayed line, so the block's 401 commands are
+        // all reused before new ones are added
+        let line_indent = block_lines[0].indent;
+        let mut line_commands = block_lines.iter();
+        for page_lines in displayed_lines.chunks(max_lines) {
+            commands.push(command.clone());
+            for text in page_lines {
+                let mut line = line_commands.next().cloned().unwrap_or_else(|| EventCommand {
+                    code: 401,
+                    indent: line_indent,
+                    parameters: Vec::new(),
+                });
+                let text = serde_json::Value::String(text.clone());
+                match line.parameters.first_mut() {
+                    Some(param) => *param = text,
+                    None => line.parameters.push(text),
+                }
+                commands.push(line);
+            }
+        }
+
+        index = block_end;
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn show_text(face: &str) -> EventCommand {
+        EventCommand {
+            code: 101,
+            indent: 0,
+            parameters: vec![
+                serde_json::json!(face),
+                serde_json::json!(0),
+                serde_json::json!(0),
+                serde_json::json!(2),
+            ],
+        }
+    }
+
+    fn message(text: &str) -> EventCommand {
+        EventCommand {
+            code: 401,
+            indent: 0,
+            parameters: vec![serde_json::json!(text)],
+        }
+    }
+
+    fn message_lines(commands: &[EventCommand]) -> Vec<(i32, String)> {
+        commands
+            .iter()
+            .map(|c| {
+                let text = match c.code {
+                    401 => c.parameters[0].as_str().unwrap_or_default().to_string(),
+                    _ => String::new(),
+                };
+                (c.code, text)
+            })
+            .collect()
+    }
+
+    fn settings() -> WordWrapSettings {
+        WordWrapSettings {
+            max_width: 20,
+            face_max_width: 12,
+            max_lines: 4,
+            font_metrics: None,
+        }
+    }
+
+    #[test]
+    fn test_wrap_message_commands_reflows_translated_block() {
+        let mut commands = vec![
+            show_text(""),
+            message("The hero walks into the village square"),
+            message("Short"),
+            EventCommand {
+                code: 0,
+                indent: 0,
+                parameters: vec![],
+            },
+        ];
+        let injected = HashSet::from([1, 2]);
+
+        let violations =
+            wrap_message_commands("common_event", 1, &mut commands, &injected, &settings());
+
+        assert!(violations.is_empty());
+        assert_eq!(
+            message_lines(&commands),
+            vec![
+                (101, String::new()),
+                (401, "The hero walks into".to_string()),
+                (401, "the village square".to_string()),
+                (401, "Short".to_string()),
+                (0, String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_message_commands_face_window_is_narrower() {
+        let mut commands = vec![show_text("Actor1"), message("The hero walks in")];
+        let injected = HashSet::from([1]);
+
+        wrap_message_commands("common_event", 1, &mut commands, &injected, &settings());
+
+        assert_eq!(
+            message_lines(&commands),
+            vec![
+                (101, String::new()),
+                (401, "The hero".to_string()),
+                (401, "walks in".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_message_commands_splits_overflow_into_new_window() {
+        let mut commands = vec![
+            show_text("Actor1"),
+            message("ようこそ"),
+            message("one two three four five six seven eight nine ten"),
+            show_text(""),
+            message("Next window"),
+        ];
+        let injected = HashSet::from([2, 4]);
+        let settings = WordWrapSettings {
+            face_max_width: 10,
+            max_width: 12,
+            max_lines: 2,
+            ..settings()
+        };
+
+        let violations =
+            wrap_message_commands("common_event", 3, &mut commands, &injected, &settings);
+
+        let codes: Vec<i32> = commands.iter().map(|c| c.code).collect();
+        assert_eq!(
+            codes,
+            vec![101, 401, 401, 101, 401, 401, 101, 401, 401, 101, 401, 101, 401]
+        );
+        assert_eq!(commands[1].parameters[0], "ようこそ", "untranslated line is kept");
+        for command in &commands[..11] {
+            match command.code {
+                101 => assert_eq!(command.parameters[0], "Actor1", "window copies the face"),
+                _ => {
+                    let line = command.parameters[0].as_str().unwrap();
+                    assert!(line.chars().count() <= 10, "'{}' exceeds width", line);
+                }
+            }
+        }
+        assert_eq!(commands[12].parameters[0], "Next window");
+
+        // Reported with the command index the extraction gave the line
+        assert_eq!(violations.len(), 1, "only the first block overflows");
+        let (unit_id, violation) = &violations[0];
+        assert_eq!(unit_id, "common_event_3_message_2");
+        assert_eq!(violation.location, "common_event:3:message:2");
+        assert_eq!(violation.max_lines, Some(2));
+        assert_eq!(violation.actual_lines, 7);
+    }
+
+    #[test]
+    fn test_wrap_message_commands_skips_untranslated_blocks() {
+        let original = vec![
+            show_text(""),
+            message("これはとても長い日本語の文章で、折り返しされてはいけません"),
+        ];
+        let mut commands = original.clone();
+
+        wrap_message_commands("common_event", 1, &mut commands, &HashSet::new(), &settings());
+        assert_eq!(message_lines(&commands), message_lines(&original));
+
+        wrap_message_commands(
+            "common_event",
+            1,
+            &mut commands,
+            &HashSet::from([5]),
+            &settings(),
+        );
+        assert_eq!(message_lines(&commands), message_lines(&original));
+    }
 }
@@ -1,5 +1,7 @@
 use crate::core::error::{AppError, AppResult};
 use crate::parsers::engine::{PromptType, TextUnit};
+use crate::parsers::text::length_budget::BudgetViolation;
+use crate::parsers::text::word_wrap::WordWrapSettings;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
@@ -8,7 +10,7 @@ use super::common::{
     extract_text_from_file_with_objects, extract_text_units_for_object,
     extract_text_units_from_event_commands, inject_text_units_for_object,
     inject_text_units_into_event_commands, inject_translations_into_file_with_objects,
-    EventCommand, GameDataFile,
+    wrap_message_commands, EventCommand, GameDataFile,
 };
 
 /// Represents a single common event in RPG Maker MV CommonEvents.json
@@ -48,12 +50,6 @@ pub struct CommonEvent {
 /// # Returns
 /// * `AppResult<GameDataFile>` - Game data file with extracted text units
 pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataFile> {
-    // Parse function for CommonEvents.json
-    let parse_common_events = |content: &str| -> AppResult<Vec<Option<CommonEvent>>> {
-        serde_json::from_str(content)
-            .map_err(|e| AppError::Parsing(format!("Failed to parse CommonEvents.json: {}", e)))
-    };
-
     // Extract function for each common event
     let extract_common_event_units =
         |common_event: &CommonEvent, index: usize, file_path: &str| -> Vec<TextUnit> {
@@ -98,10 +94,14 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
 
 /// Injects translated text back into CommonEvents.json
 ///
+/// Messages that overflow their window after word wrap continue in a new window and are
+/// logged (validation reports them, see `find_message_overflows`).
+///
 /// # Arguments
 /// * `project_path` - Path to the project directory
 /// * `file_path` - Relative path to the CommonEvents.json file
 /// * `text_units` - Vector of translated text units
+/// * `word_wrap` - Optional word wrap settings for translated messages
 ///
 /// # Returns
 /// * `AppResult<()>` - Success or error
@@ -109,31 +109,19 @@ pub fn inject_translations(
     project_path: &Path,
     file_path: &str,
     text_units: &[&TextUnit],
+    word_wrap: Option<&WordWrapSettings>,
 ) -> AppResult<()> {
-    // Parse function for CommonEvents.json
-    let parse_common_events = |content: &str| -> AppResult<Vec<Option<CommonEvent>>> {
-        serde_json::from_str(content)
-            .map_err(|e| AppError::Parsing(format!("Failed to parse CommonEvents.json: {}", e)))
-    };
-
     // Update function for each common event
     let update_common_event =
         |common_event: &mut CommonEvent, text_unit_map: &HashMap<String, &TextUnit>| {
-            // Update event name
-            inject_text_units_for_object(
-                "common_event",
-                common_event.id,
-                text_unit_map,
-                vec![("name", &mut common_event.name)],
-            );
-
-            // Update text in event commands using common helper
-            inject_text_units_into_event_commands(
-                "common_event",
-                common_event.id,
-                &mut common_event.list,
-                text_unit_map,
-            );
+            for (_, overflow) in apply_translations(common_event, text_unit_map, word_wrap) {
+                log::warn!(
+                    "Message at {} continued in a new window ({} lines, max {:?})",
+                    overflow.location,
+                    overflow.actual_lines,
+                    overflow.max_lines
+                );
+            }
         };
 
     // Use the common function
@@ -146,3 +134,73 @@ pub fn inject_translations(
         update_common_event,
     )
 }
+
+/// Finds the translated messages of CommonEvents.json that overflow their message window
+///
+/// The translations are injected and re-flowed in memory only, the file is not written.
+pub fn find_message_overflows(
+    project_path: &Path,
+    file_path: &str,
+    text_units: &[&TextUnit],
+    word_wrap: &WordWrapSettings,
+) -> AppResult<Vec<(String, BudgetViolation)>> {
+    let content = std::fs::read_to_string(project_path.join(file_path))
+        .map_err(|e| AppError::FileSystem(format!("Failed to read {}: {}", file_path, e)))?;
+    let common_events = parse_common_events(&content)?;
+
+    let text_unit_map: HashMap<String, &TextUnit> = text_units
+        .iter()
+        .map(|unit| (unit.id.clone(), *unit))
+        .collect();
+
+    Ok(common_events
+        .into_iter()
+        .flatten()
+        .flat_map(|mut common_event| {
+            apply_translations(&mut common_event, &text_unit_map, Some(word_wrap))
+        })
+        .collect())
+}
+
+/// Parse function for CommonEvents.json
+fn parse_common_events(content: &str) -> AppResult<Vec<Option<CommonEvent>>> {
+    serde_json::from_str(content)
+        .map_err(|e| AppError::Parsing(format!("Failed to parse CommonEvents.json: {}", e)))
+}
+
+/// Updates a common event with the translated texts
+///
+/// Returns the messages that overflow their window after word wrap.
+fn apply_translations(
+    common_event: &mut CommonEvent,
+    text_unit_map: &HashMap<String, &TextUnit>,
+    word_wrap: Option<&WordWrapSettings>,
+) -> Vec<(String, BudgetViolation)> {
+    // Update event name
+    inject_text_units_for_object(
+        "common_event",
+        common_event.id,
+        text_unit_map,
+        vec![("name", &mut common_event.name)],
+    );
+
+    // Update text in event commands using common helper
+    let injected_messages = inject_text_units_into_event_commands(
+        "common_event",
+        common_event.id,
+        &mut common_event.list,
+        text_unit_map,
+    );
+
+    // Re-flow translated messages to the message window width
+    match word_wrap {
+        Some(settings) => wrap_message_commands(
+            "common_event",
+            common_event.id,
+            &mut common_event.list,
+            &injected_messages,
+            settings,
+        ),
+        None => Vec::new(),
+    }
+}
@@ -2,7 +2,10 @@
 // Centralizes all file extraction and injection logic for RPG Maker projects
 
 use crate::parsers::engine::{GameEngine, TextEntry, TextUnit, TranslationEntry};
-use std::path::Path;
+use crate::parsers::handler::{FileInjectedCallback, InjectionOptions};
+use crate::parsers::text::length_budget::BudgetViolation;
+use crate::parsers::text::word_wrap::WordWrapSettings;
+use std::path::{Path, PathBuf};
 
 /// Extract all translatable texts from supported RPG Maker files
 ///
@@ -161,24 +164,7 @@ fn extract_map_data_files(
         return Ok(());
     }
 
-    // Find all MapXXX.json files (excluding MapInfos.json)
-    let map_files: Vec<_> = std::fs::read_dir(&data_dir)
-        .map_err(|e| format!("Failed to read data directory: {}", e))?
-        .filter_map(|entry| entry.ok())
-        .map(|entry| entry.path())
-        .filter(|path| {
-            path.file_name()
-                .and_then(|n| n.to_str())
-                .map(|n| {
-                    // Must start with "Map", end with ".json", and NOT be "MapInfos.json"
-                    n.starts_with("Map")
-                        && n.ends_with(".json")
-                        && n != "MapInfos.json"
-                        && n.len() > 7 // MapXXX.json minimum (Map001.json = 11 chars)
-                })
-                .unwrap_or(false)
-        })
-        .collect();
+    let map_files = list_map_files(&data_dir)?;
 
     log::info!("Found {} map files to process", map_files.len());
 
@@ -204,6 +190,7 @@ fn extract_map_data_files(
                     all_entries.push(TextEntry {
                         id: unit.id.clone(),
                         source_text: unit.source_text.clone(),
+                        source_quotes: unit.source_quotes.clone(),
                         translated_text: unit.translated_text.clone(),
                         field_type: unit.field_type.clone(),
                         status: unit.status.clone(),
@@ -224,20 +211,9 @@ fn extract_map_data_files(
     Ok(())
 }
 
-/// Inject translations into all MapXXX.json files
-fn inject_map_data_files(
-    game_path: &Path,
-    data_prefix: &str,
-    translations: &[TranslationEntry],
-) -> Result<(), String> {
-    let data_dir = game_path.join(data_prefix);
-
-    if !data_dir.exists() {
-        return Ok(());
-    }
-
-    // Find all MapXXX.json files (excluding MapInfos.json)
-    let map_files: Vec<_> = std::fs::read_dir(&data_dir)
+/// Find all MapXXX.json files of a data directory (excluding MapInfos.json)
+fn list_map_files(data_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    Ok(std::fs::read_dir(data_dir)
         .map_err(|e| format!("Failed to read data directory: {}", e))?
         .filter_map(|entry| entry.ok())
         .map(|entry| entry.path())
@@ -253,7 +229,24 @@ fn inject_map_data_files(
                 })
                 .unwrap_or(false)
         })
-        .collect();
+        .collect())
+}
+
+/// Inject translations into all MapXXX.json files
+fn inject_map_data_files(
+    game_path: &Path,
+    data_prefix: &str,
+    translations: &[TranslationEntry],
+    options: &InjectionOptions,
+    on_file: &mut FileInjectedCallback<'_>,
+) -> Result<(), String> {
+    let data_dir = game_path.join(data_prefix);
+
+    if !data_dir.exists() {
+        return Ok(());
+    }
+
+    let map_files = list_map_files(&data_dir)?;
 
     log::info!("Found {} map files for injection", map_files.len());
 
@@ -263,6 +256,9 @@ fn inject_map_data_files(
             .map_err(|e| format!("Failed to get relative path: {}", e))?
             .to_str()
             .ok_or("Invalid path encoding")?;
+        if !options.includes_file(relative_path) {
+            continue;
+        }
 
         // Extract map ID from filename
         let file_name = map_path
@@ -291,6 +287,7 @@ fn inject_map_data_files(
                 .map(|t| crate::parsers::engine::TextUnit {
                     id: t.id.clone(),
                     source_text: String::new(),
+                    source_quotes: None,
                     translated_text: t.translated_text.clone(),
                     field_type: String::new(),
                     status: crate::parsers::engine::TranslationStatus::Translated,
@@ -306,14 +303,101 @@ fn inject_map_data_files(
                 game_path,
                 relative_path,
                 &text_unit_refs,
+                options.word_wrap.as_ref(),
             )
             .map_err(|e| format!("Injection failed for {}: {}", relative_path, e))?;
         }
+        on_file(relative_path)?;
     }
 
     Ok(())
 }
 
+/// Find the translated messages that overflow their message window once re-flowed
+///
+/// Messages are only wrapped in maps and common events, so only those files are read.
+/// Translations are injected and re-flowed in memory like an injection with `word_wrap`
+/// would do, without writing into the game.
+///
+/// # Returns
+/// * `Result<Vec<(String, BudgetViolation)>, String>` - ID and violation of the overflowing
+///   messages, at their first translated line
+pub fn find_message_overflows(
+    game_path: &Path,
+    version: GameEngine,
+    translations: &[TranslationEntry],
+    word_wrap: &WordWrapSettings,
+) -> Result<Vec<(String, BudgetViolation)>, String> {
+    let data_prefix = match version {
+        GameEngine::RpgMakerMZ => "data/",
+        GameEngine::RpgMakerMV => "www/data/",
+        _ => panic!("RpgMaker handler should only be used with RPG Maker engines"),
+    };
+    let dialogue_units = |prefix: &str| -> Vec<TextUnit> {
+        translations
+            .iter()
+            .filter(|t| t.id.starts_with(prefix))
+            .map(|t| TextUnit {
+                id: t.id.clone(),
+                translated_text: t.translated_text.clone(),
+                status: crate::parsers::engine::TranslationStatus::Translated,
+                text_type: crate::parsers::engine::PromptType::Dialogue,
+                ..TextUnit::default()
+            })
+            .collect()
+    };
+    let mut overflows = Vec::new();
+
+    let common_events_path = format!("{}CommonEvents.json", data_prefix);
+    let text_units = dialogue_units("common_event_");
+    if !text_units.is_empty() && game_path.join(&common_events_path).exists() {
+        let text_unit_refs: Vec<_> = text_units.iter().collect();
+        overflows.extend(
+            crate::parsers::rpg_maker::files::common_events::find_message_overflows(
+                game_path,
+                &common_events_path,
+                &text_unit_refs,
+                word_wrap,
+            )
+            .map_err(|e| format!("Word wrap check failed for {}: {}", common_events_path, e))?,
+        );
+    }
+
+    let data_dir = game_path.join(data_prefix);
+    if !data_dir.exists() {
+        return Ok(overflows);
+    }
+    for map_path in list_map_files(&data_dir)? {
+        let relative_path = map_path
+            .strip_prefix(game_path)
+            .map_err(|e| format!("Failed to get relative path: {}", e))?
+            .to_str()
+            .ok_or("Invalid path encoding")?;
+        let map_id: i32 = map_path
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n[3..].parse().ok())
+            .ok_or_else(|| format!("Invalid map file name: {}", relative_path))?;
+
+        let text_units = dialogue_units(&format!("map_{}_", map_id));
+        if text_units.is_empty() {
+            continue;
+        }
+        let text_unit_refs: Vec<_> = text_units.iter().collect();
+        overflows.extend(
+            crate::parsers::rpg_maker::files::map_data::find_message_overflows(
+                game_path,
+                relative_path,
+                &text_unit_refs,
+                word_wrap,
+            )
+            .map_err(|e| format!("Word wrap check failed for {}: {}", relative_path, e))?,
+        );
+    }
+
+    Ok(overflows)
+}
+
 /// Helper function to extract from a specific file
 fn extract_from_file<F>(
     game_path: &Path,
@@ -378,6 +462,7 @@ where
                     all_entries.push(TextEntry {
                         id: unit.id.clone(),
                         source_text: unit.source_text.clone(),
+                        source_quotes: unit.source_quotes.clone(),
                         translated_text: unit.translated_text.clone(),
                         field_type: unit.field_type.clone(),
                         status: unit.status.clone(),
@@ -410,6 +495,7 @@ where
 /// * `game_path` - Path to the game project directory
 /// * `version` - RPG Maker version (MV or MZ)
 /// * `translations` - Vector of translation entries to inject
+/// * `options` - Injection options (word wrap for message windows)
 ///
 /// # Returns
 /// * `Result<(), String>` - Success or error
@@ -417,6 +503,21 @@ pub fn inject_all_texts(
     game_path: &Path,
     version: GameEngine,
     translations: &[TranslationEntry],
+    options: &InjectionOptions,
+) -> Result<(), String> {
+    inject_all_texts_with_progress(game_path, version, translations, options, &mut |_| Ok(()))
+}
+
+/// Inject all translated texts, calling `on_file` after each injected file
+///
+/// `on_file` receives the path of the file relative to the game root; an error it
+/// returns stops the injection before the next file.
+pub fn inject_all_texts_with_progress(
+    game_path: &Path,
+    version: GameEngine,
+    translations: &[TranslationEntry],
+    options: &InjectionOptions,
+    on_file: &mut FileInjectedCallback<'_>,
 ) -> Result<(), String> {
     // Get the correct data prefix based on version
     let data_prefix = match version {
@@ -427,43 +528,43 @@ pub fn inject_all_texts(
 
     // Inject into each supported file
     // Actors.json
-    inject_into_file(game_path, data_prefix, "Actors.json", translations)?;
+    inject_into_file(game_path, data_prefix, "Actors.json", translations, options, on_file)?;
 
     // CommonEvents.json
-    inject_into_file(game_path, data_prefix, "CommonEvents.json", translations)?;
+    inject_into_file(game_path, data_prefix, "CommonEvents.json", translations, options, on_file)?;
 
     // Classes.json
-    inject_into_file(game_path, data_prefix, "Classes.json", translations)?;
+    inject_into_file(game_path, data_prefix, "Classes.json", translations, options, on_file)?;
 
     // Weapons.json
-    inject_into_file(game_path, data_prefix, "Weapons.json", translations)?;
+    inject_into_file(game_path, data_prefix, "Weapons.json", translations, options, on_file)?;
 
     // Items.json
-    inject_into_file(game_path, data_prefix, "Items.json", translations)?;
+    inject_into_file(game_path, data_prefix, "Items.json", translations, options, on_file)?;
 
     // Armors.json
-    inject_into_file(game_path, data_prefix, "Armors.json", translations)?;
+    inject_into_file(game_path, data_prefix, "Armors.json", translations, options, on_file)?;
 
     // Enemies.json
-    inject_into_file(game_path, data_prefix, "Enemies.json", translations)?;
+    inject_into_file(game_path, data_prefix, "Enemies.json", translations, options, on_file)?;
 
     // Skills.json
-    inject_into_file(game_path, data_prefix, "Skills.json", translations)?;
+    inject_into_file(game_path, data_prefix, "Skills.json", translations, options, on_file)?;
 
     // States.json
-    inject_into_file(game_path, data_prefix, "States.json", translations)?;
+    inject_into_file(game_path, data_prefix, "States.json", translations, options, on_file)?;
 
     // Troops.json
-    inject_into_file(game_path, data_prefix, "Troops.json", translations)?;
+    inject_into_file(game_path, data_prefix, "Troops.json", translations, options, on_file)?;
 
     // MapInfos.json
-    inject_into_file(game_path, data_prefix, "MapInfos.json", translations)?;
+    inject_into_file(game_path, data_prefix, "MapInfos.json", translations, options, on_file)?;
 
     // Map data files
-    inject_map_data_files(game_path, data_prefix, translations)?;
+    inject_map_data_files(game_path, data_prefix, translations, options, on_file)?;
 
     // System.json
-    inject_into_file(game_path, data_prefix, "System.json", translations)?;
+    inject_into_file(game_path, data_prefix, "System.json", translations, options, on_file)?;
 
     Ok(())
 }
@@ -474,10 +575,17 @@ fn inject_into_file(
     data_prefix: &str,
     file_name: &str,
     translations: &[TranslationEntry],
+    options: &InjectionOptions,
+    on_file: &mut FileInjectedCallback<'_>,
 ) -> Result<(), String> {
     let relative_path = format!("{}{}", data_prefix, file_name);
     let full_path = game_path.join(&relative_path);
 
+    // File left out of a selective injection
+    if !options.includes_file(&relative_path) {
+        return Ok(());
+    }
+
     if full_path.exists() {
         // Filter translations for this specific file
         let file_translations: Vec<&TranslationEntry> = translations
@@ -511,6 +619,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -534,6 +643,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -561,6 +671,7 @@ fn inject_into_file(
                             crate::parsers::engine::TextUnit {
                                 id: t.id.clone(),
                                 source_text: String::new(), // Not used for injection
+                                source_quotes: None,
                                 translated_text: t.translated_text.clone(),
                                 field_type: String::new(), // Not used for injection
                                 status: crate::parsers::engine::TranslationStatus::Translated,
@@ -585,6 +696,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -608,6 +720,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -631,6 +744,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -654,6 +768,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -677,6 +792,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -700,6 +816,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -723,6 +840,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -738,6 +856,7 @@ fn inject_into_file(
                         game_path,
                         &relative_path,
                         &text_unit_refs,
+                        options.word_wrap.as_ref(),
                     )
                 }
                 "MapInfos.json" => {
@@ -746,6 +865,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -769,6 +889,7 @@ fn inject_into_file(
                         .map(|t| crate::parsers::engine::TextUnit {
                             id: t.id.clone(),
                             source_text: String::new(),
+                            source_quotes: None,
                             translated_text: t.translated_text.clone(),
                             field_type: String::new(),
                             status: crate::parsers::engine::TranslationStatus::Translated,
@@ -791,6 +912,7 @@ fn inject_into_file(
 
             result.map_err(|e| format!("Injection failed for {}: {}", file_name, e))?;
         }
+        on_file(&relative_path)?;
     } else {
         eprintln!(
             "Warning: {} not found at '{}', skipping injection",
@@ -1097,7 +1219,12 @@ mod tests {
             },
         ];
 
-        let result = inject_all_texts(game_path, GameEngine::RpgMakerMZ, &translations);
+        let result = inject_all_texts(
+            game_path,
+            GameEngine::RpgMakerMZ,
+            &translations,
+            &InjectionOptions::default(),
+        );
         assert!(
             result.is_ok(),
             "Injection should succeed: {:?}",
@@ -1121,4 +1248,74 @@ mod tests {
         assert_eq!(weapon_entry.source_text, "Épée");
         assert_eq!(item_entry.source_text, "Potion de Soin");
     }
+
+    #[test]
+    fn test_inject_preserves_original_formatting() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_path = temp_dir.path();
+        let data_dir = game_path.join("data");
+        fs::create_dir_all(&data_dir).unwrap();
+
+        // Compact files as written by the RPG Maker editor
+        let actors_data = "[\nnull,\n{\"id\":1,\"battlerName\":\"Actor1_1\",\"characterIndex\":0,\"name\":\"ハロルド\",\"nickname\":\"\",\"note\":\"\",\"profile\":\"\",\"traits\":[{\"code\":23,\"dataId\":0,\"value\":1}]}\n]";
+        let map_data = "{\n\"autoplayBgm\":false,\"displayName\":\"村\",\"data\":[2816,2816,0],\"events\":[\nnull,\n{\"id\":1,\"name\":\"EV001\",\"note\":\"\",\"pages\":[{\"conditions\":{\"actorValid\":false},\"list\":[{\"code\":101,\"indent\":0,\"parameters\":[\"\",0,0,2]},{\"code\":401,\"indent\":0,\"parameters\":[\"こんにちは\"]},{\"code\":0,\"indent\":0,\"parameters\":[]}],\"moveSpeed\":3}],\"x\":8,\"y\":6}\n],\"width\":17}";
+        let system_data = "{\n\"advanced\":{\"gameId\":1},\"armorTypes\":[\"\",\"盾\"],\"currencyUnit\":\"G\",\"gameTitle\":\"テスト\",\"terms\":{\"basic\":[\"レベル\"],\"commands\":[\"戦う\"],\"params\":[\"最大HP\"],\"messages\":{\"alwaysDash\":\"常時ダッシュ\"}},\"versionId\":123\n}";
+        fs::write(data_dir.join("Actors.json"), actors_data).unwrap();
+        fs::write(data_dir.join("Map001.json"), map_data).unwrap();
+        fs::write(data_dir.join("System.json"), system_data).unwrap();
+
+        // Translations targeting each file but matching no text
+        let translations = vec![
+            TranslationEntry {
+                id: "actor_99_name".to_string(),
+                translated_text: "Inconnu".to_string(),
+            },
+            TranslationEntry {
+                id: "map_1_event_99_name".to_string(),
+                translated_text: "Inconnu".to_string(),
+            },
+            TranslationEntry {
+                id: "system_unknown".to_string(),
+                translated_text: "Inconnu".to_string(),
+            },
+        ];
+        inject_all_texts(
+            game_path,
+            GameEngine::RpgMakerMZ,
+            &translations,
+            &InjectionOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(data_dir.join("Actors.json")).unwrap(),
+            actors_data
+        );
+        assert_eq!(
+            fs::read_to_string(data_dir.join("Map001.json")).unwrap(),
+            map_data
+        );
+        assert_eq!(
+            fs::read_to_string(data_dir.join("System.json")).unwrap(),
+            system_data
+        );
+
+        // A matching translation only changes its own string
+        let translations = vec![TranslationEntry {
+            id: "actor_1_name".to_string(),
+            translated_text: "Harold".to_string(),
+        }];
+        inject_all_texts(
+            game_path,
+            GameEngine::RpgMakerMZ,
+            &translations,
+            &InjectionOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(data_dir.join("Actors.json")).unwrap(),
+            actors_data.replace("ハロルド", "Harold")
+        );
+    }
 }
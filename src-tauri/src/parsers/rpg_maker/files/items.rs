@@ -270,6 +270,7 @@ mod tests {
         let text_unit_name = TextUnit {
             id: "item_1_name".to_string(),
             source_text: "ポーション".to_string(),
+            source_quotes: None,
             translated_text: "Potion".to_string(),
             field_type: "name:www/data/Items.json:1".to_string(),
             status: TranslationStatus::Translated,
@@ -3,16 +3,20 @@
 
 use crate::core::error::{AppError, AppResult};
 use crate::parsers::engine::{PromptType, TextUnit, TranslationEntry};
+use crate::parsers::json_format::to_string_preserving;
 use crate::parsers::text::formatter::EngineFormatter;
 use crate::parsers::text::formatter::RpgMakerFormatter;
+use crate::parsers::text::length_budget::BudgetViolation;
+use crate::parsers::text::word_wrap::WordWrapSettings;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::path::Path;
 
 use super::common::{
     extract_text_units_for_object, extract_text_units_from_event_commands,
-    inject_text_units_for_object, inject_text_units_into_event_commands, EventCommand,
-    GameDataFile,
+    inject_text_units_for_object, inject_text_units_into_event_commands, wrap_message_commands,
+    EventCommand, GameDataFile,
 };
 
 /// Map data structure from RPG Maker MV/MZ
@@ -24,7 +28,9 @@ pub struct MapData {
     /// List of events on this map
     #[serde(default)]
     pub events: Vec<Option<MapEvent>>,
-    // Other fields omitted for brevity (bgm, bgs, tilesetId, data, etc.)
+    /// All other fields (bgm, bgs, tilesetId, data, etc.) preserved to avoid data loss during injection
+    #[serde(flatten)]
+    pub extra_fields: HashMap<String, Value>,
 }
 
 /// Map event structure
@@ -36,7 +42,9 @@ pub struct MapEvent {
     pub name: String,
     /// Event pages (each page can have different conditions and commands)
     pub pages: Vec<MapEventPage>,
-    // Other fields omitted for brevity (x, y, etc.)
+    /// All other fields (x, y, note, etc.) preserved to avoid data loss during injection
+    #[serde(flatten)]
+    pub extra_fields: HashMap<String, Value>,
 }
 
 /// Map event page structure
@@ -44,7 +52,9 @@ pub struct MapEvent {
 pub struct MapEventPage {
     /// List of event commands for this page
     pub list: Vec<EventCommand>,
-    // Other fields omitted for brevity (conditions, image, etc.)
+    /// All other fields (conditions, image, etc.) preserved to avoid data loss during injection
+    #[serde(flatten)]
+    pub extra_fields: HashMap<String, Value>,
 }
 
 /// MapData parser implementation
@@ -163,30 +173,8 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
 
                 // Update context to structured location format for map events
                 for mut unit in page_text_units {
-                    // Reconstruct location from ID: "map_9_event_1_message_12" -> "map:9:event:1:message:12"
-                    // Or parse from existing location and rebuild
-                    if unit.id.starts_with("map_") && unit.id.contains("_event_") {
-                        // Parse: "map_9_event_1_message_12" -> ["map", "9", "event", "1", "message", "12"]
-                        let parts: Vec<&str> = unit.id.split('_').collect();
-                        if parts.len() >= 6 && parts[0] == "map" && parts[2] == "event" {
-                            let map_id_str = parts[1];
-                            let event_id_str = parts[3];
-                            let field_type = parts[4]; // "message" or "choice"
-                            let index = parts[5];
-
-                            if field_type == "message" {
-                                unit.location = format!(
-                                    "map:{}:event:{}:message:{}",
-                                    map_id_str, event_id_str, index
-                                );
-                            } else if field_type == "choice" && parts.len() >= 7 {
-                                let choice_index = parts[6];
-                                unit.location = format!(
-                                    "map:{}:event:{}:choice:{}:{}",
-                                    map_id_str, event_id_str, index, choice_index
-                                );
-                            }
-                        }
+                    if let Some(location) = event_command_location(map_id, event.id, &unit.id) {
+                        unit.location = location;
                     }
                     text_units.push(unit);
                 }
@@ -211,21 +199,77 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
 }
 
 /// Injects translated text back into MapXXX.json
+///
+/// When `word_wrap` is set, translated messages are re-flowed to the message window width;
+/// messages that overflow their window continue in a new window and are logged
+/// (validation reports them, see `find_message_overflows`).
 pub fn inject_translations(
     project_path: &Path,
     file_path: &str,
     text_units: &[&TextUnit],
+    word_wrap: Option<&WordWrapSettings>,
 ) -> AppResult<()> {
     let full_path = project_path.join(file_path);
+    let (content, mut map_data) = read_map_data(project_path, file_path)?;
+
+    let overflows = apply_translations(&mut map_data, file_path, text_units, word_wrap)?;
+    for (_, overflow) in &overflows {
+        log::warn!(
+            "Message at {} continued in a new window ({} lines, max {:?})",
+            overflow.location,
+            overflow.actual_lines,
+            overflow.max_lines
+        );
+    }
+
+    // Serialize the updated map data back to JSON, keeping the original formatting
+    let updated_content = to_string_preserving(&content, &map_data)
+        .map_err(|e| AppError::Parsing(format!("Failed to serialize {}: {}", file_path, e)))?;
+
+    // Write the updated content back to the file
+    std::fs::write(&full_path, updated_content)
+        .map_err(|e| AppError::FileSystem(format!("Failed to write {}: {}", file_path, e)))?;
+
+    Ok(())
+}
+
+/// Finds the translated messages of MapXXX.json that overflow their message window
+///
+/// The translations are injected and re-flowed in memory only, the file is not written.
+pub fn find_message_overflows(
+    project_path: &Path,
+    file_path: &str,
+    text_units: &[&TextUnit],
+    word_wrap: &WordWrapSettings,
+) -> AppResult<Vec<(String, BudgetViolation)>> {
+    let (_, mut map_data) = read_map_data(project_path, file_path)?;
+    apply_translations(&mut map_data, file_path, text_units, Some(word_wrap))
+}
+
+/// Reads and parses a map file, returning its content along with the parsed data
+fn read_map_data(project_path: &Path, file_path: &str) -> AppResult<(String, MapData)> {
+    let full_path = project_path.join(file_path);
 
     // Read the current JSON file
     let content = std::fs::read_to_string(&full_path)
         .map_err(|e| AppError::FileSystem(format!("Failed to read {}: {}", file_path, e)))?;
 
     // Parse the JSON content
-    let mut map_data: MapData = serde_json::from_str(&content)
+    let map_data: MapData = serde_json::from_str(&content)
         .map_err(|e| AppError::Parsing(format!("Failed to parse {}: {}", file_path, e)))?;
 
+    Ok((content, map_data))
+}
+
+/// Updates the map data with the translated texts
+///
+/// Returns the messages that overflow their window after word wrap.
+fn apply_translations(
+    map_data: &mut MapData,
+    file_path: &str,
+    text_units: &[&TextUnit],
+    word_wrap: Option<&WordWrapSettings>,
+) -> AppResult<Vec<(String, BudgetViolation)>> {
     // Create a map of text units for quick lookup
     let text_unit_map: HashMap<String, &TextUnit> = text_units
         .iter()
@@ -233,6 +277,7 @@ pub fn inject_translations(
         .collect();
 
     let map_id = extract_map_id_from_filename(file_path)?;
+    let mut overflows = Vec::new();
 
     // Update display name
     let display_name_key = format!("map_{}_display_name", map_id);
@@ -260,26 +305,57 @@ pub fn inject_translations(
             }
 
             // Update text in each event page
+            let object_type = format!("map_{}_event_{}", map_id, event.id);
             for page in event.pages.iter_mut() {
-                inject_text_units_into_event_commands(
-                    &format!("map_{}_event_{}", map_id, event.id),
+                let injected_messages = inject_text_units_into_event_commands(
+                    &object_type,
                     event.id,
                     &mut page.list,
                     &text_unit_map,
                 );
+
+                if let Some(settings) = word_wrap {
+                    let wrapped = wrap_message_commands(
+                        &object_type,
+                        event.id,
+                        &mut page.list,
+                        &injected_messages,
+                        settings,
+                    );
+                    overflows.extend(wrapped.into_iter().map(|(unit_id, mut violation)| {
+                        if let Some(location) = event_command_location(map_id, event.id, &unit_id)
+                        {
+                            violation.location = location;
+                        }
+                        (unit_id, violation)
+                    }));
+                }
             }
         }
     }
 
-    // Serialize the updated map data back to JSON
-    let updated_content = serde_json::to_string_pretty(&map_data)
-        .map_err(|e| AppError::Parsing(format!("Failed to serialize {}: {}", file_path, e)))?;
-
-    // Write the updated content back to the file
-    std::fs::write(&full_path, updated_content)
-        .map_err(|e| AppError::FileSystem(format!("Failed to write {}: {}", file_path, e)))?;
+    Ok(overflows)
+}
 
-    Ok(())
+/// Structured location of a message or choice of an event page, from its text unit ID
+///
+/// Event command IDs repeat the event ID after the object type
+/// (e.g., "map_9_event_1_1_message_12" -> "map:9:event:1:message:12",
+/// "map_9_event_1_1_choice_4_0" -> "map:9:event:1:choice:4:0").
+fn event_command_location(map_id: i32, event_id: i32, unit_id: &str) -> Option<String> {
+    let prefix = format!("map_{}_event_{}_{}_", map_id, event_id, event_id);
+    let parts: Vec<&str> = unit_id.strip_prefix(&prefix)?.split('_').collect();
+    match parts.as_slice() {
+        ["message", index] => Some(format!(
+            "map:{}:event:{}:message:{}",
+            map_id, event_id, index
+        )),
+        ["choice", index, choice_index] => Some(format!(
+            "map:{}:event:{}:choice:{}:{}",
+            map_id, event_id, index, choice_index
+        )),
+        _ => None,
+    }
 }
 
 /// Extracts map ID from filename (e.g., "Map001.json" -> 1)
@@ -328,6 +404,20 @@ mod tests {
         assert!(extract_map_id_from_filename("Map.json").is_err());
     }
 
+    #[test]
+    fn test_event_command_location() {
+        assert_eq!(
+            event_command_location(9, 1, "map_9_event_1_1_message_12").as_deref(),
+            Some("map:9:event:1:message:12")
+        );
+        assert_eq!(
+            event_command_location(9, 1, "map_9_event_1_1_choice_4_0").as_deref(),
+            Some("map:9:event:1:choice:4:0")
+        );
+        assert_eq!(event_command_location(9, 1, "map_9_event_1_name"), None);
+        assert_eq!(event_command_location(9, 12, "map_9_event_1_1_message_12"), None);
+    }
+
     #[test]
     fn test_extract_mv_map_data() {
         let project_path = get_test_games_path().join("MVgame");
@@ -419,6 +509,7 @@ mod tests {
         let text_unit_name = TextUnit {
             id: "map_1_event_2_name".to_string(),
             source_text: "EV002".to_string(),
+            source_quotes: None,
             translated_text: "Event002".to_string(),
             field_type: "name:www/data/Map001.json:2:2".to_string(),
             status: TranslationStatus::Translated,
@@ -430,7 +521,7 @@ mod tests {
 
         let text_units = vec![&text_unit_name];
 
-        let result = inject_translations(project_path, "www/data/Map001.json", &text_units);
+        let result = inject_translations(project_path, "www/data/Map001.json", &text_units, None);
         assert!(result.is_ok(), "Injection failed: {:?}", result.err());
 
         // Verify injection by reading the JSON file directly
@@ -209,6 +209,7 @@ mod tests {
         let text_unit_name = TextUnit {
             id: "map_info_1_name".to_string(),
             source_text: "山小屋".to_string(),
+            source_quotes: None,
             translated_text: "Mountain Cabin".to_string(),
             field_type: "name:www/data/MapInfos.json:1".to_string(),
             status: TranslationStatus::Translated,
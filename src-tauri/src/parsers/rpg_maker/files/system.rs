@@ -3,8 +3,10 @@
 
 use crate::core::error::{AppError, AppResult};
 use crate::parsers::engine::{PromptType, TextUnit};
+use crate::parsers::json_format::to_string_preserving;
 use crate::parsers::text::formatter::EngineFormatter;
 use crate::parsers::text::formatter::RpgMakerFormatter;
+use crate::parsers::text::formatter::source_quote_marks;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -114,6 +116,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: "system_gameTitle".to_string(),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(&system_data.gameTitle),
             translated_text: String::new(),
             field_type: format!("gameTitle:{}:0", file_path),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -130,6 +133,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: "system_currencyUnit".to_string(),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(&system_data.currencyUnit),
             translated_text: String::new(),
             field_type: format!("currencyUnit:{}:0", file_path),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -149,6 +153,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: format!("system_armorType_{}", index),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(armor_type),
             translated_text: String::new(),
             field_type: format!("armorTypes:{}:{}", file_path, index),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -168,6 +173,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: format!("system_element_{}", index),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(element),
             translated_text: String::new(),
             field_type: format!("elements:{}:{}", file_path, index),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -187,6 +193,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: format!("system_equipType_{}", index),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(equip_type),
             translated_text: String::new(),
             field_type: format!("equipTypes:{}:{}", file_path, index),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -206,6 +213,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: format!("system_skillType_{}", index),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(skill_type),
             translated_text: String::new(),
             field_type: format!("skillTypes:{}:{}", file_path, index),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -225,6 +233,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: format!("system_weaponType_{}", index),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(weapon_type),
             translated_text: String::new(),
             field_type: format!("weaponTypes:{}:{}", file_path, index),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -244,6 +253,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: format!("system_terms_basic_{}", index),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(basic_term),
             translated_text: String::new(),
             field_type: format!("terms.basic:{}:{}", file_path, index),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -264,6 +274,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
             text_units.push(TextUnit {
                 id: format!("system_terms_command_{}", index),
                 source_text: prepared_text,
+                source_quotes: source_quote_marks(command),
                 translated_text: String::new(),
                 field_type: format!("terms.commands:{}:{}", file_path, index),
                 status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -284,6 +295,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: format!("system_terms_param_{}", index),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(param),
             translated_text: String::new(),
             field_type: format!("terms.params:{}:{}", file_path, index),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -303,6 +315,7 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
         text_units.push(TextUnit {
             id: format!("system_terms_message_{}", key),
             source_text: prepared_text,
+            source_quotes: source_quote_marks(message),
             translated_text: String::new(),
             field_type: format!("terms.messages.{}:{}:0", key, file_path),
             status: crate::parsers::engine::TranslationStatus::NotTranslated,
@@ -455,8 +468,8 @@ pub fn inject_translations(
         }
     }
 
-    // Serialize the updated system data back to JSON
-    let updated_content = serde_json::to_string_pretty(&system_data)
+    // Serialize the updated system data back to JSON, keeping the original formatting
+    let updated_content = to_string_preserving(&content, &system_data)
         .map_err(|e| AppError::Parsing(format!("Failed to serialize {}: {}", file_path, e)))?;
 
     // Write the updated content back to the file
@@ -561,6 +574,7 @@ mod tests {
         let text_unit_title = TextUnit {
             id: "system_gameTitle".to_string(),
             source_text: "Hunterになりたい！！　ver1.1".to_string(),
+            source_quotes: None,
             translated_text: "I Want to Be a Hunter!! v1.1".to_string(),
             field_type: "gameTitle:www/data/System.json:0".to_string(),
             status: TranslationStatus::Translated,
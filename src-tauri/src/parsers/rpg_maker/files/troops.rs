@@ -92,6 +92,10 @@ pub fn extract_text(project_path: &Path, file_path: &str) -> AppResult<GameDataF
 
 /// Injects translated text back into Troops.json
 ///
+/// Only troop names are handled: battle event pages are not parsed, so their messages
+/// are never extracted and there is nothing for word wrap to re-flow (unlike maps and
+/// common events).
+///
 /// # Arguments
 /// * `project_path` - Path to the project directory
 /// * `file_path` - Relative path to the Troops.json file
@@ -243,6 +247,7 @@ mod tests {
             .map(|t| TextUnit {
                 id: t.id.clone(),
                 source_text: String::new(),
+                source_quotes: None,
                 translated_text: t.translated_text.clone(),
                 field_type: String::new(),
                 status: TranslationStatus::Translated,
@@ -2,8 +2,13 @@
 // Implements GameEngineHandler for RPG Maker MV and MZ
 
 use crate::parsers::engine::{GameEngine, TextEntry, TranslationEntry};
-use crate::parsers::handler::{GameEngineHandler, ValidationResult};
+use crate::parsers::handler::{
+    list_json_files, FileInjectedCallback, GameEngineHandler, InjectionOptions,
+    ValidationResult,
+};
 use crate::parsers::rpg_maker::engine::RpgMakerEngine;
+use crate::parsers::text::length_budget::{default_budgets, BudgetViolation, LengthBudget};
+use crate::parsers::text::word_wrap::WordWrapSettings;
 use std::path::{Path, PathBuf};
 
 /// Handler for RPG Maker MV and MZ game engines
@@ -57,41 +62,50 @@ impl GameEngineHandler for RpgMakerHandler {
         &self,
         game_path: &Path,
         translations: &[TranslationEntry],
+        options: &InjectionOptions,
     ) -> Result<(), String> {
-        RpgMakerEngine::inject_all(game_path, translations, self.version)
+        RpgMakerEngine::inject_all(game_path, translations, self.version, options)
     }
 
-    fn count_files_to_process(&self, game_path: &Path) -> usize {
-        let data_root = RpgMakerEngine::get_data_root(game_path, self.version);
-        
-        if !data_root.exists() {
-            return 0;
-        }
+    fn inject_all_texts_with_progress(
+        &self,
+        game_path: &Path,
+        translations: &[TranslationEntry],
+        options: &InjectionOptions,
+        on_file: &mut FileInjectedCallback<'_>,
+    ) -> Result<(), String> {
+        RpgMakerEngine::inject_all_with_progress(
+            game_path,
+            translations,
+            self.version,
+            options,
+            on_file,
+        )
+    }
 
-        let mut count = 0;
+    fn list_files_to_process(&self, game_path: &Path) -> Vec<PathBuf> {
+        let data_root = RpgMakerEngine::get_data_root(game_path, self.version);
 
-        // Count JSON files in data directory (includes MapXXX.json files)
         // RPG Maker stores all JSON files including maps directly in data/
-        if let Ok(entries) = std::fs::read_dir(&data_root) {
-            for entry in entries.flatten() {
-                if let Ok(file_type) = entry.file_type() {
-                    if file_type.is_file() {
-                        if let Some(ext) = entry.path().extension() {
-                            if ext == "json" {
-                                count += 1;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        count
+        list_json_files(&data_root)
     }
 
     fn get_data_root(&self, game_path: &Path) -> PathBuf {
         RpgMakerEngine::get_data_root(game_path, self.version)
     }
+
+    fn default_length_budgets(&self) -> Vec<LengthBudget> {
+        default_budgets(self.version)
+    }
+
+    fn find_message_overflows(
+        &self,
+        game_path: &Path,
+        translations: &[TranslationEntry],
+        word_wrap: &WordWrapSettings,
+    ) -> Result<Vec<(String, BudgetViolation)>, String> {
+        RpgMakerEngine::find_message_overflows(game_path, translations, self.version, word_wrap)
+    }
 }
 
 #[cfg(test)]
@@ -303,7 +317,11 @@ mod tests {
         }
 
         // Inject translations
-        let inject_result = handler.inject_all_texts(&temp_game_path, &translations);
+        let inject_result = handler.inject_all_texts(
+            &temp_game_path,
+            &translations,
+            &InjectionOptions::default(),
+        );
         assert!(inject_result.is_ok(), "Injection should succeed: {:?}", inject_result.err());
 
         // Verify injection by re-extracting
@@ -360,6 +378,26 @@ mod tests {
         assert_eq!(count, 0);
     }
 
+    #[test]
+    fn test_list_files_to_process_only_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_path = temp_dir.path();
+        let data_dir = game_path.join("data");
+        fs::create_dir_all(data_dir.join("sub")).unwrap();
+        fs::write(data_dir.join("System.json"), "{}").unwrap();
+        fs::write(data_dir.join("Actors.json"), "[]").unwrap();
+        fs::write(data_dir.join("notes.txt"), "").unwrap();
+        fs::write(data_dir.join("sub").join("Nested.json"), "{}").unwrap();
+        let handler = RpgMakerHandler::new_mz();
+
+        let files = handler.list_files_to_process(game_path);
+        assert_eq!(
+            files,
+            vec![data_dir.join("Actors.json"), data_dir.join("System.json")]
+        );
+        assert_eq!(handler.count_files_to_process(game_path), 2);
+    }
+
     #[test]
     fn test_extract_all_texts_invalid_structure() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,347 @@
+use serde::{Deserialize, Serialize};
+
+/// Policy applied to normalized quotation marks when restoring translated text
+///
+/// `UniversalFormatter::prepare_for_translation` turns 「」 into `"` and 『』 into `'`
+/// so models see familiar punctuation. On restore, the policy decides what those
+/// quotes become in the target language:
+/// - `Keep`: leave the ASCII quotes as translated
+/// - `TargetStyle`: convert to the typographic quotes of the target language (« », „ “, ...)
+/// - `RestoreOriginal`: give back 「」 and 『』 to the pairs that had them in the source
+///   (see `source_quote_marks`); quotes the source already had in ASCII stay ASCII
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotePolicy {
+    Keep,
+    TargetStyle,
+    RestoreOriginal,
+}
+
+/// Opening and closing marks for one quote level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteMarks {
+    pub open: &'static str,
+    pub close: &'static str,
+}
+
+/// Primary (double) and secondary (single) quote marks of a language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteStyle {
+    pub double: QuoteMarks,
+    pub single: QuoteMarks,
+}
+
+const CORNER_BRACKETS: QuoteStyle = QuoteStyle {
+    double: QuoteMarks {
+        open: "「",
+        close: "」",
+    },
+    single: QuoteMarks {
+        open: "『",
+        close: "』",
+    },
+};
+
+const ENGLISH_QUOTES: QuoteStyle = QuoteStyle {
+    double: QuoteMarks {
+        open: "“",
+        close: "”",
+    },
+    single: QuoteMarks {
+        open: "‘",
+        close: "’",
+    },
+};
+
+// French guillemets use a no-break space inside the marks
+const FRENCH_QUOTES: QuoteStyle = QuoteStyle {
+    double: QuoteMarks {
+        open: "«\u{a0}",
+        close: "\u{a0}»",
+    },
+    single: QuoteMarks {
+        open: "“",
+        close: "”",
+    },
+};
+
+const GUILLEMET_QUOTES: QuoteStyle = QuoteStyle {
+    double: QuoteMarks {
+        open: "«",
+        close: "»",
+    },
+    single: QuoteMarks {
+        open: "“",
+        close: "”",
+    },
+};
+
+const GERMAN_QUOTES: QuoteStyle = QuoteStyle {
+    double: QuoteMarks {
+        open: "„",
+        close: "“",
+    },
+    single: QuoteMarks {
+        open: "‚",
+        close: "‘",
+    },
+};
+
+const RUSSIAN_QUOTES: QuoteStyle = QuoteStyle {
+    double: QuoteMarks {
+        open: "«",
+        close: "»",
+    },
+    single: QuoteMarks {
+        open: "„",
+        close: "“",
+    },
+};
+
+/// Normalize a language code or name ("fr", "fr-FR", "French") to its ISO 639-1 base
+pub fn normalize_language_code(language: &str) -> String {
+    let lower = language.trim().to_lowercase();
+    let base = lower.split(['-', '_']).next().unwrap_or("").to_string();
+    match base.as_str() {
+        "english" => "en".to_string(),
+        "french" | "français" | "francais" => "fr".to_string(),
+        "german" | "deutsch" => "de".to_string(),
+        "spanish" | "español" | "espanol" => "es".to_string(),
+        "italian" | "italiano" => "it".to_string(),
+        "portuguese" | "português" | "portugues" => "pt".to_string(),
+        "russian" => "ru".to_string(),
+        "chinese" => "zh".to_string(),
+        "japanese" => "ja".to_string(),
+        "korean" => "ko".to_string(),
+        _ => base,
+    }
+}
+
+impl QuotePolicy {
+    /// Default policy for a target language
+    ///
+    /// English keeps the ASCII quotes (safe for every game font), European languages
+    /// get their own typography, and Chinese/Japanese go back to corner brackets.
+    pub fn for_language(target_language: &str) -> Self {
+        match normalize_language_code(target_language).as_str() {
+            "fr" | "de" | "es" | "it" | "pt" | "ru" => QuotePolicy::TargetStyle,
+            "zh" | "ja" => QuotePolicy::RestoreOriginal,
+            _ => QuotePolicy::Keep,
+        }
+    }
+}
+
+impl QuoteStyle {
+    /// Typographic quote style of a target language
+    pub fn for_language(target_language: &str) -> Self {
+        match normalize_language_code(target_language).as_str() {
+            "fr" => FRENCH_QUOTES,
+            "es" | "it" | "pt" => GUILLEMET_QUOTES,
+            "de" => GERMAN_QUOTES,
+            "ru" => RUSSIAN_QUOTES,
+            "zh" | "ja" => CORNER_BRACKETS,
+            _ => ENGLISH_QUOTES,
+        }
+    }
+}
+
+/// Role of a quote character found in translated text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuoteRole {
+    Open,
+    Close,
+}
+
+/// Opening marks of the quote pairs of a source text, in order of appearance
+///
+/// Recorded at extraction, before `prepare_for_translation` turns 「」 and 『』 into ASCII
+/// quotes, so `RestoreOriginal` can tell them apart from the ASCII quotes the source
+/// already had. Returns `None` when the source has no corner brackets to restore.
+pub fn source_quote_marks(source_text: &str) -> Option<String> {
+    if !source_text.contains(['「', '『']) {
+        return None;
+    }
+
+    // Pair the corner brackets like the ASCII quotes they become
+    let chars: Vec<char> = source_text.chars().collect();
+    let normalized: Vec<char> = chars
+        .iter()
+        .map(|c| match c {
+            '「' => '“',
+            '」' => '”',
+            '『' | '』' => '\'',
+            c => *c,
+        })
+        .collect();
+
+    let mut opens: Vec<usize> = double_quote_pairs(&normalized)
+        .into_iter()
+        .chain(single_quote_pairs(&normalized))
+        .map(|(open, _)| open)
+        .collect();
+    opens.sort_unstable();
+    Some(opens.into_iter().map(|i| chars[i]).collect())
+}
+
+/// Apply a quote policy to translated text
+///
+/// Double quotes (`"`, `“`, `”`) are paired in order of appearance. Single quotes are
+/// only treated as quotes when they are not inside a word and have a matching partner,
+/// so apostrophes ("don't", "l'épée") are left untouched. `source_quotes` are the marks
+/// recorded at extraction, only used by `RestoreOriginal`.
+pub fn apply_quote_policy(
+    text: &str,
+    policy: QuotePolicy,
+    target_language: &str,
+    source_quotes: Option<&str>,
+) -> String {
+    if policy == QuotePolicy::Keep || !text.contains(['"', '\'', '“', '”']) {
+        return text.to_string();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut replacements: Vec<Option<&'static str>> = vec![None; chars.len()];
+    let double_pairs = double_quote_pairs(&chars);
+    let single_pairs = single_quote_pairs(&chars);
+
+    match policy {
+        QuotePolicy::Keep => {}
+        QuotePolicy::TargetStyle => {
+            let style = QuoteStyle::for_language(target_language);
+            for pair in double_pairs {
+                mark_pair(pair, &style.double, &mut replacements);
+            }
+            for pair in single_pairs {
+                mark_pair(pair, &style.single, &mut replacements);
+            }
+        }
+        QuotePolicy::RestoreOriginal => {
+            let source_quotes = source_quotes.unwrap_or_default();
+            let (source_single, source_double): (Vec<char>, Vec<char>) = source_quotes
+                .chars()
+                .partition(|c| matches!(c, '『' | '\''));
+            restore_original_pairs(
+                &double_pairs,
+                &source_double,
+                '「',
+                &CORNER_BRACKETS.double,
+                &mut replacements,
+            );
+            restore_original_pairs(
+                &single_pairs,
+                &source_single,
+                '『',
+                &CORNER_BRACKETS.single,
+                &mut replacements,
+            );
+        }
+    }
+
+    chars
+        .iter()
+        .zip(replacements)
+        .map(|(c, replacement)| match replacement {
+            Some(mark) => mark.to_string(),
+            None => c.to_string(),
+        })
+        .collect()
+}
+
+/// Give corner brackets back to the translated pairs whose source pair had them
+///
+/// Pairs are matched by order, so a quote level is only restored when the translation
+/// has as many pairs as the source: a model that merged or split quotes would otherwise
+/// get brackets on the wrong pair, and the ASCII quotes are left as translated.
+fn restore_original_pairs(
+    pairs: &[(usize, usize)],
+    source_opens: &[char],
+    corner: char,
+    marks: &QuoteMarks,
+    replacements: &mut [Option<&'static str>],
+) {
+    if pairs.len() != source_opens.len() {
+        return;
+    }
+    for (pair, source_open) in pairs.iter().zip(source_opens) {
+        if *source_open == corner {
+            mark_pair(*pair, marks, replacements);
+        }
+    }
+}
+
+fn mark_pair(
+    (open, close): (usize, usize),
+    marks: &QuoteMarks,
+    replacements: &mut [Option<&'static str>],
+) {
+    replacements[open] = Some(marks.open);
+    replacements[close] = Some(marks.close);
+}
+
+/// Opening and closing positions of the double quote pairs, in order
+fn double_quote_pairs(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    let mut open_index: Option<usize> = None;
+    for (i, c) in chars.iter().enumerate() {
+        let role = match c {
+            '“' => QuoteRole::Open,
+            '”' => QuoteRole::Close,
+            '"' if open_index.is_some() => QuoteRole::Close,
+            '"' => QuoteRole::Open,
+            _ => continue,
+        };
+        match (role, open_index) {
+            (QuoteRole::Open, _) => open_index = Some(i),
+            (QuoteRole::Close, Some(start)) => {
+                pairs.push((start, i));
+                open_index = None;
+            }
+            (QuoteRole::Close, None) => {}
+        }
+    }
+    pairs
+}
+
+/// Whether a character belongs to a CJK script, where words are not space-separated
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30ff}'
+            | '\u{3400}'..='\u{9fff}'
+            | '\u{f900}'..='\u{faff}'
+            | '\u{ac00}'..='\u{d7af}'
+            | '\u{ff66}'..='\u{ff9f}')
+}
+
+/// Opening and closing positions of the single quote pairs, in order
+fn single_quote_pairs(chars: &[char]) -> Vec<(usize, usize)> {
+    let is_word_char =
+        |c: Option<&char>| matches!(c, Some(c) if c.is_alphanumeric() && !is_cjk(*c));
+
+    let mut pairs = Vec::new();
+    let mut open_index: Option<usize> = None;
+    for (i, c) in chars.iter().enumerate() {
+        if *c != '\'' {
+            continue;
+        }
+        let before = if i > 0 { chars.get(i - 1) } else { None };
+        let after = chars.get(i + 1);
+        let role = match (is_word_char(before), is_word_char(after)) {
+            // Apostrophe inside a word ("don't", "l'épée")
+            (true, true) => continue,
+            (false, true) => QuoteRole::Open,
+            (true, false) => QuoteRole::Close,
+            // No word context (CJK text, punctuation): alternate open/close
+            (false, false) if open_index.is_some() => QuoteRole::Close,
+            (false, false) => QuoteRole::Open,
+        };
+        match (role, open_index) {
+            (QuoteRole::Open, _) => open_index = Some(i),
+            (QuoteRole::Close, Some(start)) => {
+                pairs.push((start, i));
+                open_index = None;
+            }
+            (QuoteRole::Close, None) => {}
+        }
+    }
+    pairs
+}
@@ -1,4 +1,5 @@
 use super::formatter_trait::EngineFormatter;
+use super::quote_policy::{apply_quote_policy, QuotePolicy};
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
 
@@ -41,7 +42,8 @@ static TAB_RESTORE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[TAB_(\d+)\]"
 /// - Numeric prefixes: 100＿text, ２００_text
 /// - Whitespace encoding: spaces, tabs, full-width spaces
 /// - Control codes: \., \|, \^, \!
-/// - Japanese quotation marks normalization (unidirectional): 「 and 」 → ", 『 and 』 → '
+/// - Japanese quotation marks normalization: 「 and 」 → ", 『 and 』 → '
+///   (reversed per target language with `restore_quotes`)
 ///
 /// This formatter is used by ALL engine-specific formatters for universal patterns.
 pub struct UniversalFormatter;
@@ -82,13 +84,12 @@ impl EngineFormatter for UniversalFormatter {
         result = result.replace('\r', "[CTRL_CARRIAGE_RETURN]");
         result = result.replace('\t', "[CTRL_TAB]");
 
-        // Japanese quotation marks normalization (unidirectional transformation)
+        // Japanese quotation marks normalization
         // Transform Japanese quotes 「 and 」 to standard double quotes "
-        // Note: This is a one-way normalization - no restoration needed
+        // Restoration depends on the target language (see `restore_quotes`)
         result = result.replace('「', "\"");
         result = result.replace('」', "\"");
         // Transform Japanese double quotes 『 and 』 to standard single quotes '
-        // Note: This is a one-way normalization - no restoration needed
         result = result.replace('『', "'");
         result = result.replace('』', "'");
 
@@ -166,6 +167,22 @@ impl EngineFormatter for UniversalFormatter {
 }
 
 impl UniversalFormatter {
+    /// Restore normalized quotation marks according to a target language policy
+    ///
+    /// Quotes are not placeholders, so this runs on the translated text independently
+    /// of `restore_after_translation`. When no policy is given, the default policy of
+    /// the target language is used (see `QuotePolicy::for_language`). `source_quotes`
+    /// are the quote marks recorded with the entry at extraction (`TextUnit::source_quotes`).
+    pub fn restore_quotes(
+        text: &str,
+        target_language: &str,
+        policy: Option<QuotePolicy>,
+        source_quotes: Option<&str>,
+    ) -> String {
+        let policy = policy.unwrap_or_else(|| QuotePolicy::for_language(target_language));
+        apply_quote_policy(text, policy, target_language, source_quotes)
+    }
+
     /// Encode significant whitespace using pre-compiled regexes
     fn encode_whitespace_placeholders(input: &str) -> String {
         let mut result = input.to_string();
@@ -248,6 +265,7 @@ impl UniversalFormatter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parsers::text::formatter::source_quote_marks;
 
     #[test]
     fn test_universal_formatting() {
@@ -356,4 +374,103 @@ mod tests {
         let prepared3 = UniversalFormatter::prepare_for_translation(input3);
         assert_eq!(prepared3, expected3, "Closing double quote should be normalized");
     }
+
+    #[test]
+    fn test_restore_quotes_french_target_style() {
+        let prepared = UniversalFormatter::prepare_for_translation("勇者「こんにちは」");
+        let translated = "Le héros \"Bonjour\"";
+        assert_eq!(prepared, "勇者\"こんにちは\"");
+
+        let restored = UniversalFormatter::restore_quotes(translated, "fr", None, None);
+        assert_eq!(restored, "Le héros «\u{a0}Bonjour\u{a0}»");
+
+        // Apostrophes must not be mistaken for quotes
+        let restored =
+            UniversalFormatter::restore_quotes("Prends l'épée 'Excalibur'", "fr", None, None);
+        assert_eq!(restored, "Prends l'épée “Excalibur”");
+    }
+
+    #[test]
+    fn test_restore_quotes_english_keeps_ascii() {
+        let translated = "The hero said \"Hello\" and 'don't' move";
+        let restored = UniversalFormatter::restore_quotes(translated, "en", None, None);
+        assert_eq!(restored, translated, "English default policy keeps ASCII quotes");
+
+        let restored = UniversalFormatter::restore_quotes(
+            translated,
+            "en",
+            Some(QuotePolicy::TargetStyle),
+            None,
+        );
+        assert_eq!(restored, "The hero said “Hello” and ‘don't’ move");
+    }
+
+    #[test]
+    fn test_restore_quotes_german_target_style() {
+        let restored =
+            UniversalFormatter::restore_quotes("Er sagte \"Hallo 'Welt'\"", "de", None, None);
+        assert_eq!(restored, "Er sagte „Hallo ‚Welt‘“");
+    }
+
+    #[test]
+    fn test_restore_quotes_chinese_restores_original() {
+        let source = "「『剣』を取れ」";
+        let prepared = UniversalFormatter::prepare_for_translation(source);
+        assert_eq!(prepared, "\"'剣'を取れ\"");
+        let source_quotes = source_quote_marks(source);
+        assert_eq!(source_quotes.as_deref(), Some("「『"));
+
+        let restored = UniversalFormatter::restore_quotes(
+            "\"拿起'剑'\"",
+            "zh",
+            None,
+            source_quotes.as_deref(),
+        );
+        assert_eq!(restored, "「拿起『剑』」");
+
+        // Curly quotes produced by the model are paired as well
+        let source_quotes = source_quote_marks("「剣を取れ」");
+        let restored =
+            UniversalFormatter::restore_quotes("“拿起剑”", "zh-CN", None, source_quotes.as_deref());
+        assert_eq!(restored, "「拿起剑」");
+    }
+
+    #[test]
+    fn test_restore_quotes_keeps_source_ascii_quotes() {
+        // The source mixes corner brackets and ASCII quotes, normalized alike
+        let source = "「こんにちは」と\"OK\"";
+        let prepared = UniversalFormatter::prepare_for_translation(source);
+        assert_eq!(prepared, "\"こんにちは\"と\"OK\"");
+        let source_quotes = source_quote_marks(source);
+        assert_eq!(source_quotes.as_deref(), Some("「\""));
+
+        // Only the pair that had corner brackets gets them back
+        let restored = UniversalFormatter::restore_quotes(
+            "\"你好\"和\"OK\"",
+            "zh",
+            None,
+            source_quotes.as_deref(),
+        );
+        assert_eq!(restored, "「你好」和\"OK\"");
+
+        // A translation that lost a pair cannot be matched: quotes stay as translated
+        let restored = UniversalFormatter::restore_quotes(
+            "你好和\"OK\"",
+            "zh",
+            None,
+            source_quotes.as_deref(),
+        );
+        assert_eq!(restored, "你好和\"OK\"");
+
+        // Without corner brackets in the source there is nothing to restore
+        assert_eq!(source_quote_marks("\"OK\"と言え"), None);
+        let restored = UniversalFormatter::restore_quotes("\"OK\"", "ja", None, None);
+        assert_eq!(restored, "\"OK\"");
+    }
+
+    #[test]
+    fn test_restore_quotes_unbalanced_left_untouched() {
+        let restored = UniversalFormatter::restore_quotes("Il dit \"Bonjour", "fr", None, None);
+        assert_eq!(restored, "Il dit \"Bonjour");
+    }
 }
@@ -0,0 +1,206 @@
+//! Length budgets for database fields
+//!
+//! Menus and windows show item, skill and state names in a fixed width, so a long
+//! translation gets cut off. A budget limits the display width (in cells: full-width
+//! characters count 2) and optionally the number of lines of the texts whose location
+//! matches a pattern such as `item:*:name`.
+
+use crate::parsers::engine::GameEngine;
+use crate::parsers::text::word_wrap::display_cells;
+use serde::{Deserialize, Serialize};
+
+// Line break markers found in texts prepared for translation
+const LINE_BREAK_MARKERS: [&str; 2] = ["[CTRL_NEWLINE]", "[NEWLINE]"];
+
+/// Maximum size of the texts whose location matches `pattern`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LengthBudget {
+    /// Location pattern, segments separated by ':' where `*` matches any one segment
+    pub pattern: String,
+    /// Maximum display cells per line
+    pub max_cells: u32,
+    /// Maximum number of lines (None = not limited)
+    #[serde(default)]
+    pub max_lines: Option<usize>,
+}
+
+impl LengthBudget {
+    pub fn new(pattern: &str, max_cells: u32, max_lines: Option<usize>) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            max_cells,
+            max_lines,
+        }
+    }
+
+    /// Whether the budget applies to a text location
+    pub fn matches(&self, location: &str) -> bool {
+        let mut pattern_segments = self.pattern.split(':');
+        let mut location_segments = location.split(':');
+        loop {
+            match (pattern_segments.next(), location_segments.next()) {
+                (None, None) => return true,
+                (Some(pattern), Some(segment)) if pattern == "*" || pattern == segment => {}
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// A text exceeding its length budget
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetViolation {
+    pub location: String,
+    pub pattern: String,
+    pub max_cells: u32,
+    pub max_lines: Option<usize>,
+    /// Width of the widest line
+    pub actual_cells: u32,
+    pub actual_lines: usize,
+}
+
+/// First budget matching a location (user budgets should come before defaults)
+pub fn find_budget<'a>(location: &str, budgets: &'a [LengthBudget]) -> Option<&'a LengthBudget> {
+    budgets.iter().find(|budget| budget.matches(location))
+}
+
+/// Check a text against the budget of its location
+///
+/// Returns `None` when no budget applies or the text fits.
+pub fn check_length_budget(
+    location: &str,
+    text: &str,
+    budgets: &[LengthBudget],
+) -> Option<BudgetViolation> {
+    let budget = find_budget(location, budgets)?;
+
+    let mut normalized = text.to_string();
+    for marker in LINE_BREAK_MARKERS {
+        normalized = normalized.replace(marker, "\n");
+    }
+    let lines: Vec<&str> = normalized.split('\n').collect();
+    let actual_cells = lines
+        .iter()
+        .map(|line| display_cells(line))
+        .max()
+        .unwrap_or(0);
+    let actual_lines = lines.len();
+
+    let too_wide = actual_cells > budget.max_cells;
+    let too_many_lines = budget.max_lines.is_some_and(|max| actual_lines > max);
+    if !too_wide && !too_many_lines {
+        return None;
+    }
+
+    Some(BudgetViolation {
+        location: location.to_string(),
+        pattern: budget.pattern.clone(),
+        max_cells: budget.max_cells,
+        max_lines: budget.max_lines,
+        actual_cells,
+        actual_lines,
+    })
+}
+
+/// Default budgets of an engine's database fields
+pub fn default_budgets(engine: GameEngine) -> Vec<LengthBudget> {
+    match engine {
+        // Default MV/MZ windows at 816x624 with the standard 28px font
+        GameEngine::RpgMakerMV | GameEngine::RpgMakerMZ => vec![
+            LengthBudget::new("actor:*:name", 12, Some(1)),
+            LengthBudget::new("actor:*:nickname", 20, Some(1)),
+            LengthBudget::new("actor:*:profile", 50, Some(2)),
+            LengthBudget::new("class:*:name", 16, Some(1)),
+            LengthBudget::new("item:*:name", 22, Some(1)),
+            LengthBudget::new("weapon:*:name", 22, Some(1)),
+            LengthBudget::new("armor:*:name", 22, Some(1)),
+            LengthBudget::new("skill:*:name", 22, Some(1)),
+            LengthBudget::new("item:*:description", 50, Some(2)),
+            LengthBudget::new("weapon:*:description", 50, Some(2)),
+            LengthBudget::new("armor:*:description", 50, Some(2)),
+            LengthBudget::new("skill:*:description", 50, Some(2)),
+            LengthBudget::new("state:*:name", 16, Some(1)),
+            LengthBudget::new("enemy:*:name", 20, Some(1)),
+            LengthBudget::new("system:terms:basic:*", 10, Some(1)),
+            LengthBudget::new("system:terms:command:*", 12, Some(1)),
+            LengthBudget::new("system:terms:param:*", 10, Some(1)),
+            LengthBudget::new("system:armor_type:*", 12, Some(1)),
+            LengthBudget::new("system:weapon_type:*", 12, Some(1)),
+            LengthBudget::new("system:skill_type:*", 12, Some(1)),
+            LengthBudget::new("system:equipment_type:*", 12, Some(1)),
+            LengthBudget::new("system:element:*", 12, Some(1)),
+        ],
+        GameEngine::WolfRPG => vec![LengthBudget::new("db:*:type:*:data:*:name", 20, Some(1))],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matches_segments() {
+        let budget = LengthBudget::new("item:*:name", 10, None);
+        assert!(budget.matches("item:12:name"));
+        assert!(!budget.matches("item:12:description"));
+        assert!(!budget.matches("item:12"));
+        assert!(!budget.matches("item:12:name:extra"));
+        assert!(!budget.matches("weapon:12:name"));
+    }
+
+    #[test]
+    fn test_first_matching_budget_wins() {
+        let budgets = vec![
+            LengthBudget::new("item:7:name", 30, None),
+            LengthBudget::new("item:*:name", 10, None),
+        ];
+        assert_eq!(find_budget("item:7:name", &budgets).unwrap().max_cells, 30);
+        assert_eq!(find_budget("item:8:name", &budgets).unwrap().max_cells, 10);
+        assert!(find_budget("skill:8:name", &budgets).is_none());
+    }
+
+    #[test]
+    fn test_check_name_width() {
+        let budgets = vec![LengthBudget::new("item:*:name", 12, Some(1))];
+        assert!(check_length_budget("item:1:name", "Potion", &budgets).is_none());
+
+        let violation =
+            check_length_budget("item:1:name", "Potion de soin supérieure", &budgets).unwrap();
+        assert_eq!(violation.actual_cells, 25);
+        assert_eq!(violation.max_cells, 12);
+        assert_eq!(violation.pattern, "item:*:name");
+    }
+
+    #[test]
+    fn test_check_counts_wide_characters_and_ignores_codes() {
+        let budgets = vec![LengthBudget::new("state:*:name", 6, Some(1))];
+        // 3 CJK characters = 6 cells
+        assert!(check_length_budget("state:1:name", "戦闘不", &budgets).is_none());
+        assert!(check_length_budget("state:1:name", "戦闘不能", &budgets).is_some());
+        // Placeholders have no width
+        assert!(check_length_budget("state:1:name", "[COLOR_2]Poison", &budgets).is_none());
+    }
+
+    #[test]
+    fn test_check_description_lines() {
+        let budgets = vec![LengthBudget::new("item:*:description", 20, Some(2))];
+        let two_lines = "Restores 50 HP[CTRL_NEWLINE]to one ally.";
+        assert!(check_length_budget("item:1:description", two_lines, &budgets).is_none());
+
+        let three_lines = "Restores 50 HP\nto one ally\nin battle.";
+        let violation = check_length_budget("item:1:description", three_lines, &budgets).unwrap();
+        assert_eq!(violation.actual_lines, 3);
+        assert_eq!(violation.actual_cells, 14);
+    }
+
+    #[test]
+    fn test_default_budgets_cover_database_names() {
+        let rpg = default_budgets(GameEngine::RpgMakerMZ);
+        assert!(find_budget("item:3:name", &rpg).is_some());
+        assert!(find_budget("skill:3:description", &rpg).is_some());
+        assert!(find_budget("map:1:event:2:message:3", &rpg).is_none());
+
+        let wolf = default_budgets(GameEngine::WolfRPG);
+        assert!(find_budget("db:DataBase.project:type:1:data:4:name", &wolf).is_some());
+    }
+}
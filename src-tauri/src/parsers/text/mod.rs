@@ -3,8 +3,12 @@
 /// This module contains text validation, formatting, and processing utilities
 /// for different game engines and universal text operations.
 pub mod formatter;
+pub mod length_budget;
 pub mod validation;
+pub mod word_wrap;
 
 // Re-export for convenience
 pub use formatter::{EngineFormatter, RpgMakerFormatter, UniversalFormatter, WolfRpgFormatter};
+pub use length_budget::{BudgetViolation, LengthBudget};
 pub use validation::ContentValidator;
+pub use word_wrap::WordWrapSettings;
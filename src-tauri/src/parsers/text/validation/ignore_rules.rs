@@ -0,0 +1,177 @@
+//! Detection of texts that don't need translation
+//!
+//! Game data is full of Latin-script strings that must stay as they are whatever the
+//! target language: stat abbreviations ("HP", "ATK"), internal identifiers
+//! ("switch_12", "EV003") and file names. Projects can add their own rules as regexes
+//! or lists of known technical tokens.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// Stat and UI abbreviations found in RPG Maker and Wolf RPG databases
+const BUILTIN_TOKENS: &[&str] = &[
+    "HP", "MP", "TP", "SP", "BP", "AP", "EXP", "XP", "LV", "LVL", "ATK", "DEF", "MAT", "MDF",
+    "AGI", "LUK", "HIT", "EVA", "CRI", "STR", "INT", "DEX", "VIT", "MND", "SPD", "OK", "NG", "MAX",
+    "MIN", "ON", "OFF", "NEW", "BGM", "BGS", "SE", "ME",
+];
+
+// Short all-caps abbreviation followed by a number: "MP2", "LV 10"
+// (bare all-caps words like "YES" or "SAVE" are real words and get translated)
+static ABBREVIATION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Z]{1,4}\s?\d+$").unwrap());
+// Identifiers joined by separators: "switch_12", "Actor1.png", "img/faces/Actor1"
+// (hyphens are left out: "Hi-Potion" is a name to translate)
+static SEPARATED_IDENTIFIER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z0-9]+(?:[_./\\][A-Za-z0-9]+)+$").unwrap());
+// camelCase identifiers and codes mixing letters and digits: "isBoss", "EV003", "var2x"
+static CODE_IDENTIFIER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:[a-z]+[A-Z][A-Za-z0-9]*|[A-Za-z]+\d+[A-Za-z0-9]*|\d+[A-Za-z]+)$").unwrap()
+});
+
+/// User-defined ignore rules of a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IgnoreRules {
+    /// Regular expressions matched against the trimmed text
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// Exact technical tokens (case-sensitive)
+    #[serde(default)]
+    pub tokens: Vec<String>,
+}
+
+impl IgnoreRules {
+    /// Compile the rules, failing on the first invalid regex
+    pub fn compile(&self) -> Result<IgnoreMatcher, String> {
+        let patterns = self
+            .patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| format!("Invalid ignore pattern '{}': {}", pattern, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(IgnoreMatcher {
+            patterns,
+            tokens: self.tokens.iter().map(|t| t.trim().to_string()).collect(),
+        })
+    }
+}
+
+/// Compiled user ignore rules
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreMatcher {
+    patterns: Vec<Regex>,
+    tokens: HashSet<String>,
+}
+
+impl IgnoreMatcher {
+    /// Whether a text matches one of the user rules
+    pub fn is_match(&self, text: &str) -> bool {
+        let text = text.trim();
+        self.tokens.contains(text) || self.patterns.iter().any(|p| p.is_match(text))
+    }
+}
+
+/// Whether a letter belongs to the Latin script (ASCII, accented or full-width)
+fn is_latin_letter(c: char) -> bool {
+    c.is_ascii_alphabetic()
+        || matches!(c, '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}')
+        || matches!(c, '\u{FF21}'..='\u{FF3A}' | '\u{FF41}'..='\u{FF5A}')
+}
+
+/// Whether a text contains letters and all of them are Latin
+pub fn is_latin_only(text: &str) -> bool {
+    let mut letters = text.chars().filter(|c| c.is_alphabetic()).peekable();
+    letters.peek().is_some() && letters.all(is_latin_letter)
+}
+
+/// Whether a Latin-script text is a technical token that no language translates
+///
+/// Covers stat abbreviations, internal identifiers and file names. Abbreviations only
+/// match in capitals: "Max" or "Me" are words to translate.
+pub fn is_technical_token(text: &str) -> bool {
+    let text = text.trim();
+    if text.is_empty() || !text.is_ascii() {
+        return false;
+    }
+
+    BUILTIN_TOKENS.contains(&text)
+        || ABBREVIATION_REGEX.is_match(text)
+        || SEPARATED_IDENTIFIER_REGEX.is_match(text)
+        || CODE_IDENTIFIER_REGEX.is_match(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_technical_tokens() {
+        for token in [
+            "HP",
+            "MP",
+            "OK",
+            "ATK",
+            "LV 10",
+            "MP2",
+            "switch_12",
+            "Actor1.png",
+            "EV003",
+            "isBoss",
+        ] {
+            assert!(is_technical_token(token), "{} should be technical", token);
+        }
+        for text in [
+            "Potion",
+            "Hello world",
+            "YES",
+            "SAVE",
+            "Hi-Potion",
+            "I'm fine.",
+            "ポーション",
+        ] {
+            assert!(!is_technical_token(text), "{} should be translated", text);
+        }
+    }
+
+    #[test]
+    fn test_builtin_tokens_only_match_in_capitals() {
+        assert!(is_technical_token("MAX"));
+        assert!(is_technical_token("ME"));
+        for word in ["Max", "Me", "max", "me", "Ok", "New", "Off"] {
+            assert!(!is_technical_token(word), "{} should be translated", word);
+        }
+    }
+
+    #[test]
+    fn test_latin_only() {
+        assert!(is_latin_only("Épée légendaire"));
+        assert!(is_latin_only("ＨＰ"));
+        assert!(!is_latin_only("HPが回復"));
+        assert!(!is_latin_only("123 !"));
+    }
+
+    #[test]
+    fn test_user_rules() {
+        let rules = IgnoreRules {
+            patterns: vec![r"^DBG_".to_string()],
+            tokens: vec!["Zeal".to_string()],
+        };
+        let matcher = rules.compile().unwrap();
+        assert!(matcher.is_match("DBG_spawn"));
+        assert!(matcher.is_match(" Zeal "));
+        assert!(!matcher.is_match("zeal"));
+        assert!(!matcher.is_match("Potion"));
+    }
+
+    #[test]
+    fn test_invalid_user_pattern() {
+        let rules = IgnoreRules {
+            patterns: vec!["(unclosed".to_string()],
+            tokens: Vec::new(),
+        };
+        assert!(rules.compile().is_err());
+    }
+}
@@ -2,6 +2,10 @@
 ///
 /// This module provides unified validation logic that works for all engines
 /// without engine-specific knowledge.
+pub mod ignore_rules;
+pub mod translation_check;
 pub mod validation;
 
+pub use ignore_rules::{IgnoreMatcher, IgnoreRules};
+pub use translation_check::{check_translation, RetranslationReason, Script};
 pub use validation::ContentValidator;
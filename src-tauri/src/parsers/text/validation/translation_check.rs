@@ -0,0 +1,165 @@
+//! Post-translation checks: translations that still need work
+//!
+//! These checks run on translated text (before injection) and flag entries the model
+//! left untranslated: residual Japanese, Chinese or Korean script in a target language
+//! that doesn't use it, or a translation identical to its source.
+
+use crate::parsers::text::formatter::quote_policy::normalize_language_code;
+use serde::{Deserialize, Serialize};
+
+/// Writing system detected in a translation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Script {
+    /// Hiragana and katakana (Japanese)
+    Kana,
+    /// CJK ideographs (Japanese kanji, Chinese hanzi)
+    Han,
+    /// Korean hangul
+    Hangul,
+}
+
+impl Script {
+    fn of(c: char) -> Option<Self> {
+        match c {
+            '\u{3040}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' => {
+                Some(Script::Kana)
+            }
+            // Half-width katakana
+            '\u{FF66}'..='\u{FF9D}' => Some(Script::Kana),
+            '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' => {
+                Some(Script::Han)
+            }
+            '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => {
+                Some(Script::Hangul)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a target language is normally written with this script
+    fn is_used_by(self, target_language: &str) -> bool {
+        match normalize_language_code(target_language).as_str() {
+            "ja" => matches!(self, Script::Kana | Script::Han),
+            "zh" => matches!(self, Script::Han),
+            "ko" => matches!(self, Script::Hangul | Script::Han),
+            _ => false,
+        }
+    }
+}
+
+/// Reason why a translation should be sent back for retranslation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "script")]
+pub enum RetranslationReason {
+    /// The translation still contains source script the target language doesn't use
+    ResidualSourceScript(Script),
+    /// The translation is identical to the source text
+    IdenticalToSource,
+}
+
+/// Find the first script in a translation that the target language doesn't use
+pub fn find_residual_script(translated_text: &str, target_language: &str) -> Option<Script> {
+    translated_text
+        .chars()
+        .filter_map(Script::of)
+        .find(|script| !script.is_used_by(target_language))
+}
+
+/// Whether a translation is an unchanged copy of its source
+///
+/// Texts without letters (numbers, punctuation, placeholders) and very short words
+/// ("HP", "OK") are legitimately kept as is and are not reported.
+pub fn is_identical_to_source(source_text: &str, translated_text: &str) -> bool {
+    let source = source_text.trim();
+    if source != translated_text.trim() {
+        return false;
+    }
+
+    let letters = source
+        .split(['[', ']'])
+        .step_by(2) // Skip placeholder names between brackets
+        .flat_map(str::chars)
+        .filter(|c| c.is_alphabetic())
+        .count();
+    letters > 3
+}
+
+/// Check a translation, returning the reason it needs retranslation (if any)
+pub fn check_translation(
+    source_text: &str,
+    translated_text: &str,
+    target_language: &str,
+) -> Option<RetranslationReason> {
+    if translated_text.trim().is_empty() {
+        return None;
+    }
+    if is_identical_to_source(source_text, translated_text) {
+        return Some(RetranslationReason::IdenticalToSource);
+    }
+    find_residual_script(translated_text, target_language)
+        .map(RetranslationReason::ResidualSourceScript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_residual_japanese_in_french() {
+        assert_eq!(
+            find_residual_script("Le héros dit: ありがとう", "fr"),
+            Some(Script::Kana)
+        );
+        assert_eq!(
+            find_residual_script("Épée de 勇者", "fr"),
+            Some(Script::Han)
+        );
+        assert_eq!(find_residual_script("Le héros dit merci", "fr"), None);
+        // Placeholders and typographic quotes are not script
+        assert_eq!(
+            find_residual_script("[NAME_1] : «\u{a0}Merci\u{a0}»", "fr"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_residual_script_respects_target_language() {
+        assert_eq!(find_residual_script("勇者の剣", "ja"), None);
+        assert_eq!(find_residual_script("勇者之剑", "zh"), None);
+        assert_eq!(find_residual_script("勇者の剣", "zh"), Some(Script::Kana));
+        assert_eq!(find_residual_script("용사의 검", "ko"), None);
+        assert_eq!(
+            find_residual_script("용사의 검", "en-US"),
+            Some(Script::Hangul)
+        );
+    }
+
+    #[test]
+    fn test_identical_to_source() {
+        assert!(is_identical_to_source("ポーション", "ポーション"));
+        assert!(is_identical_to_source(
+            "Potion of healing",
+            " Potion of healing "
+        ));
+        assert!(!is_identical_to_source("ポーション", "Potion"));
+        // Short words, numbers and placeholders are fine to keep
+        assert!(!is_identical_to_source("HP", "HP"));
+        assert!(!is_identical_to_source("100", "100"));
+        assert!(!is_identical_to_source("[NAME_1]", "[NAME_1]"));
+    }
+
+    #[test]
+    fn test_check_translation() {
+        assert_eq!(
+            check_translation("ポーション", "ポーション", "fr"),
+            Some(RetranslationReason::IdenticalToSource)
+        );
+        assert_eq!(
+            check_translation("勇者の剣", "Épée du 勇者", "fr"),
+            Some(RetranslationReason::ResidualSourceScript(Script::Han))
+        );
+        assert_eq!(check_translation("勇者の剣", "Épée du héros", "fr"), None);
+        assert_eq!(check_translation("勇者の剣", "", "fr"), None);
+    }
+}
@@ -1,3 +1,8 @@
+use crate::parsers::text::formatter::quote_policy::normalize_language_code;
+use crate::parsers::text::validation::ignore_rules::{
+    is_latin_only, is_technical_token, IgnoreMatcher,
+};
+
 /// Universal validation: common logic for all engines
 ///
 /// This struct provides unified validation logic that works for all engines
@@ -12,12 +17,33 @@ impl ContentValidator {
     pub fn get_initial_status(
         content: &str,
         target_language: &str,
+    ) -> crate::parsers::engine::TranslationStatus {
+        Self::get_initial_status_with_rules(content, target_language, None)
+    }
+
+    /// Determine the initial translation status, applying project ignore rules
+    ///
+    /// Text is marked as Ignored when it matches a user ignore rule, when it is a
+    /// Latin-script technical token (stat abbreviation, identifier, file name) for any
+    /// target language, or when it is already ASCII text and the target is English.
+    pub fn get_initial_status_with_rules(
+        content: &str,
+        target_language: &str,
+        ignore_rules: Option<&IgnoreMatcher>,
     ) -> crate::parsers::engine::TranslationStatus {
         let content = content.trim();
 
+        if ignore_rules.is_some_and(|rules| rules.is_match(content)) {
+            return crate::parsers::engine::TranslationStatus::Ignored;
+        }
+
+        // Technical tokens stay as they are whatever the target language
+        if is_latin_only(content) && is_technical_token(content) {
+            return crate::parsers::engine::TranslationStatus::Ignored;
+        }
+
         // Check if we're translating to English/ASCII
-        let is_ascii_target =
-            target_language.to_lowercase() == "en" || target_language.to_lowercase() == "english";
+        let is_ascii_target = normalize_language_code(target_language) == "en";
 
         if is_ascii_target {
             // Check if the text is already in ASCII form
@@ -647,4 +673,61 @@ mod tests {
             assert!(result, "Text '{}' should NOT be filtered out", text);
         }
     }
+
+    #[test]
+    fn test_initial_status_ignores_technical_tokens_for_all_targets() {
+        use crate::parsers::engine::TranslationStatus;
+
+        for target in ["fr", "es", "de", "zh"] {
+            for token in ["HP", "MP", "OK", "switch_12", "Actor1.png"] {
+                assert_eq!(
+                    ContentValidator::get_initial_status(token, target),
+                    TranslationStatus::Ignored,
+                    "{} should be ignored for target {}",
+                    token,
+                    target
+                );
+            }
+            assert_eq!(
+                ContentValidator::get_initial_status("Hello world", target),
+                TranslationStatus::NotTranslated
+            );
+            assert_eq!(
+                ContentValidator::get_initial_status("ポーション", target),
+                TranslationStatus::NotTranslated
+            );
+        }
+
+        // English targets still ignore any ASCII text
+        assert_eq!(
+            ContentValidator::get_initial_status("Hello world", "en-US"),
+            TranslationStatus::Ignored
+        );
+    }
+
+    #[test]
+    fn test_initial_status_with_user_rules() {
+        use crate::parsers::engine::TranslationStatus;
+        use crate::parsers::text::validation::IgnoreRules;
+
+        let rules = IgnoreRules {
+            patterns: vec![r"^<.*>$".to_string()],
+            tokens: vec!["Zeal".to_string()],
+        }
+        .compile()
+        .unwrap();
+
+        assert_eq!(
+            ContentValidator::get_initial_status_with_rules("<Boss>", "fr", Some(&rules)),
+            TranslationStatus::Ignored
+        );
+        assert_eq!(
+            ContentValidator::get_initial_status_with_rules("Zeal", "fr", Some(&rules)),
+            TranslationStatus::Ignored
+        );
+        assert_eq!(
+            ContentValidator::get_initial_status_with_rules("Potion", "fr", Some(&rules)),
+            TranslationStatus::NotTranslated
+        );
+    }
 }
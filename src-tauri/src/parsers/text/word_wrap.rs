@@ -0,0 +1,290 @@
+//! Word wrapping for translated message text
+//!
+//! Japanese fits far more characters per line than Latin languages, so translated
+//! dialogue often overflows the message window. This module re-flows restored text
+//! (engine codes already restored) to a maximum width, measured either in characters
+//! or in pixels from a font metrics table. Control codes (`\C[1]`, `\.`, ...) and
+//! leftover placeholders (`[NAME_1]`) have no width and are never split.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Backslash control codes with optional bracket argument: \C[1], \FS<24>, \{, \.
+static CONTROL_CODE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\\[A-Za-z]+(?:\[[^\]]*\]|<[^>]*>)?|\\[^A-Za-z]").unwrap());
+// Translation placeholders that were not restored: [NAME_1], [CTRL_DOT]
+static PLACEHOLDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[[A-Z][A-Za-z0-9_>]*\]").unwrap());
+
+/// Pixel widths of characters for a message window font
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontMetrics {
+    /// Width used for characters missing from `char_widths`
+    pub default_width: u32,
+    /// Width per character (keys are single characters)
+    #[serde(default)]
+    pub char_widths: HashMap<String, u32>,
+}
+
+impl FontMetrics {
+    /// Pixel width of a single character
+    pub fn char_width(&self, c: char) -> u32 {
+        let mut buf = [0u8; 4];
+        self.char_widths
+            .get(c.encode_utf8(&mut buf) as &str)
+            .copied()
+            .unwrap_or(self.default_width)
+    }
+}
+
+/// Word wrap settings for message windows
+///
+/// Widths are in characters, or in pixels when `font_metrics` is provided.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordWrapSettings {
+    /// Maximum line width of a message window without face graphic
+    pub max_width: u32,
+    /// Maximum line width when the message window shows a face graphic
+    pub face_max_width: u32,
+    /// Maximum number of lines displayed by one message window
+    pub max_lines: usize,
+    /// Optional font metrics to measure widths in pixels
+    #[serde(default)]
+    pub font_metrics: Option<FontMetrics>,
+}
+
+impl Default for WordWrapSettings {
+    /// Default RPG Maker MV/MZ message window (816px wide, 28px font) in characters
+    fn default() -> Self {
+        Self {
+            max_width: 55,
+            face_max_width: 42,
+            max_lines: 4,
+            font_metrics: None,
+        }
+    }
+}
+
+impl WordWrapSettings {
+    /// Line width to use depending on whether a face graphic is shown
+    pub fn line_width(&self, has_face: bool) -> u32 {
+        if has_face {
+            self.face_max_width
+        } else {
+            self.max_width
+        }
+    }
+}
+
+/// A piece of text that is either zero-width (code) or a visible character
+enum Token<'a> {
+    Code(&'a str),
+    Char(char),
+}
+
+/// Split text into control codes/placeholders and visible characters
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut codes: Vec<(usize, usize)> = CONTROL_CODE_REGEX
+        .find_iter(text)
+        .chain(PLACEHOLDER_REGEX.find_iter(text))
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    codes.sort_unstable();
+
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    for (start, end) in codes {
+        if start < position {
+            // Overlapping match (placeholder inside a code argument)
+            continue;
+        }
+        tokens.extend(text[position..start].chars().map(Token::Char));
+        tokens.push(Token::Code(&text[start..end]));
+        position = end;
+    }
+    tokens.extend(text[position..].chars().map(Token::Char));
+    tokens
+}
+
+fn char_width(c: char, metrics: Option<&FontMetrics>) -> u32 {
+    match metrics {
+        Some(metrics) => metrics.char_width(c),
+        None => 1,
+    }
+}
+
+/// Measure the display width of text, ignoring control codes and placeholders
+pub fn measure_width(text: &str, metrics: Option<&FontMetrics>) -> u32 {
+    tokenize(text)
+        .iter()
+        .map(|token| match token {
+            Token::Code(_) => 0,
+            Token::Char(c) => char_width(*c, metrics),
+        })
+        .sum()
+}
+
+/// Whether a character is displayed full-width (two cells) in game fonts
+fn is_wide(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{115f}'
+            | '\u{2e80}'..='\u{303e}'
+            | '\u{3040}'..='\u{a4cf}'
+            | '\u{ac00}'..='\u{d7a3}'
+            | '\u{f900}'..='\u{faff}'
+            | '\u{fe30}'..='\u{fe4f}'
+            | '\u{ff00}'..='\u{ff60}'
+            | '\u{ffe0}'..='\u{ffe6}')
+}
+
+/// Measure text in display cells: full-width characters count 2, codes count 0
+pub fn display_cells(text: &str) -> u32 {
+    tokenize(text)
+        .iter()
+        .map(|token| match token {
+            Token::Code(_) => 0,
+            Token::Char(c) if is_wide(*c) => 2,
+            Token::Char(_) => 1,
+        })
+        .sum()
+}
+
+/// Wrap a single line of text to the given width
+///
+/// Lines are broken at spaces. Words longer than the width (or text without spaces,
+/// such as Chinese) are broken between characters, never inside a control code.
+/// Lines that already fit are returned unchanged.
+pub fn wrap_line(text: &str, max_width: u32, metrics: Option<&FontMetrics>) -> Vec<String> {
+    if max_width == 0 || measure_width(text, metrics) <= max_width {
+        return vec![text.to_string()];
+    }
+
+    let space_width = char_width(' ', metrics);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+
+    for word in text.split(' ').filter(|w| !w.is_empty()) {
+        let word_width = measure_width(word, metrics);
+        let separator_width = if current.is_empty() { 0 } else { space_width };
+
+        if current_width + separator_width + word_width <= max_width {
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+            current_width += separator_width + word_width;
+            continue;
+        }
+
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+
+        if word_width <= max_width {
+            current.push_str(word);
+            current_width = word_width;
+            continue;
+        }
+
+        // Break an overlong word between characters
+        for token in tokenize(word) {
+            match token {
+                Token::Code(code) => current.push_str(code),
+                Token::Char(c) => {
+                    let width = char_width(c, metrics);
+                    if current_width + width > max_width && current_width > 0 {
+                        lines.push(std::mem::take(&mut current));
+                        current_width = 0;
+                    }
+                    current.push(c);
+                    current_width += width;
+                }
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+/// Wrap text that may contain line breaks, wrapping each line independently
+pub fn wrap_text(text: &str, max_width: u32, metrics: Option<&FontMetrics>) -> Vec<String> {
+    text.split('\n')
+        .flat_map(|line| wrap_line(line, max_width, metrics))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_ignores_control_codes_and_placeholders() {
+        assert_eq!(measure_width("Hello", None), 5);
+        assert_eq!(measure_width("\\C[2]Hello\\C[0]", None), 5);
+        assert_eq!(measure_width("\\N[1] waits\\.\\|", None), 6);
+        assert_eq!(measure_width("[NAME_1] waits", None), 6);
+    }
+
+    #[test]
+    fn test_display_cells_counts_full_width_double() {
+        assert_eq!(display_cells("Potion"), 6);
+        assert_eq!(display_cells("ポーション"), 10);
+        assert_eq!(display_cells("\\C[2]剣\\C[0] A"), 4);
+    }
+
+    #[test]
+    fn test_wrap_line_fits_unchanged() {
+        let lines = wrap_line("Short line", 20, None);
+        assert_eq!(lines, vec!["Short line".to_string()]);
+    }
+
+    #[test]
+    fn test_wrap_line_breaks_at_spaces() {
+        let lines = wrap_line("The quick brown fox jumps over the lazy dog", 15, None);
+        assert_eq!(
+            lines,
+            vec!["The quick brown", "fox jumps over", "the lazy dog"]
+        );
+        for line in &lines {
+            assert!(measure_width(line, None) <= 15);
+        }
+    }
+
+    #[test]
+    fn test_wrap_line_codes_do_not_count() {
+        let lines = wrap_line("\\C[2]Hero\\C[0] found a \\C[3]sword\\C[0]!", 12, None);
+        assert_eq!(lines, vec!["\\C[2]Hero\\C[0] found a", "\\C[3]sword\\C[0]!"]);
+    }
+
+    #[test]
+    fn test_wrap_line_breaks_text_without_spaces() {
+        let lines = wrap_line("你好世界你好世界", 3, None);
+        assert_eq!(lines, vec!["你好世", "界你好", "世界"]);
+    }
+
+    #[test]
+    fn test_wrap_line_with_pixel_metrics() {
+        let metrics = FontMetrics {
+            default_width: 10,
+            char_widths: HashMap::from([("i".to_string(), 4), (" ".to_string(), 5)]),
+        };
+        // "iiii iiii" = 4*4 + 5 + 4*4 = 37px
+        assert_eq!(measure_width("iiii iiii", Some(&metrics)), 37);
+        let lines = wrap_line("iiii iiii aaaa", 40, Some(&metrics));
+        assert_eq!(lines, vec!["iiii iiii", "aaaa"]);
+    }
+
+    #[test]
+    fn test_wrap_text_keeps_existing_breaks() {
+        let lines = wrap_text("One two three\nfour", 7, None);
+        assert_eq!(lines, vec!["One two", "three", "four"]);
+    }
+}
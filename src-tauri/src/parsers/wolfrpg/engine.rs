@@ -2,7 +2,10 @@
 // Handles version detection and orchestrates file parsing
 
 use crate::parsers::engine::{TextEntry, TranslationEntry};
-use crate::parsers::wolfrpg::files::handler::{extract_all_texts, inject_all_texts};
+use crate::parsers::handler::{FileInjectedCallback, InjectionOptions};
+use crate::parsers::wolfrpg::files::handler::{
+    extract_all_texts, inject_all_texts, inject_all_texts_with_progress,
+};
 use std::path::{Path, PathBuf};
 
 /// Wolf RPG Editor engine handler
@@ -63,4 +66,14 @@ impl WolfRpgEngine {
         // Use the centralized handler to inject into all supported files
         inject_all_texts(game_path, translations)
     }
+
+    /// Inject translations back into game files, calling `on_file` after each file
+    pub fn inject_all_with_progress(
+        game_path: &Path,
+        translations: &[TranslationEntry],
+        options: &InjectionOptions,
+        on_file: &mut FileInjectedCallback<'_>,
+    ) -> Result<(), String> {
+        inject_all_texts_with_progress(game_path, translations, options, on_file)
+    }
 }
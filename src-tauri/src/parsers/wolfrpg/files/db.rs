@@ -2,6 +2,7 @@
 use crate::parsers::engine::{PromptType, TextUnit, TranslationStatus};
 use crate::parsers::text::formatter::EngineFormatter;
 use crate::parsers::text::formatter::WolfRpgFormatter;
+use crate::parsers::text::formatter::source_quote_marks;
 use crate::parsers::wolfrpg::WolfRpgTextValidator;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -60,6 +61,7 @@ fn extract_from_db_data_entry(
                         normalized_path, type_idx, data_idx
                     ),
                     source_text: processed_text,
+                    source_quotes: source_quote_marks(name),
                     translated_text: String::new(),
                     field_type: format!("Database entry name ({})", file_name),
                     status: TranslationStatus::NotTranslated,
@@ -95,6 +97,7 @@ fn extract_from_db_data_entry(
                                 normalized_path, type_idx, data_idx, data_data_idx
                             ),
                             source_text: processed_text,
+                            source_quotes: source_quote_marks(value),
                             translated_text: String::new(),
                             field_type: format!("Database value ({})", file_name),
                             status: TranslationStatus::NotTranslated,
@@ -2,6 +2,8 @@
 // Orchestrates parsing of mps/ directory only
 
 use crate::parsers::engine::{TextEntry, TextUnit, TranslationEntry};
+use crate::parsers::handler::{FileInjectedCallback, InjectionOptions};
+use crate::parsers::json_format::to_string_preserving;
 use crate::parsers::wolfrpg::files::{common, db, mps};
 use std::collections::HashMap;
 use std::fs;
@@ -32,6 +34,7 @@ pub fn extract_all_texts(game_path: &Path) -> Result<Vec<TextEntry>, String> {
                     all_texts.push(TextEntry {
                         id: unit.id.clone(),
                         source_text: unit.source_text.clone(),
+                        source_quotes: unit.source_quotes.clone(),
                         translated_text: unit.translated_text.clone(),
                         field_type: unit.field_type.clone(),
                         status: unit.status.clone(),
@@ -69,6 +72,7 @@ pub fn extract_all_texts(game_path: &Path) -> Result<Vec<TextEntry>, String> {
                     all_texts.push(TextEntry {
                         id: unit.id.clone(),
                         source_text: unit.source_text.clone(),
+                        source_quotes: unit.source_quotes.clone(),
                         translated_text: unit.translated_text.clone(),
                         field_type: unit.field_type.clone(),
                         status: unit.status.clone(),
@@ -106,6 +110,7 @@ pub fn extract_all_texts(game_path: &Path) -> Result<Vec<TextEntry>, String> {
                     all_texts.push(TextEntry {
                         id: unit.id.clone(),
                         source_text: unit.source_text.clone(),
+                        source_quotes: unit.source_quotes.clone(),
                         translated_text: unit.translated_text.clone(),
                         field_type: unit.field_type.clone(),
                         status: unit.status.clone(),
@@ -124,6 +129,25 @@ pub fn extract_all_texts(game_path: &Path) -> Result<Vec<TextEntry>, String> {
 
 /// Inject all translations into Wolf RPG project
 pub fn inject_all_texts(game_path: &Path, translations: &[TranslationEntry]) -> Result<(), String> {
+    inject_all_texts_with_progress(
+        game_path,
+        translations,
+        &InjectionOptions::default(),
+        &mut |_| Ok(()),
+    )
+}
+
+/// Inject all translations, calling `on_file` after each injected file
+///
+/// `on_file` receives the path of the file relative to the game root; an error it
+/// returns stops the injection before the next file. Files left out of
+/// `options.files` are skipped.
+pub fn inject_all_texts_with_progress(
+    game_path: &Path,
+    translations: &[TranslationEntry],
+    options: &InjectionOptions,
+    on_file: &mut FileInjectedCallback<'_>,
+) -> Result<(), String> {
     // Build HashMap for quick lookup by id
     // We need owned values first, then create references
     let mut text_units_map: HashMap<String, TextUnit> = HashMap::new();
@@ -133,6 +157,7 @@ pub fn inject_all_texts(game_path: &Path, translations: &[TranslationEntry]) ->
             TextUnit {
                 id: t.id.clone(),
                 source_text: String::new(),
+                source_quotes: None,
                 translated_text: t.translated_text.clone(),
                 field_type: String::new(),
                 status: crate::parsers::engine::TranslationStatus::Translated,
@@ -154,20 +179,21 @@ pub fn inject_all_texts(game_path: &Path, translations: &[TranslationEntry]) ->
         if db_dir.exists() {
             for db_file in ["DataBase.json"/* , "CDataBase.json", "SysDatabase.json"*/] {
                 let db_path = db_dir.join(db_file);
-                if db_path.exists() {
+                let relative_path = format!("dump/db/{}", db_file);
+                if db_path.exists() && options.includes_file(&relative_path) {
                     let content = fs::read_to_string(&db_path)
                         .map_err(|e| format!("Erreur lecture {}: {}", db_file, e))?;
                     let mut json: serde_json::Value = serde_json::from_str(&content)
                         .map_err(|e| format!("Erreur parsing {}: {}", db_file, e))?;
 
-                    let relative_path = format!("dump/db/{}", db_file);
                     db::inject_text_units_into_db(&mut json, &text_units_refs, &relative_path);
 
-                    // Write back to file
-                    let updated_content = serde_json::to_string_pretty(&json)
+                    // Write back to file in its original format
+                    let updated_content = to_string_preserving(&content, &json)
                         .map_err(|e| format!("Erreur sérialisation {}: {}", db_file, e))?;
                     fs::write(&db_path, updated_content)
                         .map_err(|e| format!("Erreur écriture {}: {}", db_file, e))?;
+                    on_file(&relative_path)?;
                 }
             }
         }
@@ -178,24 +204,27 @@ pub fn inject_all_texts(game_path: &Path, translations: &[TranslationEntry]) ->
         for entry in fs::read_dir(&mps_dir).map_err(|e| format!("Erreur lecture mps/: {}", e))? {
             let entry = entry.map_err(|e| format!("Erreur entrée mps/: {}", e))?;
             let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            let file_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+            let relative_path = format!("dump/mps/{}", file_name);
+            if path.extension().and_then(|s| s.to_str()) == Some("json")
+                && options.includes_file(&relative_path)
+            {
                 let content = fs::read_to_string(&path)
                     .map_err(|e| format!("Erreur lecture {:?}: {}", path, e))?;
                 let mut json: serde_json::Value = serde_json::from_str(&content)
                     .map_err(|e| format!("Erreur parsing {:?}: {}", path, e))?;
 
-                let file_name = path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("unknown");
-                let relative_path = format!("dump/mps/{}", file_name);
                 mps::inject_text_units_into_mps(&mut json, &text_units_refs, &relative_path);
 
-                // Write back to file
-                let updated_content = serde_json::to_string_pretty(&json)
+                // Write back to file in its original format
+                let updated_content = to_string_preserving(&content, &json)
                     .map_err(|e| format!("Erreur sérialisation {:?}: {}", path, e))?;
                 fs::write(&path, updated_content)
                     .map_err(|e| format!("Erreur écriture {:?}: {}", path, e))?;
+                on_file(&relative_path)?;
             }
         }
     }
@@ -207,23 +236,26 @@ pub fn inject_all_texts(game_path: &Path, translations: &[TranslationEntry]) ->
                 .map_err(|e| format!("Erreur lecture common/: {}", e))? {
                 let entry = entry.map_err(|e| format!("Erreur entrée common/: {}", e))?;
                 let path = entry.path();
-                if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                let file_name = path.file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("unknown");
+                let relative_path = format!("dump/common/{}", file_name);
+                if path.extension().and_then(|s| s.to_str()) == Some("json")
+                    && options.includes_file(&relative_path)
+                {
                     let content = fs::read_to_string(&path)
                         .map_err(|e| format!("Erreur lecture {:?}: {}", path, e))?;
                     let mut json: serde_json::Value = serde_json::from_str(&content)
                         .map_err(|e| format!("Erreur parsing {:?}: {}", path, e))?;
 
-                    let file_name = path.file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("unknown");
-                    let relative_path = format!("dump/common/{}", file_name);
                     common::inject_text_units_into_common(&mut json, &text_units_refs, &relative_path);
 
-                    // Write back to file
-                    let updated_content = serde_json::to_string_pretty(&json)
+                    // Write back to file in its original format
+                    let updated_content = to_string_preserving(&content, &json)
                         .map_err(|e| format!("Erreur sérialisation {:?}: {}", path, e))?;
                     fs::write(&path, updated_content)
                         .map_err(|e| format!("Erreur écriture {:?}: {}", path, e))?;
+                    on_file(&relative_path)?;
                 }
             }
         }
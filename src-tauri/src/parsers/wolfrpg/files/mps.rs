@@ -2,6 +2,7 @@
 use crate::parsers::engine::{PromptType, TextUnit, TranslationStatus};
 use crate::parsers::text::formatter::EngineFormatter;
 use crate::parsers::text::formatter::WolfRpgFormatter;
+use crate::parsers::text::formatter::source_quote_marks;
 use crate::parsers::wolfrpg::WolfRpgTextValidator;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -175,6 +176,7 @@ fn extract_command_strings(
                         normalized_path, event_idx, page_idx, cmd_idx, arg_idx
                     ),
                     source_text: processed_text,
+                    source_quotes: source_quote_marks(arg_text),
                     translated_text: String::new(),
                     field_type: format!(
                         "command_{}:{}:events[{}].pages[{}].list[{}]",
@@ -1,8 +1,12 @@
 // Wolf RPG Editor Handler Implementation
 // Implements GameEngineHandler for Wolf RPG Editor
 
-use crate::parsers::engine::{TextEntry, TranslationEntry};
-use crate::parsers::handler::{GameEngineHandler, ValidationResult};
+use crate::parsers::engine::{GameEngine, TextEntry, TranslationEntry};
+use crate::parsers::handler::{
+    list_json_files, FileInjectedCallback, GameEngineHandler, InjectionOptions,
+    ValidationResult,
+};
+use crate::parsers::text::length_budget::{default_budgets, LengthBudget};
 use crate::parsers::wolfrpg::engine::WolfRpgEngine;
 use std::path::{Path, PathBuf};
 
@@ -36,79 +40,38 @@ impl GameEngineHandler for WolfRpgHandler {
         &self,
         game_path: &Path,
         translations: &[TranslationEntry],
+        options: &InjectionOptions,
     ) -> Result<(), String> {
-        WolfRpgEngine::inject_all(game_path, translations)
+        WolfRpgEngine::inject_all_with_progress(game_path, translations, options, &mut |_| Ok(()))
     }
 
-    fn count_files_to_process(&self, game_path: &Path) -> usize {
-        let dump_root = WolfRpgEngine::get_data_root(game_path);
-        
-        if !dump_root.exists() {
-            return 0;
-        }
-
-        let mut count = 0;
-
-        // Count JSON files in dump/db/
-        let db_dir = dump_root.join("db");
-        if db_dir.exists() {
-            if let Ok(entries) = std::fs::read_dir(&db_dir) {
-                for entry in entries.flatten() {
-                    if let Ok(file_type) = entry.file_type() {
-                        if file_type.is_file() {
-                            if let Some(ext) = entry.path().extension() {
-                                if ext == "json" {
-                                    count += 1;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Count JSON files in dump/mps/
-        let mps_dir = dump_root.join("mps");
-        if mps_dir.exists() {
-            if let Ok(entries) = std::fs::read_dir(&mps_dir) {
-                for entry in entries.flatten() {
-                    if let Ok(file_type) = entry.file_type() {
-                        if file_type.is_file() {
-                            if let Some(ext) = entry.path().extension() {
-                                if ext == "json" {
-                                    count += 1;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    fn inject_all_texts_with_progress(
+        &self,
+        game_path: &Path,
+        translations: &[TranslationEntry],
+        options: &InjectionOptions,
+        on_file: &mut FileInjectedCallback<'_>,
+    ) -> Result<(), String> {
+        WolfRpgEngine::inject_all_with_progress(game_path, translations, options, on_file)
+    }
 
-        // Count JSON files in dump/common/
-        let common_dir = dump_root.join("common");
-        if common_dir.exists() {
-            if let Ok(entries) = std::fs::read_dir(&common_dir) {
-                for entry in entries.flatten() {
-                    if let Ok(file_type) = entry.file_type() {
-                        if file_type.is_file() {
-                            if let Some(ext) = entry.path().extension() {
-                                if ext == "json" {
-                                    count += 1;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    fn list_files_to_process(&self, game_path: &Path) -> Vec<PathBuf> {
+        let dump_root = WolfRpgEngine::get_data_root(game_path);
 
-        count
+        // JSON files in dump/db/, dump/mps/ and dump/common/
+        ["db", "mps", "common"]
+            .iter()
+            .flat_map(|dir| list_json_files(&dump_root.join(dir)))
+            .collect()
     }
 
     fn get_data_root(&self, game_path: &Path) -> PathBuf {
         WolfRpgEngine::get_data_root(game_path)
     }
+
+    fn default_length_budgets(&self) -> Vec<LengthBudget> {
+        default_budgets(GameEngine::WolfRPG)
+    }
 }
 
 #[cfg(test)]
@@ -274,7 +237,11 @@ mod tests {
         }
 
         // Inject translations
-        let inject_result = handler.inject_all_texts(&temp_game_path, &translations);
+        let inject_result = handler.inject_all_texts(
+            &temp_game_path,
+            &translations,
+            &InjectionOptions::default(),
+        );
         assert!(inject_result.is_ok(), "Injection should succeed: {:?}", inject_result.err());
 
         // Verify injection by re-extracting
@@ -444,7 +411,11 @@ mod tests {
         }
 
         // Inject translations
-        let inject_result = handler.inject_all_texts(&temp_game_path, &translations);
+        let inject_result = handler.inject_all_texts(
+            &temp_game_path,
+            &translations,
+            &InjectionOptions::default(),
+        );
         assert!(inject_result.is_ok(), "Injection should succeed: {:?}", inject_result.err());
 
         // Verify injection by re-extracting
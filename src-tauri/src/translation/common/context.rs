@@ -0,0 +1,190 @@
+// Surrounding dialogue context for sequential translation
+// Gives the model the previous lines of the same event page and the current speaker,
+// so pronouns and tone stay consistent from one message line to the next
+
+use crate::translation::common::types::TranslationText;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Speaker tag at the start of a line: 【アリス】, or a name directly followed by a quote: アリス「
+static SPEAKER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:【([^】\n]{1,20})】|([^\s「」『』【】:：]{1,12})[「『])").unwrap()
+});
+
+/// Event page or common event a dialogue line belongs to, from its structured location
+///
+/// - RPG Maker: "map:9:event:1:message:12" -> "map:9:event:1", "common_event:3:choice:4:0" -> "common_event:3"
+/// - Wolf RPG: "wolf_json:Map001.json#events[1].pages[0].list[5].stringArgs[0]" -> "wolf_json:Map001.json#events[1].pages[0]"
+///
+/// Database fields (item names, system terms...) have no dialogue group.
+pub fn dialogue_group(location: &str) -> Option<&str> {
+    if location.starts_with("wolf_json:") {
+        let end = location
+            .rfind(".list[")
+            .or_else(|| location.rfind("#commands["))?;
+        return Some(&location[..end]);
+    }
+
+    [":message:", ":choice:"]
+        .iter()
+        .filter_map(|marker| location.find(marker))
+        .min()
+        .map(|end| &location[..end])
+}
+
+/// Speaker name announced at the start of a dialogue line, if any
+pub fn detect_speaker(source_text: &str) -> Option<String> {
+    let caps = SPEAKER_REGEX.captures(source_text)?;
+    caps.get(1)
+        .or_else(|| caps.get(2))
+        .map(|name| name.as_str().trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Build the dialogue context of the entry at `index`
+///
+/// Walks back over at most `max_lines` entries of the same dialogue group
+/// (entries are in extraction order) and renders them with their translation
+/// when one is already known. The speaker is taken from the entry itself, or
+/// else from the closest previous line announcing one.
+///
+/// # Arguments
+/// * `texts` - Texts of the session, in extraction order
+/// * `translations` - Translations already made in the session, by entry id
+/// * `index` - Index of the entry to translate
+/// * `max_lines` - Number of previous lines to include (0 disables the context)
+///
+/// # Returns
+/// * Context text for `SingleTranslationRequest::context`, None when there is nothing to add
+pub fn build_dialogue_context(
+    texts: &[TranslationText],
+    translations: &HashMap<i32, String>,
+    index: usize,
+    max_lines: usize,
+) -> Option<String> {
+    if max_lines == 0 {
+        return None;
+    }
+
+    let current = texts.get(index)?;
+    let group = dialogue_group(current.location.as_deref()?)?;
+
+    let previous: Vec<&TranslationText> = texts[..index]
+        .iter()
+        .rev()
+        .take_while(|text| text.location.as_deref().and_then(dialogue_group) == Some(group))
+        .take(max_lines)
+        .collect();
+
+    let speaker = detect_speaker(&current.source_text).or_else(|| {
+        previous
+            .iter()
+            .find_map(|text| detect_speaker(&text.source_text))
+    });
+
+    if previous.is_empty() && speaker.is_none() {
+        return None;
+    }
+
+    let mut context = String::new();
+    if let Some(speaker) = speaker {
+        context.push_str(&format!("Speaker: {}\n", speaker));
+    }
+    if !previous.is_empty() {
+        context.push_str("Previous lines:\n");
+        for text in previous.iter().rev() {
+            let source = one_line(&text.source_text);
+            match translations.get(&text.id) {
+                Some(translated) => {
+                    context.push_str(&format!("- {} => {}\n", source, one_line(translated)))
+                }
+                None => context.push_str(&format!("- {}\n", source)),
+            }
+        }
+    }
+
+    Some(context.trim_end().to_string())
+}
+
+/// Keep one context line per entry
+fn one_line(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(id: i32, source_text: &str, location: &str) -> TranslationText {
+        TranslationText {
+            id,
+            source_text: source_text.to_string(),
+            context: None,
+            text_type: Some("dialogue".to_string()),
+            location: Some(location.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_dialogue_group_from_locations() {
+        assert_eq!(dialogue_group("map:9:event:1:message:12"), Some("map:9:event:1"));
+        assert_eq!(dialogue_group("common_event:3:choice:4:0"), Some("common_event:3"));
+        assert_eq!(
+            dialogue_group("wolf_json:Map001.json#events[1].pages[0].list[5].stringArgs[0]"),
+            Some("wolf_json:Map001.json#events[1].pages[0]")
+        );
+        assert_eq!(
+            dialogue_group("wolf_json:CommonEvent/12.json#commands[3].stringArgs[0]"),
+            Some("wolf_json:CommonEvent/12.json")
+        );
+        assert_eq!(dialogue_group("item:1:name"), None);
+        assert_eq!(dialogue_group("system:terms:basic:0"), None);
+    }
+
+    #[test]
+    fn test_detect_speaker() {
+        assert_eq!(detect_speaker("【アリス】"), Some("アリス".to_string()));
+        assert_eq!(detect_speaker("【[NAME_1]】どうしたの？"), Some("[NAME_1]".to_string()));
+        assert_eq!(detect_speaker("アリス「行こう！」"), Some("アリス".to_string()));
+        assert_eq!(detect_speaker("「行こう！」"), None);
+        assert_eq!(detect_speaker("勇者が来た。"), None);
+    }
+
+    #[test]
+    fn test_build_dialogue_context_uses_previous_lines_of_same_event() {
+        let texts = vec![
+            text(1, "宿屋へようこそ。", "map:1:event:2:message:0"),
+            text(2, "【アリス】", "map:1:event:3:message:0"),
+            text(3, "ねえ、聞いて。", "map:1:event:3:message:1"),
+            text(4, "彼が来たの。", "map:1:event:3:message:2"),
+        ];
+        let translations = HashMap::from([(3, "Dis, écoute.".to_string())]);
+
+        let context = build_dialogue_context(&texts, &translations, 3, 5).unwrap();
+        assert_eq!(
+            context,
+            "Speaker: アリス\nPrevious lines:\n- 【アリス】\n- ねえ、聞いて。 => Dis, écoute."
+        );
+
+        let context = build_dialogue_context(&texts, &translations, 3, 1).unwrap();
+        assert_eq!(context, "Previous lines:\n- ねえ、聞いて。 => Dis, écoute.");
+    }
+
+    #[test]
+    fn test_build_dialogue_context_skips_entries_without_context() {
+        let texts = vec![
+            text(1, "宿屋へようこそ。", "map:1:event:2:message:0"),
+            text(2, "ポーション", "item:1:name"),
+        ];
+        let translations = HashMap::new();
+
+        assert_eq!(build_dialogue_context(&texts, &translations, 0, 3), None);
+        assert_eq!(build_dialogue_context(&texts, &translations, 1, 3), None);
+        assert_eq!(build_dialogue_context(&texts, &translations, 0, 0), None);
+    }
+}
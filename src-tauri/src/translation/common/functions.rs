@@ -36,9 +36,14 @@
 //! }
 //! ```
 
+use crate::parsers::text::length_budget::{check_length_budget, BudgetViolation, LengthBudget};
+use crate::translation::common::context::build_dialogue_context;
 use crate::translation::common::types::*;
 use crate::translation::glossary::lookup_glossary_terms;
-use crate::translation::ollama::common::{build_translation_prompt, parse_translation_response, validate_translation_request};
+use crate::translation::machine::same_placeholders;
+use crate::translation::memory::normalize_memory_source;
+use std::collections::HashMap;
+use crate::translation::ollama::common::{append_length_constraint, build_batch_translation_prompt, build_translation_prompt, parse_batch_translation_response, parse_translation_response, validate_translation_request, MAX_BATCH_ENTRY_LENGTH};
 use tauri::AppHandle;
 
 /// # Translation Client Trait
@@ -116,37 +121,14 @@ pub async fn translate_single_common<T: TranslationClient>(
     let start_time = std::time::Instant::now();
 
     // Lookup glossary terms (common logic)
-    let source_lang = request.source_language.as_deref().unwrap_or("ja");
-    let target_lang = request.target_language.as_deref().unwrap_or("fr");
-
-    let category = crate::translation::glossary::map_text_type_to_category(request.text_type.as_deref());
-
-    let glossary_terms = match lookup_glossary_terms(
+    let glossary_terms = lookup_request_glossary(
         app_handle,
-        source_lang,
-        target_lang,
+        request.source_language.as_deref(),
+        request.target_language.as_deref(),
         request.project_id,
-        category,
+        request.text_type.as_deref(),
     )
-    .await
-    {
-        Ok(terms) => {
-            log::debug!(
-                "Found {} glossary terms for {}-{}",
-                terms.len(),
-                source_lang,
-                target_lang
-            );
-            Some(terms)
-        }
-        Err(e) => {
-            log::warn!(
-                "Failed to lookup glossary terms: {}, continuing without glossary",
-                e
-            );
-            None
-        }
-    };
+    .await;
 
     // Build prompt using common prompt builder
     let prompt = build_translation_prompt(
@@ -154,7 +136,12 @@ pub async fn translate_single_common<T: TranslationClient>(
         request.source_language.as_deref(),
         request.target_language.as_deref(),
         glossary_terms.as_deref(),
+        request.context.as_deref(),
     );
+    let prompt = match request.max_length {
+        Some(max_length) => append_length_constraint(&prompt, max_length),
+        None => prompt,
+    };
 
     // Get model (clone to avoid move)
     let model = request.model.clone();
@@ -175,6 +162,93 @@ pub async fn translate_single_common<T: TranslationClient>(
     })
 }
 
+/// Common batch translation function that works with any client
+///
+/// Packs all entries into one numbered prompt. Entries the model skipped,
+/// duplicated or answered with different placeholders come back as None so
+/// the caller can translate them one by one.
+pub async fn translate_batch_common<T: TranslationClient>(
+    client: &T,
+    request: BatchTranslationRequest,
+    app_handle: &AppHandle,
+) -> Result<BatchTranslationResult, String> {
+    for source_text in &request.source_texts {
+        validate_translation_request(source_text)?;
+    }
+
+    let start_time = std::time::Instant::now();
+
+    let glossary_terms = lookup_request_glossary(
+        app_handle,
+        request.source_language.as_deref(),
+        request.target_language.as_deref(),
+        request.project_id,
+        request.text_type.as_deref(),
+    )
+    .await;
+
+    let prompt = build_batch_translation_prompt(
+        &request.source_texts,
+        request.source_language.as_deref(),
+        request.target_language.as_deref(),
+        glossary_terms.as_deref(),
+        request.context.as_deref(),
+    );
+
+    let model = request.model.clone();
+    let response = client.call_api(&prompt, model.clone()).await?;
+
+    let translations = parse_batch_translation_response(&response, request.source_texts.len())
+        .into_iter()
+        .zip(&request.source_texts)
+        .map(|(translation, source_text)| {
+            translation.filter(|translated| same_placeholders(source_text, translated))
+        })
+        .collect();
+
+    Ok(BatchTranslationResult {
+        translations,
+        model_used: model.unwrap_or_else(|| "ludolingo:latest".to_string()),
+        processing_time_ms: start_time.elapsed().as_millis() as u64,
+    })
+}
+
+/// Lookup glossary terms for a request, continuing without glossary on error
+async fn lookup_request_glossary(
+    app_handle: &AppHandle,
+    source_language: Option<&str>,
+    target_language: Option<&str>,
+    project_id: Option<i64>,
+    text_type: Option<&str>,
+) -> Option<Vec<(String, String)>> {
+    let source_lang = source_language.unwrap_or("ja");
+    let target_lang = target_language.unwrap_or("fr");
+
+    let category = crate::translation::glossary::map_text_type_to_category(text_type);
+
+    match lookup_glossary_terms(app_handle, source_lang, target_lang, project_id, category).await {
+        Ok(terms) => {
+            log::debug!(
+                "Found {} glossary terms for {}-{}",
+                terms.len(),
+                source_lang,
+                target_lang
+            );
+            Some(terms)
+        }
+        Err(e) => {
+            log::warn!(
+                "Failed to lookup glossary terms: {}, continuing without glossary",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Delay between two requests of a session sending one request at a time
+pub const SEQUENTIAL_REQUEST_DELAY_MS: u64 = 500;
+
 /// # Sequential Translation Functions
 ///
 /// These functions provide common logic for sequential translation operations.
@@ -188,11 +262,11 @@ pub async fn translate_single_common<T: TranslationClient>(
 /// * `counter` - Mutable reference to session counter for uniqueness
 ///
 /// # Returns
-/// * Unique session ID string
+/// * Unique session ID string, also across app restarts since sessions are persisted by the frontend
 pub fn common_generate_session_id(prefix: &str, counter: &mut u64) -> String {
     let current = *counter;
     *counter += 1;
-    format!("{}{}", prefix, current)
+    format!("{}{}_{}", prefix, chrono::Utc::now().timestamp_millis(), current)
 }
 
 /// Get progress information from a sequential session
@@ -203,18 +277,15 @@ pub fn common_generate_session_id(prefix: &str, counter: &mut u64) -> String {
 /// # Returns
 /// * SequentialProgress structure with current status
 pub fn common_get_session_progress(session: &SequentialSession) -> SequentialProgress {
-    let total_count = session.texts.len() as i32;
+    let duplicate_count: usize = session.duplicates.values().map(Vec::len).sum();
+    let total_count = (session.texts.len() + duplicate_count) as i32;
     let processed_count = session.processed_entries.len() as i32;
     let current_entry = session.texts.get(session.current_index).map(|text| text.id);
 
-    // Estimate remaining time (rough calculation: 3 seconds per entry)
-    let avg_time_per_entry = 3.0; // seconds
-    let remaining_entries = total_count - processed_count;
-    let estimated_time_remaining = if processed_count > 0 {
-        Some((remaining_entries as f64 * avg_time_per_entry) as i64)
-    } else {
-        None
-    };
+    let estimated_time_remaining = common_estimate_time_remaining(session);
+    let throughput = session
+        .stats
+        .throughput(session.stats.active_time(session.start_time));
 
     SequentialProgress {
         session_id: session.session_id.clone(),
@@ -226,7 +297,233 @@ pub fn common_get_session_progress(session: &SequentialSession) -> SequentialPro
         errors: session.errors.clone(),
         successful_translations: session.successful_translations.clone(),
         pause_time_remaining: None, // Sera mis à jour par le gestionnaire de session
+        throughput,
+        latency_by_model: session.stats.latency_by_model(),
+    }
+}
+
+/// Estimate the remaining time of a sequential session, in seconds
+///
+/// Uses the moving average of the measured time per source character, applied to
+/// the length of the entries left, spread over the parallel requests. The delay
+/// between sequential requests and the pauses still to come (`PauseSettings`) are added.
+///
+/// # Arguments
+/// * `session` - Reference to the sequential session
+///
+/// # Returns
+/// * Remaining seconds, None until a first translation has been timed
+pub fn common_estimate_time_remaining(session: &SequentialSession) -> Option<i64> {
+    let ms_per_char = session.stats.ms_per_char()?;
+
+    let remaining = &session.texts[session.current_index.min(session.texts.len())..];
+    let remaining_chars: usize = remaining
+        .iter()
+        .map(|text| text.source_text.chars().count().max(1))
+        .sum();
+
+    let parallel_requests = session.parallel_requests.max(1);
+    let mut remaining_ms = ms_per_char * remaining_chars as f64 / parallel_requests as f64;
+
+    if parallel_requests == 1 {
+        let requests = remaining.len().div_ceil(session.prompt_batch_size.max(1));
+        remaining_ms += (requests as u64 * SEQUENTIAL_REQUEST_DELAY_MS) as f64;
+    }
+
+    let pause = &session.pause_settings;
+    if pause.enabled && pause.batch_size > 0 {
+        let pause_ms = pause.pause_duration_minutes as f64 * 60_000.0;
+
+        // A pause follows every `batch_size` translations
+        let batch_size = pause.batch_size as usize;
+        let into_batch = session.stats.translated_entries() % batch_size;
+        let upcoming_pauses = (into_batch + remaining.len()) / batch_size;
+        remaining_ms += upcoming_pauses as f64 * pause_ms;
+
+        // Rest of the pause in progress
+        if let Some(paused_for) = session.stats.current_pause() {
+            remaining_ms += (pause_ms - paused_for.as_millis() as f64).max(0.0);
+        }
+    }
+
+    Some((remaining_ms / 1000.0).round() as i64)
+}
+
+/// Count the entries to pack into the next batch prompt
+///
+/// Starting at `start`, takes up to `batch_size` consecutive short entries
+/// sharing the text type of the first one (the glossary category is per prompt).
+///
+/// # Arguments
+/// * `texts` - Texts of the session
+/// * `start` - Index of the next entry to translate
+/// * `batch_size` - Maximum entries per prompt
+///
+/// # Returns
+/// * Number of entries for the next prompt: 1 means a regular single prompt
+pub fn common_select_batch(texts: &[TranslationText], start: usize, batch_size: usize) -> usize {
+    let is_batchable = |text: &TranslationText| {
+        !text.source_text.trim().is_empty()
+            && text.source_text.chars().count() <= MAX_BATCH_ENTRY_LENGTH
+    };
+
+    let Some(first) = texts.get(start) else {
+        return 0;
+    };
+    if batch_size <= 1 || !is_batchable(first) {
+        return 1;
     }
+
+    texts[start..]
+        .iter()
+        .take(batch_size)
+        .take_while(|text| is_batchable(text) && text.text_type == first.text_type)
+        .count()
+}
+
+/// Build the dialogue context of the entry at `index` in a sequential session
+///
+/// With one request at a time, every previous chunk is committed before the next one
+/// is sent, so the previous lines come with their translation. With parallel requests,
+/// which of them are committed depends on timing: the context then only gives the
+/// source lines, so that the prompt of an entry does not change from one run to another.
+///
+/// # Arguments
+/// * `session` - Reference to the sequential session
+/// * `index` - Index of the entry to translate
+///
+/// # Returns
+/// * Context text for `SingleTranslationRequest::context`, None when there is nothing to add
+pub fn common_dialogue_context(session: &SequentialSession, index: usize) -> Option<String> {
+    let no_translations = HashMap::new();
+    let translations = if session.parallel_requests > 1 {
+        &no_translations
+    } else {
+        &session.translated_texts
+    };
+    build_dialogue_context(&session.texts, translations, index, session.context_lines)
+}
+
+/// Text types translated entry by entry by default: the same line may need a different
+/// translation depending on who says it and when
+pub const DEFAULT_DEDUPLICATE_EXCLUDED_TYPES: &[&str] = &["dialogue"];
+
+/// Group the entries of a session sharing the same source text
+///
+/// From `start` on, an entry whose normalized source text (as keyed in the translation
+/// memory) was already seen is removed from `texts` and attached to the first entry
+/// with that text, which is translated once for all of them. Entries of an excluded
+/// text type are always translated on their own.
+///
+/// # Arguments
+/// * `texts` - Texts of the session, duplicates are removed in place
+/// * `start` - Index of the first entry to translate, earlier entries are left alone
+/// * `excluded_text_types` - Text types never grouped, None for `DEFAULT_DEDUPLICATE_EXCLUDED_TYPES`
+///
+/// # Returns
+/// * Entries removed, by ID of the entry translated for them
+pub fn common_group_duplicates(
+    texts: &mut Vec<TranslationText>,
+    start: usize,
+    excluded_text_types: Option<&[String]>,
+) -> HashMap<i32, Vec<TranslationText>> {
+    let is_excluded = |text: &TranslationText| match (&text.text_type, excluded_text_types) {
+        (Some(text_type), Some(excluded)) => excluded.contains(text_type),
+        (Some(text_type), None) => DEFAULT_DEDUPLICATE_EXCLUDED_TYPES.contains(&text_type.as_str()),
+        (None, _) => false,
+    };
+
+    let start = start.min(texts.len());
+    let mut first_by_source: HashMap<String, i32> = HashMap::new();
+    let mut duplicates: HashMap<i32, Vec<TranslationText>> = HashMap::new();
+    let mut kept = Vec::with_capacity(texts.len() - start);
+
+    for text in texts.drain(start..) {
+        let normalized = normalize_memory_source(&text.source_text);
+        if is_excluded(&text) || normalized.is_empty() {
+            kept.push(text);
+            continue;
+        }
+        match first_by_source.get(&normalized) {
+            Some(first_id) => duplicates.entry(*first_id).or_default().push(text),
+            None => {
+                first_by_source.insert(normalized, text.id);
+                kept.push(text);
+            }
+        }
+    }
+    texts.extend(kept);
+
+    duplicates
+}
+
+/// Record a successful translation for the entries grouped with the translated one
+///
+/// Each duplicate gets its own record, with the length budget of its own location.
+///
+/// # Arguments
+/// * `session` - Mutable reference to the sequential session
+/// * `translation` - Translation of the entry the duplicates are attached to
+pub fn common_fan_out_translation(session: &mut SequentialSession, translation: &SuccessfulTranslation) {
+    let Some(duplicates) = session.duplicates.get(&translation.entry_id) else {
+        return;
+    };
+
+    for duplicate in duplicates {
+        let over_budget = common_check_length_budget(
+            duplicate.location.as_deref(),
+            &translation.translated_text,
+            &session.length_budgets,
+        );
+        session.processed_entries.insert(duplicate.id, true);
+        session
+            .translated_texts
+            .insert(duplicate.id, translation.translated_text.clone());
+        session.successful_translations.push(SuccessfulTranslation {
+            entry_id: duplicate.id,
+            processing_time_ms: 0,
+            over_budget,
+            ..translation.clone()
+        });
+    }
+}
+
+/// Record a failed translation for the entries grouped with the failed one
+///
+/// The duplicates are reported as failed too, and requeued with it.
+///
+/// # Arguments
+/// * `session` - Mutable reference to the sequential session
+/// * `error` - Error of the entry the duplicates are attached to
+pub fn common_fan_out_error(session: &mut SequentialSession, error: &SequentialError) {
+    let Some(duplicates) = session.duplicates.get(&error.entry_id) else {
+        return;
+    };
+
+    for duplicate in duplicates {
+        session.processed_entries.insert(duplicate.id, false);
+        session.errors.push(SequentialError {
+            entry_id: duplicate.id,
+            ..error.clone()
+        });
+    }
+}
+
+/// Check a translation against the length budget of its text location
+///
+/// # Arguments
+/// * `location` - Structured location of the text ("item:1:name"), None if unknown
+/// * `translated_text` - Translated text to check
+/// * `budgets` - Length budgets of the session
+///
+/// # Returns
+/// * The budget violation, or None when the text fits or no budget applies
+pub fn common_check_length_budget(
+    location: Option<&str>,
+    translated_text: &str,
+    budgets: &[LengthBudget],
+) -> Option<BudgetViolation> {
+    check_length_budget(location?, translated_text, budgets)
 }
 
 /// Pause a sequential session
@@ -235,6 +532,7 @@ pub fn common_get_session_progress(session: &SequentialSession) -> SequentialPro
 /// * `session` - Mutable reference to the sequential session
 pub fn common_pause_session(session: &mut SequentialSession) {
     session.status = SequentialStatus::Paused;
+    session.stats.pause_started();
 }
 
 /// Resume a sequential session
@@ -244,6 +542,7 @@ pub fn common_pause_session(session: &mut SequentialSession) {
 pub fn common_resume_session(session: &mut SequentialSession) {
     if matches!(session.status, SequentialStatus::Paused) {
         session.status = SequentialStatus::Running;
+        session.stats.pause_ended();
     }
 }
 
@@ -253,6 +552,47 @@ pub fn common_resume_session(session: &mut SequentialSession) {
 /// * `session` - Mutable reference to the sequential session
 pub fn common_stop_session(session: &mut SequentialSession) {
     session.status = SequentialStatus::Idle;
+    session.stats.pause_ended();
+}
+
+/// Put the failed entries of a sequential session back in the queue
+///
+/// Failed entries are moved, in their original order, right after the entries
+/// already translated, and `current_index` points at the first of them, so they
+/// are translated again before the entries not reached yet. Their errors are dropped.
+///
+/// # Arguments
+/// * `session` - Mutable reference to the sequential session, which must not be processing
+///
+/// # Returns
+/// * Number of entries requeued
+pub fn common_requeue_errors(session: &mut SequentialSession) -> usize {
+    let failed: std::collections::HashSet<i32> = session
+        .processed_entries
+        .iter()
+        .filter(|(_, success)| !**success)
+        .map(|(entry_id, _)| *entry_id)
+        .collect();
+    if failed.is_empty() {
+        return 0;
+    }
+
+    let committed = session.current_index.min(session.texts.len());
+    let not_reached = session.texts.split_off(committed);
+    let (failed_texts, done): (Vec<_>, Vec<_>) = session
+        .texts
+        .drain(..)
+        .partition(|text| failed.contains(&text.id));
+    let requeued = failed_texts.len();
+
+    session.current_index = done.len();
+    session.texts = done;
+    session.texts.extend(failed_texts);
+    session.texts.extend(not_reached);
+    session.processed_entries.retain(|entry_id, _| !failed.contains(entry_id));
+    session.errors.retain(|error| !failed.contains(&error.entry_id));
+
+    requeued
 }
 
 /// Get translation settings with defaults applied
@@ -287,3 +627,246 @@ where
     }
     settings
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(id: i32, source_text: &str, text_type: Option<&str>) -> TranslationText {
+        TranslationText {
+            id,
+            source_text: source_text.to_string(),
+            context: None,
+            text_type: text_type.map(str::to_string),
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_select_batch_packs_short_entries_of_same_type() {
+        let texts = vec![
+            text(1, "はい", Some("dialogue")),
+            text(2, "いいえ", Some("dialogue")),
+            text(3, "勇者", Some("dialogue")),
+            text(4, "ポーション", Some("item")),
+        ];
+
+        assert_eq!(common_select_batch(&texts, 0, 10), 3);
+        assert_eq!(common_select_batch(&texts, 0, 2), 2);
+        assert_eq!(common_select_batch(&texts, 3, 10), 1);
+        assert_eq!(common_select_batch(&texts, 4, 10), 0);
+    }
+
+    fn session(texts: Vec<TranslationText>) -> SequentialSession {
+        SequentialSession {
+            session_id: "seq_test".to_string(),
+            project_id: 1,
+            texts,
+            current_index: 0,
+            processed_entries: std::collections::HashMap::new(),
+            errors: Vec::new(),
+            successful_translations: Vec::new(),
+            status: SequentialStatus::Paused,
+            start_time: std::time::Instant::now(),
+            translation_settings: TranslationSettings {
+                source_language: None,
+                target_language: None,
+                model: None,
+            },
+            pause_settings: PauseSettings {
+                enabled: false,
+                batch_size: 150,
+                pause_duration_minutes: 5,
+            },
+            batch_counter: 0,
+            length_budgets: Vec::new(),
+            retranslate_over_budget: false,
+            prompt_batch_size: 1,
+            context_lines: 0,
+            translated_texts: std::collections::HashMap::new(),
+            parallel_requests: 1,
+            processing: false,
+            stats: Default::default(),
+            use_translation_memory: true,
+            duplicates: std::collections::HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_requeue_errors_moves_failed_entries_before_remaining_ones() {
+        let mut session = session(vec![
+            text(1, "はい", None),
+            text(2, "いいえ", None),
+            text(3, "勇者", None),
+            text(4, "魔王", None),
+            text(5, "宿屋", None),
+        ]);
+        session.current_index = 4;
+        session.processed_entries.extend([(1, false), (2, true), (3, false), (4, true)]);
+        for entry_id in [1, 3] {
+            session.errors.push(SequentialError {
+                entry_id,
+                error_message: "Translation failed: HTTP error 503".to_string(),
+                timestamp: 0,
+                kind: Default::default(),
+                attempts: 4,
+            });
+        }
+
+        assert_eq!(common_requeue_errors(&mut session), 2);
+
+        let order: Vec<i32> = session.texts.iter().map(|text| text.id).collect();
+        assert_eq!(order, vec![2, 4, 1, 3, 5]);
+        assert_eq!(session.current_index, 2);
+        assert!(session.errors.is_empty());
+        assert_eq!(session.processed_entries.len(), 2);
+
+        assert_eq!(common_requeue_errors(&mut session), 0);
+    }
+
+    #[test]
+    fn test_group_duplicates_keeps_first_entry_of_each_source_text() {
+        let mut texts = vec![
+            text(1, "はい", Some("system")),
+            text(2, "はい", Some("system")),
+            text(3, "……", None),
+            text(4, "はい ", Some("item")),
+            text(5, "はい", Some("dialogue")),
+            text(6, "……", None),
+            text(7, "いいえ", Some("system")),
+        ];
+
+        let duplicates = common_group_duplicates(&mut texts, 0, None);
+        let order: Vec<i32> = texts.iter().map(|text| text.id).collect();
+        assert_eq!(order, vec![1, 3, 5, 7]);
+        let grouped: Vec<i32> = duplicates[&1].iter().map(|text| text.id).collect();
+        assert_eq!(grouped, vec![2, 4]);
+        assert_eq!(duplicates[&3][0].id, 6);
+        assert_eq!(duplicates.len(), 2);
+
+        // Entries before the start are not part of the translation, nothing is excluded
+        let mut texts = vec![text(1, "はい", None), text(2, "はい", Some("dialogue")), text(3, "はい", None)];
+        let nothing_excluded: Vec<String> = Vec::new();
+        let duplicates = common_group_duplicates(&mut texts, 1, Some(nothing_excluded.as_slice()));
+        assert_eq!(texts.len(), 2);
+        assert_eq!(duplicates[&2][0].id, 3);
+    }
+
+    #[test]
+    fn test_fan_out_reports_every_grouped_entry() {
+        let mut texts = vec![text(1, "はい", None), text(2, "いいえ", None), text(3, "はい", None)];
+        let duplicates = common_group_duplicates(&mut texts, 0, None);
+        let mut succeeded = session(texts);
+        succeeded.duplicates = duplicates;
+
+        let translation = SuccessfulTranslation {
+            entry_id: 1,
+            translated_text: "Oui".to_string(),
+            model_used: "qwen".to_string(),
+            timestamp: 0,
+            processing_time_ms: 800,
+            over_budget: None,
+        };
+        common_fan_out_translation(&mut succeeded, &translation);
+        succeeded.processed_entries.insert(1, true);
+        succeeded.successful_translations.push(translation);
+
+        let reported: Vec<(i32, &str)> = succeeded
+            .successful_translations
+            .iter()
+            .map(|t| (t.entry_id, t.translated_text.as_str()))
+            .collect();
+        assert_eq!(reported, vec![(3, "Oui"), (1, "Oui")]);
+        assert_eq!(succeeded.processed_entries.get(&3), Some(&true));
+
+        let progress = common_get_session_progress(&succeeded);
+        assert_eq!((progress.processed_count, progress.total_count), (2, 3));
+
+        // A failure is reported for the grouped entries too, which are requeued with it
+        let mut failed = session(vec![text(1, "はい", None)]);
+        failed.duplicates.insert(1, vec![text(3, "はい", None)]);
+        failed.current_index = 1;
+        let error = SequentialError {
+            entry_id: 1,
+            error_message: "Translation failed: HTTP error 503".to_string(),
+            timestamp: 0,
+            kind: Default::default(),
+            attempts: 4,
+        };
+        common_fan_out_error(&mut failed, &error);
+        failed.processed_entries.insert(1, false);
+        failed.errors.push(error);
+        assert_eq!(failed.errors.len(), 2);
+
+        assert_eq!(common_requeue_errors(&mut failed), 1);
+        assert!(failed.errors.is_empty());
+        assert!(failed.processed_entries.is_empty());
+    }
+
+    #[test]
+    fn test_estimate_time_remaining_from_measured_timings() {
+        let mut session = session(vec![
+            text(1, "はい", None),
+            text(2, "勇者は城へ向かった。", None),
+            text(3, "いいえ", None),
+        ]);
+        assert_eq!(common_estimate_time_remaining(&session), None);
+
+        // 10 chars in 1 s: 100 ms per char, 2 entries (13 chars) left
+        session.stats.record(10, 1000, "qwen");
+        session.current_index = 1;
+        assert_eq!(common_estimate_time_remaining(&session), Some(2)); // 1.3 s + 2 x 500 ms
+
+        session.parallel_requests = 2;
+        assert_eq!(common_estimate_time_remaining(&session), Some(1)); // 0.65 s, no delay
+
+        // One pause of 5 minutes every 2 translations: the next one comes after entry 2
+        session.pause_settings = PauseSettings {
+            enabled: true,
+            batch_size: 2,
+            pause_duration_minutes: 5,
+        };
+        assert_eq!(common_estimate_time_remaining(&session), Some(301));
+    }
+
+    #[test]
+    fn test_dialogue_context_only_gives_source_lines_with_parallel_requests() {
+        let mut texts = vec![
+            text(1, "ねえ、聞いて。", Some("dialogue")),
+            text(2, "彼が来たの。", Some("dialogue")),
+        ];
+        for (index, text) in texts.iter_mut().enumerate() {
+            text.location = Some(format!("map:1:event:3:message:{}", index));
+        }
+        let mut session = session(texts);
+        session.context_lines = 3;
+        session.translated_texts.insert(1, "Dis, écoute.".to_string());
+
+        assert_eq!(
+            common_dialogue_context(&session, 1),
+            Some("Previous lines:\n- ねえ、聞いて。 => Dis, écoute.".to_string())
+        );
+
+        // Whether entry 1 is already committed depends on timing: leave its translation out
+        session.parallel_requests = 2;
+        assert_eq!(
+            common_dialogue_context(&session, 1),
+            Some("Previous lines:\n- ねえ、聞いて。".to_string())
+        );
+    }
+
+    #[test]
+    fn test_select_batch_keeps_long_entries_alone() {
+        let long_text = "あ".repeat(MAX_BATCH_ENTRY_LENGTH + 1);
+        let texts = vec![
+            text(1, "はい", None),
+            text(2, &long_text, None),
+            text(3, "いいえ", None),
+        ];
+
+        assert_eq!(common_select_batch(&texts, 0, 10), 1);
+        assert_eq!(common_select_batch(&texts, 1, 10), 1);
+        assert_eq!(common_select_batch(&texts, 2, 10), 1);
+        assert_eq!(common_select_batch(&texts, 0, 1), 1);
+    }
+}
@@ -1,9 +1,15 @@
 // Common translation utilities and shared logic
 // This module centralizes code that is duplicated between ollama and runpod providers
 
+pub mod context;
 pub mod functions;
+pub mod retry;
+pub mod stats;
 pub mod types;
 
+#[cfg(test)]
+pub mod test_server;
+
 // Re-export common types and functions for easy access
 pub use functions::*;
 pub use types::*;
@@ -0,0 +1,246 @@
+// Provider error classification and retry with exponential backoff
+// Providers report failures as plain strings; they are sorted here into kinds that
+// decide whether an entry is retried, recorded as failed, or pauses the whole session
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// HTTP status in the provider error formats: "HTTP error 502 Bad Gateway: ...", "HTTP 404 error", "HTTP error: 429 ..."
+static HTTP_STATUS_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"HTTP(?: error)?:? (\d{3})\b").unwrap());
+
+/// Kind of a provider failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationErrorKind {
+    /// Network failure, timeout or server error: retried
+    #[default]
+    Transient,
+    /// The provider asks to slow down (HTTP 429): retried with longer delays
+    RateLimited,
+    /// Wrong key, model or endpoint, exhausted quota: every entry would fail, the session is paused
+    FatalConfig,
+    /// The entry itself failed (unusable answer, lost placeholders, rejected text): recorded, not retried
+    BadOutput,
+}
+
+impl TranslationErrorKind {
+    /// Whether another attempt may succeed
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Transient | Self::RateLimited)
+    }
+}
+
+/// Classify a provider error message
+pub fn classify_translation_error(message: &str) -> TranslationErrorKind {
+    let lower = message.to_lowercase();
+
+    if let Some(status) = HTTP_STATUS_REGEX
+        .captures(message)
+        .and_then(|caps| caps[1].parse::<u16>().ok())
+    {
+        return match status {
+            429 => TranslationErrorKind::RateLimited,
+            // 456: DeepL character quota exceeded
+            401 | 402 | 403 | 404 | 456 => TranslationErrorKind::FatalConfig,
+            408 => TranslationErrorKind::Transient,
+            400..=499 => TranslationErrorKind::BadOutput,
+            _ => TranslationErrorKind::Transient,
+        };
+    }
+
+    if lower.contains("rate limit") || lower.contains("too many requests") {
+        TranslationErrorKind::RateLimited
+    } else if lower.contains("no model configured")
+        || lower.contains("api key")
+        || lower.contains("unauthorized")
+        || (lower.contains("model") && lower.contains("not found"))
+    {
+        TranslationErrorKind::FatalConfig
+    } else if lower.contains("failed to parse")
+        || lower.contains("empty translation")
+        || lower.contains("placeholder")
+        || lower.contains("cannot be empty")
+    {
+        TranslationErrorKind::BadOutput
+    } else {
+        // Connection errors, timeouts and anything unknown get another chance
+        TranslationErrorKind::Transient
+    }
+}
+
+/// How often and how long to wait before retrying a failed request
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, the first one included
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled at each new attempt
+    pub base_delay: Duration,
+    /// Upper bound of a single delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay to wait after `attempt` failed attempts, None when no retry is due
+    ///
+    /// The delay doubles at each attempt (four times longer when rate-limited) and
+    /// is randomized between half and all of it, so parallel requests do not retry in lockstep.
+    pub fn delay(&self, kind: TranslationErrorKind, attempt: u32) -> Option<Duration> {
+        if !kind.is_retryable() || attempt >= self.max_attempts {
+            return None;
+        }
+
+        let factor = if kind == TranslationErrorKind::RateLimited { 4 } else { 1 };
+        let exponential = self
+            .base_delay
+            .saturating_mul(factor)
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+
+        Some(exponential.mul_f64(0.5 + jitter_fraction() / 2.0))
+    }
+}
+
+/// Random fraction in [0, 1), without pulling a random number crate
+fn jitter_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Run a provider request, retrying transient and rate-limited failures
+///
+/// # Returns
+/// * The last result, and the number of attempts made
+pub async fn with_retry<T, F, Fut>(policy: &RetryPolicy, mut operation: F) -> (Result<T, String>, u32)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let error = match operation().await {
+            Ok(value) => return (Ok(value), attempt),
+            Err(error) => error,
+        };
+
+        let kind = classify_translation_error(&error);
+        let Some(delay) = policy.delay(kind, attempt) else {
+            return (Err(error), attempt);
+        };
+
+        println!(
+            "🔁 [Retry] Attempt {} failed ({:?}), retrying in {} ms: {}",
+            attempt,
+            kind,
+            delay.as_millis(),
+            error
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_classify_provider_errors() {
+        use TranslationErrorKind::*;
+
+        assert_eq!(classify_translation_error("HTTP error 429 Too Many Requests: slow down"), RateLimited);
+        assert_eq!(classify_translation_error("HTTP error: 429 Too Many Requests"), RateLimited);
+        assert_eq!(classify_translation_error("Ollama API error: rate limit exceeded"), RateLimited);
+
+        assert_eq!(classify_translation_error("HTTP error 401 Unauthorized: invalid key"), FatalConfig);
+        assert_eq!(classify_translation_error("HTTP 404 Not Found error"), FatalConfig);
+        assert_eq!(classify_translation_error("HTTP error 456 <unknown status code>: Quota exceeded"), FatalConfig);
+        assert_eq!(classify_translation_error("No model configured for OpenAI-compatible provider"), FatalConfig);
+        assert_eq!(classify_translation_error("Ollama API error: model 'qwen' not found"), FatalConfig);
+
+        assert_eq!(classify_translation_error("HTTP error 400 Bad Request: ja is not supported"), BadOutput);
+        assert_eq!(classify_translation_error("Failed to parse response: EOF"), BadOutput);
+        assert_eq!(classify_translation_error("Empty translation response"), BadOutput);
+        assert_eq!(classify_translation_error("Unknown placeholder marker in translation: {9}"), BadOutput);
+
+        assert_eq!(classify_translation_error("HTTP error 502 Bad Gateway: upstream"), Transient);
+        assert_eq!(classify_translation_error("HTTP request failed: connection refused"), Transient);
+        assert_eq!(classify_translation_error("Timeout waiting for glossary-lookup-response"), Transient);
+    }
+
+    #[test]
+    fn test_retry_delay_grows_and_stops() {
+        let policy = RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+        };
+
+        for (attempt, full) in [(1, 100), (2, 200), (3, 400)] {
+            let delay = policy.delay(TranslationErrorKind::Transient, attempt).unwrap();
+            assert!(delay >= Duration::from_millis(full / 2) && delay <= Duration::from_millis(full));
+        }
+        let delay = policy.delay(TranslationErrorKind::RateLimited, 3).unwrap();
+        assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_millis(1000));
+
+        assert_eq!(policy.delay(TranslationErrorKind::Transient, 4), None);
+        assert_eq!(policy.delay(TranslationErrorKind::FatalConfig, 1), None);
+        assert_eq!(policy.delay(TranslationErrorKind::BadOutput, 1), None);
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_retries_transient_errors_only() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+        };
+
+        let calls = AtomicU32::new(0);
+        let (result, attempts) = with_retry(&policy, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err("HTTP error 503 Service Unavailable: busy".to_string()),
+                _ => Ok("Bonjour"),
+            }
+        })
+        .await;
+        assert_eq!(result, Ok("Bonjour"));
+        assert_eq!(attempts, 2);
+
+        let (result, attempts) = with_retry(&policy, || async {
+            Err::<(), _>("HTTP request failed: connection refused".to_string())
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+
+        let (result, attempts) = with_retry(&policy, || async {
+            Err::<(), _>("HTTP error 401 Unauthorized: bad key".to_string())
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
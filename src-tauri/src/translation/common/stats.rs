@@ -0,0 +1,210 @@
+// Timing statistics of a sequential session
+// Measured processing times feed the remaining time estimate, the throughput
+// and the latency percentiles reported with the session progress
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Number of recent translations the time per character is averaged over
+pub const ETA_WINDOW: usize = 50;
+
+/// Latencies kept per model for the percentiles
+const LATENCY_SAMPLES_PER_MODEL: usize = 500;
+
+/// Translation rate over the time the session actually ran (pauses excluded)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputStats {
+    pub entries_per_minute: f64,
+    pub chars_per_minute: f64,
+}
+
+/// Latency percentiles of the translations made with one model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelLatency {
+    pub model: String,
+    pub samples: usize,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+}
+
+/// Timings measured during a sequential session
+#[derive(Debug, Clone, Default)]
+pub struct SessionStats {
+    /// (source characters, processing time in ms) of the last translations
+    recent: VecDeque<(usize, u64)>,
+    latencies: HashMap<String, VecDeque<u64>>,
+    translated_entries: usize,
+    translated_chars: usize,
+    paused_since: Option<Instant>,
+    paused_total: Duration,
+}
+
+impl SessionStats {
+    /// Record a successful translation of `source_chars` characters
+    pub fn record(&mut self, source_chars: usize, processing_time_ms: u64, model: &str) {
+        let chars = source_chars.max(1);
+
+        self.recent.push_back((chars, processing_time_ms));
+        if self.recent.len() > ETA_WINDOW {
+            self.recent.pop_front();
+        }
+
+        let latencies = self.latencies.entry(model.to_string()).or_default();
+        latencies.push_back(processing_time_ms);
+        if latencies.len() > LATENCY_SAMPLES_PER_MODEL {
+            latencies.pop_front();
+        }
+
+        self.translated_entries += 1;
+        self.translated_chars += chars;
+    }
+
+    /// Number of successful translations recorded
+    pub fn translated_entries(&self) -> usize {
+        self.translated_entries
+    }
+
+    /// Moving average of the processing time per source character
+    /// Averaging over characters weights each translation by its source length
+    pub fn ms_per_char(&self) -> Option<f64> {
+        let (chars, ms) = self
+            .recent
+            .iter()
+            .fold((0usize, 0u64), |(chars, ms), (c, t)| (chars + c, ms + t));
+        (chars > 0).then(|| ms as f64 / chars as f64)
+    }
+
+    /// The session was paused
+    pub fn pause_started(&mut self) {
+        self.paused_since.get_or_insert_with(Instant::now);
+    }
+
+    /// The session was resumed (or stopped)
+    pub fn pause_ended(&mut self) {
+        if let Some(since) = self.paused_since.take() {
+            self.paused_total += since.elapsed();
+        }
+    }
+
+    /// How long the current pause has lasted, None when not paused
+    pub fn current_pause(&self) -> Option<Duration> {
+        self.paused_since.map(|since| since.elapsed())
+    }
+
+    /// Time the session spent running since `start_time`, pauses excluded
+    pub fn active_time(&self, start_time: Instant) -> Duration {
+        start_time
+            .elapsed()
+            .saturating_sub(self.paused_total)
+            .saturating_sub(self.current_pause().unwrap_or_default())
+    }
+
+    /// Entries and characters translated per minute of `active_time`
+    pub fn throughput(&self, active_time: Duration) -> Option<ThroughputStats> {
+        let minutes = active_time.as_secs_f64() / 60.0;
+        if self.translated_entries == 0 || minutes <= 0.0 {
+            return None;
+        }
+        Some(ThroughputStats {
+            entries_per_minute: self.translated_entries as f64 / minutes,
+            chars_per_minute: self.translated_chars as f64 / minutes,
+        })
+    }
+
+    /// p50/p95 latency of each model used, sorted by model name
+    pub fn latency_by_model(&self) -> Vec<ModelLatency> {
+        let mut latencies: Vec<ModelLatency> = self
+            .latencies
+            .iter()
+            .filter(|(_, samples)| !samples.is_empty())
+            .map(|(model, samples)| {
+                let mut sorted: Vec<u64> = samples.iter().copied().collect();
+                sorted.sort_unstable();
+                ModelLatency {
+                    model: model.clone(),
+                    samples: sorted.len(),
+                    p50_ms: percentile(&sorted, 50.0),
+                    p95_ms: percentile(&sorted, 95.0),
+                }
+            })
+            .collect();
+        latencies.sort_by(|a, b| a.model.cmp(&b.model));
+        latencies
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ms_per_char_is_weighted_by_source_length() {
+        let mut stats = SessionStats::default();
+        assert_eq!(stats.ms_per_char(), None);
+
+        stats.record(2, 400, "qwen");
+        stats.record(13, 2600, "qwen");
+        assert_eq!(stats.ms_per_char(), Some(3000.0 / 15.0));
+
+        for _ in 0..ETA_WINDOW {
+            stats.record(3, 30, "qwen");
+        }
+        assert_eq!(stats.ms_per_char(), Some(10.0));
+        assert_eq!(stats.translated_entries(), ETA_WINDOW + 2);
+    }
+
+    #[test]
+    fn test_latency_percentiles_per_model() {
+        let mut stats = SessionStats::default();
+        for ms in 1..=100 {
+            stats.record(2, ms * 10, "qwen");
+        }
+        stats.record(2, 700, "gpt-4o-mini");
+
+        let latencies = stats.latency_by_model();
+        assert_eq!(
+            latencies,
+            vec![
+                ModelLatency { model: "gpt-4o-mini".to_string(), samples: 1, p50_ms: 700, p95_ms: 700 },
+                ModelLatency { model: "qwen".to_string(), samples: 100, p50_ms: 500, p95_ms: 950 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_throughput_over_active_time() {
+        let mut stats = SessionStats::default();
+        assert_eq!(stats.throughput(Duration::from_secs(60)), None);
+
+        stats.record(2, 500, "qwen");
+        stats.record(3, 500, "qwen");
+        let throughput = stats.throughput(Duration::from_secs(30)).unwrap();
+        assert_eq!(throughput.entries_per_minute, 4.0);
+        assert_eq!(throughput.chars_per_minute, 10.0);
+    }
+
+    #[test]
+    fn test_pauses_are_excluded_from_active_time() {
+        let mut stats = SessionStats::default();
+        let start_time = Instant::now() - Duration::from_secs(100);
+
+        stats.paused_total = Duration::from_secs(40);
+        let active = stats.active_time(start_time);
+        assert!(active >= Duration::from_secs(60) && active < Duration::from_secs(61));
+
+        stats.pause_started();
+        assert!(stats.current_pause().is_some());
+        stats.pause_ended();
+        assert!(stats.current_pause().is_none());
+    }
+}
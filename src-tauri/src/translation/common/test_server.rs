@@ -0,0 +1,71 @@
+// Minimal HTTP stub server for provider client tests
+// Serves a single canned response and captures the request it received
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Request captured by the stub server
+pub struct CapturedRequest {
+    /// e.g. "POST /v1/chat/completions HTTP/1.1"
+    pub request_line: String,
+    /// Header lines, lowercased for easy matching
+    pub headers: String,
+    pub body: String,
+}
+
+/// Serve one HTTP request on a local port with a canned JSON response
+/// Returns the server root URL (http://127.0.0.1:port) and a handle to the captured request
+pub async fn serve_once(
+    status: &'static str,
+    response_body: &'static str,
+) -> (String, tokio::task::JoinHandle<CapturedRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let root_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let (head, body) = loop {
+            let read = socket.read(&mut chunk).await.unwrap();
+            buffer.extend_from_slice(&chunk[..read]);
+            let text = String::from_utf8_lossy(&buffer).to_string();
+            if let Some(split) = text.find("\r\n\r\n") {
+                let head = text[..split].to_string();
+                let content_length = head
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())
+                            .flatten()
+                    })
+                    .unwrap_or(0);
+                if buffer.len() >= split + 4 + content_length {
+                    break (head, text[split + 4..].to_string());
+                }
+            }
+            if read == 0 {
+                panic!("connection closed before the request was complete");
+            }
+        };
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            response_body.len(),
+            response_body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        socket.shutdown().await.unwrap();
+
+        let (request_line, headers) = head.split_once("\r\n").unwrap_or((&head, ""));
+        CapturedRequest {
+            request_line: request_line.to_string(),
+            headers: headers.to_lowercase(),
+            body,
+        }
+    });
+
+    (root_url, handle)
+}
@@ -27,6 +27,9 @@
 //! - `*Progress`: Progress tracking structures
 //! - `*Error`: Error reporting structures
 
+use crate::parsers::text::length_budget::{BudgetViolation, LengthBudget};
+use crate::translation::common::retry::TranslationErrorKind;
+use crate::translation::common::stats::{ModelLatency, SessionStats, ThroughputStats};
 use serde::{Deserialize, Serialize};
 
 /// Pause settings for sequential translation operations
@@ -56,10 +59,11 @@ pub struct PauseSettings {
 /// * `source_text` - The text to be translated (required)
 /// * `source_language` - Source language code (e.g., "ja", "en") - defaults to provider default
 /// * `target_language` - Target language code (e.g., "fr", "es") - defaults to provider default
-/// * `context` - Additional context for better translation quality
+/// * `context` - Surrounding dialogue (previous lines, speaker) shown to the model but not translated
 /// * `model` - Specific model to use - uses provider default if None
 /// * `project_id` - Project ID for glossary lookup (None = global only, Some(id) = global + project-specific)
 /// * `text_type` - Text category for glossary filtering ('dialogue', 'system', 'item', 'skill', 'other')
+/// * `max_length` - Maximum display length asked to the model (None = no constraint)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SingleTranslationRequest {
     pub source_text: String,
@@ -69,6 +73,8 @@ pub struct SingleTranslationRequest {
     pub model: Option<String>,
     pub project_id: Option<i64>,
     pub text_type: Option<String>,
+    #[serde(default)]
+    pub max_length: Option<u32>,
 }
 
 /// Single translation result - common structure
@@ -80,12 +86,50 @@ pub struct SingleTranslationResult {
     pub processing_time_ms: u64,
 }
 
+/// Request structure for multi-entry (batch) prompt translation
+///
+/// Several short entries are packed into one numbered prompt to cut the
+/// per-request overhead of LLM providers. All entries share the same
+/// languages, model and glossary category.
+///
+/// # Fields
+/// * `source_texts` - Texts to translate, in prompt order
+/// * `source_language` - Source language code - defaults to provider default
+/// * `target_language` - Target language code - defaults to provider default
+/// * `model` - Specific model to use - uses provider default if None
+/// * `project_id` - Project ID for glossary lookup
+/// * `text_type` - Text category shared by all entries, for glossary filtering
+/// * `context` - Dialogue preceding the first entry, rendered as non-translated context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTranslationRequest {
+    pub source_texts: Vec<String>,
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    pub model: Option<String>,
+    pub project_id: Option<i64>,
+    pub text_type: Option<String>,
+    #[serde(default)]
+    pub context: Option<String>,
+}
+
+/// Batch translation result - common structure
+///
+/// `translations` is aligned with the request's `source_texts`: an entry is
+/// None when the model skipped it or its answer could not be trusted, and
+/// must be translated again on its own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTranslationResult {
+    pub translations: Vec<Option<String>>,
+    pub model_used: String,
+    pub processing_time_ms: u64,
+}
+
 /// Translation suggestion - common structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslationSuggestion {
     pub suggestion: String,
     pub confidence: f32,
-    pub source: String, // "ollama", "runpod", "glossary", "similar"
+    pub source: String, // "ollama", "runpod", "glossary", "similar", "memory"
 }
 
 /// Translation text with metadata - common structure
@@ -96,6 +140,8 @@ pub struct TranslationText {
     pub source_text: String,
     pub context: Option<String>,
     pub text_type: Option<String>, // Text type for category filtering: 'dialogue', 'system', 'item', 'skill', 'other'
+    #[serde(default)]
+    pub location: Option<String>, // Structured location ("item:1:name") used for length budgets
 }
 
 /// Request structure for batch/sequential translation operations
@@ -112,6 +158,14 @@ pub struct TranslationText {
 /// * `target_language` - Override project's default target language
 /// * `model` - Override default model for this batch
 /// * `pause_settings` - Optional pause configuration for batch processing
+/// * `length_budgets` - Length budgets checked on each translation (by text location)
+/// * `retranslate_over_budget` - Ask once for a shorter translation when over budget
+/// * `prompt_batch_size` - Number of short entries packed into one prompt (None or 1 = one prompt per entry)
+/// * `context_lines` - Previous lines of the same event page given as context (None or 0 = no context)
+/// * `parallel_requests` - Requests in flight at once (None or 1 = one at a time, above 1 the context has source lines only)
+/// * `use_translation_memory` - Reuse the translations remembered for identical texts (None = true)
+/// * `deduplicate` - Translate entries with the same source text once (None = true)
+/// * `deduplicate_excluded_types` - Text types always translated entry by entry (None = dialogue)
 ///
 /// # Example
 /// ```json
@@ -139,6 +193,22 @@ pub struct SequentialTranslationRequest {
     pub target_language: Option<String>,
     pub model: Option<String>,
     pub pause_settings: Option<PauseSettings>,
+    #[serde(default)]
+    pub length_budgets: Option<Vec<LengthBudget>>,
+    #[serde(default)]
+    pub retranslate_over_budget: Option<bool>,
+    #[serde(default)]
+    pub prompt_batch_size: Option<u32>,
+    #[serde(default)]
+    pub context_lines: Option<u32>,
+    #[serde(default)]
+    pub parallel_requests: Option<u32>,
+    #[serde(default)]
+    pub use_translation_memory: Option<bool>,
+    #[serde(default)]
+    pub deduplicate: Option<bool>,
+    #[serde(default)]
+    pub deduplicate_excluded_types: Option<Vec<String>>,
 }
 
 /// Sequential status enum - common structure
@@ -163,6 +233,8 @@ pub struct SequentialProgress {
     pub errors: Vec<SequentialError>,
     pub successful_translations: Vec<SuccessfulTranslation>,
     pub pause_time_remaining: Option<i64>, // seconds remaining in current pause
+    pub throughput: Option<ThroughputStats>, // measured over running time, pauses excluded
+    pub latency_by_model: Vec<ModelLatency>,
 }
 
 /// Sequential error - common structure
@@ -171,6 +243,10 @@ pub struct SequentialError {
     pub entry_id: i32,
     pub error_message: String,
     pub timestamp: i64,
+    #[serde(default)]
+    pub kind: TranslationErrorKind,
+    #[serde(default)]
+    pub attempts: u32, // Requêtes envoyées avant d'abandonner l'entrée
 }
 
 /// Successful translation - common structure
@@ -181,6 +257,8 @@ pub struct SuccessfulTranslation {
     pub model_used: String,
     pub timestamp: i64,
     pub processing_time_ms: u64,
+    /// Set when the translation still exceeds the length budget of its field
+    pub over_budget: Option<BudgetViolation>,
 }
 
 /// Sequential session data structure - common fields
@@ -202,6 +280,26 @@ pub struct SequentialSession {
     pub translation_settings: TranslationSettings,
     pub pause_settings: PauseSettings, // Configuration des pauses
     pub batch_counter: usize,           // Compteur interne pour les pauses
+    pub length_budgets: Vec<LengthBudget>,
+    pub retranslate_over_budget: bool,
+    pub prompt_batch_size: usize, // Entrées envoyées dans un même prompt (1 = pas de batch)
+    pub context_lines: usize,      // Lignes précédentes données en contexte (0 = pas de contexte)
+    pub translated_texts: std::collections::HashMap<i32, String>, // entry_id -> traduction, pour le contexte
+    pub parallel_requests: usize,  // Requêtes envoyées en parallèle (1 = une à la fois)
+    pub processing: bool,          // Une boucle de traitement est active pour cette session
+    pub stats: SessionStats,       // Temps mesurés, pour l'estimation du temps restant
+    pub use_translation_memory: bool, // Traductions mémorisées réutilisées sans appeler le modèle
+    pub duplicates: std::collections::HashMap<i32, Vec<TranslationText>>, // entry_id traduit -> entrées au texte identique qui reçoivent sa traduction
+}
+
+/// Outcome of one entry translated by a sequential worker
+/// Kept until every earlier entry is done, so results are committed in order
+#[derive(Debug, Clone)]
+pub struct EntryOutcome {
+    pub text: TranslationText,
+    pub request: SingleTranslationRequest,
+    pub result: Result<SingleTranslationResult, String>,
+    pub attempts: u32,
 }
 
 /// Translation settings for a session
@@ -0,0 +1,401 @@
+// DeepL HTTP client using reqwest
+// Speaks the DeepL v2 REST API: POST /v2/translate, GET /v2/languages, GET /v2/usage
+// Placeholders are sent as XML tags (tag_handling=xml) so DeepL leaves them alone
+
+use crate::translation::common::functions::TranslationClient;
+use crate::translation::machine::{protect_placeholders, restore_placeholders, MachineTranslationClient};
+use crate::translation::ollama::common::{DEFAULT_SOURCE_LANGUAGE, DEFAULT_TARGET_LANGUAGE};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// API root for free keys (ending in ":fx")
+pub const DEEPL_FREE_URL: &str = "https://api-free.deepl.com";
+/// API root for Pro keys
+pub const DEEPL_PRO_URL: &str = "https://api.deepl.com";
+
+/// Placeholder tag as DeepL returns it; it may expand self-closing tags
+static PLACEHOLDER_TAG_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<x\s+id="(\d+)"\s*(?:/>|>\s*</x>)"#).unwrap());
+
+/// DeepL client configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeepLConfig {
+    pub api_key: String,
+    /// Overrides the API root picked from the key type (proxies, tests)
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+/// DeepL HTTP client wrapper
+#[derive(Clone)]
+pub struct DeepLClient {
+    client: Client,
+    config: DeepLConfig,
+    base_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TranslateRequest<'a> {
+    text: Vec<String>,
+    source_lang: String,
+    target_lang: String,
+    tag_handling: &'a str,
+    ignore_tags: Vec<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranslateResponse {
+    translations: Vec<TranslationEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranslationEntry {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageEntry {
+    language: String,
+}
+
+/// Map one of our ISO 639-1 codes to a DeepL language code
+/// Targets need a regional variant for English and Portuguese, sources must not have one
+pub fn deepl_language_code(iso_code: &str, is_target: bool) -> String {
+    let normalized = iso_code.trim().replace('_', "-").to_lowercase();
+    let (primary, region) = normalized.split_once('-').unwrap_or((&normalized, ""));
+
+    if !is_target {
+        return primary.to_uppercase();
+    }
+
+    match (primary, region) {
+        ("en", "gb") | ("en", "uk") => "EN-GB".to_string(),
+        ("en", _) => "EN-US".to_string(),
+        ("pt", "br") => "PT-BR".to_string(),
+        ("pt", _) => "PT-PT".to_string(),
+        ("zh", "tw") | ("zh", "hk") | ("zh", "hant") => "ZH-HANT".to_string(),
+        ("zh", _) => "ZH-HANS".to_string(),
+        _ => primary.to_uppercase(),
+    }
+}
+
+/// Escape text for DeepL XML tag handling
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Undo XML escaping on DeepL output
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+impl DeepLClient {
+    /// Create new DeepL client
+    /// Free keys (":fx" suffix) go to api-free.deepl.com unless a base URL is configured
+    pub fn new(config: DeepLConfig) -> Self {
+        let base_url = match config.base_url.as_deref().filter(|url| !url.trim().is_empty()) {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None if config.api_key.ends_with(":fx") => DEEPL_FREE_URL.to_string(),
+            None => DEEPL_PRO_URL.to_string(),
+        };
+
+        Self {
+            client: Client::new(),
+            config,
+            base_url,
+        }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &DeepLConfig {
+        &self.config
+    }
+
+    /// Get base URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        request.header("Authorization", format!("DeepL-Auth-Key {}", self.config.api_key))
+    }
+
+    /// Read a non-success response into an error message
+    async fn http_error(response: reqwest::Response) -> String {
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unable to read error body".to_string());
+        format!("HTTP error {}: {}", status, body)
+    }
+
+    /// Translate text, protecting placeholders as XML tags
+    /// POST /v2/translate
+    pub async fn translate(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, String> {
+        let url = format!("{}/v2/translate", self.base_url);
+
+        // Escape first so placeholders are captured in their escaped form,
+        // then a single unescape at the end restores both text and placeholders
+        let protected = protect_placeholders(&escape_xml(text), |i| format!("<x id=\"{}\"/>", i));
+
+        let request = TranslateRequest {
+            text: vec![protected.text],
+            source_lang: deepl_language_code(source_language, false),
+            target_lang: deepl_language_code(target_language, true),
+            tag_handling: "xml",
+            ignore_tags: vec!["x"],
+        };
+
+        let response = self
+            .authorize(self.client.post(&url))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(Self::http_error(response).await);
+        }
+
+        let parsed: TranslateResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        let translated = parsed
+            .translations
+            .into_iter()
+            .next()
+            .map(|entry| entry.text)
+            .ok_or_else(|| "Response contained no translations".to_string())?;
+
+        let restored = restore_placeholders(&translated, &PLACEHOLDER_TAG_REGEX, &protected.placeholders)?;
+        Ok(unescape_xml(&restored))
+    }
+
+    /// List supported target languages
+    /// GET /v2/languages?type=target
+    pub async fn target_languages(&self) -> Result<Vec<String>, String> {
+        let url = format!("{}/v2/languages", self.base_url);
+
+        let response = self
+            .authorize(self.client.get(&url))
+            .query(&[("type", "target")])
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(Self::http_error(response).await);
+        }
+
+        let languages: Vec<LanguageEntry> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        Ok(languages.into_iter().map(|entry| entry.language).collect())
+    }
+
+    /// Check the key against the usage endpoint
+    /// GET /v2/usage
+    pub async fn check_usage(&self) -> Result<(), String> {
+        let url = format!("{}/v2/usage", self.base_url);
+
+        let response = self
+            .authorize(self.client.get(&url))
+            .send()
+            .await
+            .map_err(|e| format!("Connection failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(Self::http_error(response).await);
+        }
+        Ok(())
+    }
+}
+
+/// Check DeepL availability and get supported target languages
+/// Returns a JSON value with availability status and available languages
+pub async fn check_deepl_status(config: DeepLConfig) -> Result<serde_json::Value, String> {
+    use tokio::time::{timeout, Duration};
+
+    if config.api_key.trim().is_empty() {
+        return Ok(serde_json::json!({
+            "available": false,
+            "error": "API key cannot be empty"
+        }));
+    }
+
+    let client = DeepLClient::new(config);
+
+    match timeout(Duration::from_secs(5), client.target_languages()).await {
+        Ok(Ok(languages)) => Ok(serde_json::json!({
+            "available": true,
+            "languages_available": languages
+        })),
+        Ok(Err(e)) => Ok(serde_json::json!({
+            "available": false,
+            "error": format!("Failed to list languages: {}", e)
+        })),
+        Err(_) => Ok(serde_json::json!({
+            "available": false,
+            "error": "Connection timeout: DeepL is not responding"
+        })),
+    }
+}
+
+impl MachineTranslationClient for DeepLClient {
+    fn provider_name(&self) -> &'static str {
+        "deepl"
+    }
+
+    fn translate_text(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<String, String>> + Send + '_>> {
+        let text = text.to_string();
+        let source_language = source_language.to_string();
+        let target_language = target_language.to_string();
+        Box::pin(async move { self.translate(&text, &source_language, &target_language).await })
+    }
+}
+
+/// DeepL has no prompt or model: `call_api` translates the given text between
+/// the default languages, and `list_models` reports the supported target languages
+impl TranslationClient for DeepLClient {
+    fn call_api(&self, prompt: &str, _model: Option<String>) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<String, String>> + Send + '_>> {
+        self.translate_text(prompt, DEFAULT_SOURCE_LANGUAGE, DEFAULT_TARGET_LANGUAGE)
+    }
+
+    fn list_models(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<String>, String>> + Send + '_>> {
+        Box::pin(self.target_languages())
+    }
+
+    fn test_connection(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), String>> + Send + '_>> {
+        Box::pin(self.check_usage())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translation::common::test_server::serve_once;
+
+    fn config(base_url: String) -> DeepLConfig {
+        DeepLConfig {
+            api_key: "deepl-key:fx".to_string(),
+            base_url: Some(base_url),
+        }
+    }
+
+    #[test]
+    fn test_deepl_language_codes() {
+        assert_eq!(deepl_language_code("ja", false), "JA");
+        assert_eq!(deepl_language_code("en", false), "EN");
+        assert_eq!(deepl_language_code("fr", true), "FR");
+        assert_eq!(deepl_language_code("en", true), "EN-US");
+        assert_eq!(deepl_language_code("en-GB", true), "EN-GB");
+        assert_eq!(deepl_language_code("pt", true), "PT-PT");
+        assert_eq!(deepl_language_code("pt_BR", true), "PT-BR");
+        assert_eq!(deepl_language_code("zh", true), "ZH-HANS");
+        assert_eq!(deepl_language_code("zh-TW", true), "ZH-HANT");
+        assert_eq!(deepl_language_code("zh-TW", false), "ZH");
+    }
+
+    #[test]
+    fn test_base_url_follows_key_type() {
+        let free = DeepLClient::new(DeepLConfig { api_key: "abc:fx".to_string(), base_url: None });
+        assert_eq!(free.base_url(), DEEPL_FREE_URL);
+
+        let pro = DeepLClient::new(DeepLConfig { api_key: "abc".to_string(), base_url: None });
+        assert_eq!(pro.base_url(), DEEPL_PRO_URL);
+    }
+
+    #[tokio::test]
+    async fn test_translate_sends_placeholders_as_xml_tags() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            r#"{"translations":[{"detected_source_language":"JA","text":"<x id=\"0\"/>Le héros<x id=\"1\"></x> utilise &lt;<x id=\"2\"/>&gt; &amp; fuit !"}]}"#,
+        )
+        .await;
+        let client = DeepLClient::new(config(base_url));
+
+        let result = client
+            .translate("[COLOR_1]勇者[COLOR_0]は<[CONDITIONAL_v1>2]>&逃げた！", "ja", "fr")
+            .await
+            .unwrap();
+        assert_eq!(result, "[COLOR_1]Le héros[COLOR_0] utilise <[CONDITIONAL_v1>2]> & fuit !");
+
+        let request = server.await.unwrap();
+        assert_eq!(request.request_line, "POST /v2/translate HTTP/1.1");
+        assert!(request.headers.contains("authorization: deepl-auth-key deepl-key:fx"));
+
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(
+            body["text"][0],
+            "<x id=\"0\"/>勇者<x id=\"1\"/>は&lt;<x id=\"2\"/>&gt;&amp;逃げた！"
+        );
+        assert_eq!(body["source_lang"], "JA");
+        assert_eq!(body["target_lang"], "FR");
+        assert_eq!(body["tag_handling"], "xml");
+    }
+
+    #[tokio::test]
+    async fn test_translate_rejects_lost_placeholders() {
+        let (base_url, _server) = serve_once(
+            "200 OK",
+            r#"{"translations":[{"text":"Le héros"}]}"#,
+        )
+        .await;
+        let client = DeepLClient::new(config(base_url));
+
+        let error = client.translate("[NAME_1]勇者", "ja", "fr").await.unwrap_err();
+        assert!(error.contains("[NAME_1]"));
+    }
+
+    #[tokio::test]
+    async fn test_translate_reports_http_errors() {
+        let (base_url, _server) = serve_once(
+            "456 Quota Exceeded",
+            r#"{"message":"Quota exceeded"}"#,
+        )
+        .await;
+        let client = DeepLClient::new(config(base_url));
+
+        let error = client.translate("勇者", "ja", "fr").await.unwrap_err();
+        assert!(error.contains("456"));
+        assert!(error.contains("Quota exceeded"));
+    }
+
+    #[tokio::test]
+    async fn test_list_models_returns_target_languages() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            r#"[{"language":"EN-US","name":"English (American)"},{"language":"FR","name":"French"}]"#,
+        )
+        .await;
+        let client = DeepLClient::new(config(base_url));
+
+        let languages = client.list_models().await.unwrap();
+        assert_eq!(languages, vec!["EN-US".to_string(), "FR".to_string()]);
+
+        let request = server.await.unwrap();
+        assert_eq!(request.request_line, "GET /v2/languages?type=target HTTP/1.1");
+    }
+}
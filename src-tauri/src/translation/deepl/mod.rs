@@ -0,0 +1,14 @@
+// DeepL integration module exports
+// This module handles the DeepL v2 REST API (machine translation, no LLM)
+
+pub mod client;
+
+use crate::translation::machine;
+
+// Re-export main types
+pub use client::{check_deepl_status, deepl_language_code, DeepLClient, DeepLConfig};
+
+/// Single translation manager for DeepL
+pub type SingleTranslationManager = machine::SingleTranslationManager<DeepLClient>;
+/// Sequential translation manager for DeepL
+pub type SequentialTranslationManager = machine::SequentialTranslationManager<DeepLClient>;
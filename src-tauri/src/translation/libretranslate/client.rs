@@ -0,0 +1,351 @@
+// LibreTranslate HTTP client using reqwest
+// Speaks the LibreTranslate API: POST /translate, GET /languages
+// Placeholders are swapped for numbered {N} markers, which Argos models copy through
+
+use crate::translation::common::functions::TranslationClient;
+use crate::translation::machine::{protect_placeholders, restore_placeholders, MachineTranslationClient};
+use crate::translation::ollama::common::{DEFAULT_SOURCE_LANGUAGE, DEFAULT_TARGET_LANGUAGE};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// Placeholder marker as LibreTranslate returns it; spacing inside the braces may change
+static PLACEHOLDER_MARKER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\s*(\d+)\s*\}").unwrap());
+
+/// LibreTranslate client configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibreTranslateConfig {
+    /// Instance root (e.g. http://localhost:5000)
+    pub base_url: String,
+    /// Required by public instances, optional when self-hosting
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+impl Default for LibreTranslateConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:5000".to_string(),
+            api_key: None,
+        }
+    }
+}
+
+/// LibreTranslate HTTP client wrapper
+#[derive(Clone)]
+pub struct LibreTranslateClient {
+    client: Client,
+    config: LibreTranslateConfig,
+    base_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TranslateRequest<'a> {
+    q: String,
+    source: String,
+    target: String,
+    format: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageEntry {
+    code: String,
+}
+
+/// Map one of our ISO 639-1 codes to a LibreTranslate language code
+/// LibreTranslate uses bare lowercase codes, plus "zt" and "pb" for the regional variants
+pub fn libretranslate_language_code(iso_code: &str) -> String {
+    let normalized = iso_code.trim().replace('_', "-").to_lowercase();
+    let (primary, region) = normalized.split_once('-').unwrap_or((&normalized, ""));
+
+    match (primary, region) {
+        ("zh", "tw") | ("zh", "hk") | ("zh", "hant") => "zt".to_string(),
+        ("pt", "br") => "pb".to_string(),
+        _ => primary.to_string(),
+    }
+}
+
+impl LibreTranslateClient {
+    /// Create new LibreTranslate client
+    pub fn new(config: LibreTranslateConfig) -> Self {
+        let base_url = config.base_url.trim_end_matches('/').to_string();
+
+        Self {
+            client: Client::new(),
+            config,
+            base_url,
+        }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &LibreTranslateConfig {
+        &self.config
+    }
+
+    /// Get base URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Read a non-success response into an error message
+    /// LibreTranslate reports failures as {"error": "..."}
+    async fn http_error(response: reqwest::Response) -> String {
+        #[derive(Deserialize)]
+        struct ErrorResponse {
+            error: String,
+        }
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unable to read error body".to_string());
+        let message = serde_json::from_str::<ErrorResponse>(&body)
+            .map(|e| e.error)
+            .unwrap_or(body);
+        format!("HTTP error {}: {}", status, message)
+    }
+
+    /// Translate text, protecting placeholders as numbered markers
+    /// POST /translate
+    pub async fn translate(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, String> {
+        let url = format!("{}/translate", self.base_url);
+
+        let protected = protect_placeholders(text, |i| format!("{{{}}}", i));
+
+        let request = TranslateRequest {
+            q: protected.text,
+            source: libretranslate_language_code(source_language),
+            target: libretranslate_language_code(target_language),
+            format: "text",
+            api_key: self.config.api_key.clone().filter(|key| !key.is_empty()),
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(Self::http_error(response).await);
+        }
+
+        let parsed: TranslateResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        restore_placeholders(
+            &parsed.translated_text,
+            &PLACEHOLDER_MARKER_REGEX,
+            &protected.placeholders,
+        )
+    }
+
+    /// List supported languages
+    /// GET /languages
+    pub async fn languages(&self) -> Result<Vec<String>, String> {
+        let url = format!("{}/languages", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(Self::http_error(response).await);
+        }
+
+        let languages: Vec<LanguageEntry> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        Ok(languages.into_iter().map(|entry| entry.code).collect())
+    }
+}
+
+/// Check LibreTranslate availability and get supported languages
+/// Returns a JSON value with availability status and available languages
+pub async fn check_libretranslate_status(
+    config: LibreTranslateConfig,
+) -> Result<serde_json::Value, String> {
+    use tokio::time::{timeout, Duration};
+
+    if config.base_url.trim().is_empty() {
+        return Ok(serde_json::json!({
+            "available": false,
+            "error": "Base URL cannot be empty"
+        }));
+    }
+
+    let client = LibreTranslateClient::new(config);
+
+    match timeout(Duration::from_secs(5), client.languages()).await {
+        Ok(Ok(languages)) => Ok(serde_json::json!({
+            "available": true,
+            "languages_available": languages
+        })),
+        Ok(Err(e)) => Ok(serde_json::json!({
+            "available": false,
+            "error": format!("Failed to list languages: {}", e)
+        })),
+        Err(_) => Ok(serde_json::json!({
+            "available": false,
+            "error": "Connection timeout: LibreTranslate is not responding"
+        })),
+    }
+}
+
+impl MachineTranslationClient for LibreTranslateClient {
+    fn provider_name(&self) -> &'static str {
+        "libretranslate"
+    }
+
+    fn translate_text(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<String, String>> + Send + '_>> {
+        let text = text.to_string();
+        let source_language = source_language.to_string();
+        let target_language = target_language.to_string();
+        Box::pin(async move { self.translate(&text, &source_language, &target_language).await })
+    }
+}
+
+/// LibreTranslate has no prompt or model: `call_api` translates the given text between
+/// the default languages, and `list_models` reports the supported languages
+impl TranslationClient for LibreTranslateClient {
+    fn call_api(&self, prompt: &str, _model: Option<String>) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<String, String>> + Send + '_>> {
+        self.translate_text(prompt, DEFAULT_SOURCE_LANGUAGE, DEFAULT_TARGET_LANGUAGE)
+    }
+
+    fn list_models(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<String>, String>> + Send + '_>> {
+        Box::pin(self.languages())
+    }
+
+    fn test_connection(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), String>> + Send + '_>> {
+        Box::pin(async move {
+            self.languages()
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("Connection test failed: {}", e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translation::common::test_server::serve_once;
+
+    fn config(base_url: String) -> LibreTranslateConfig {
+        LibreTranslateConfig {
+            base_url,
+            api_key: Some("libre-key".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_libretranslate_language_codes() {
+        assert_eq!(libretranslate_language_code("ja"), "ja");
+        assert_eq!(libretranslate_language_code("FR"), "fr");
+        assert_eq!(libretranslate_language_code("en-GB"), "en");
+        assert_eq!(libretranslate_language_code("zh"), "zh");
+        assert_eq!(libretranslate_language_code("zh_TW"), "zt");
+        assert_eq!(libretranslate_language_code("pt-BR"), "pb");
+    }
+
+    #[tokio::test]
+    async fn test_translate_protects_placeholders() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            r#"{"translatedText":"{ 1 } a donné { 0 } au héros."}"#,
+        )
+        .await;
+        let client = LibreTranslateClient::new(config(base_url));
+
+        let result = client
+            .translate("[ITEM_5]を[NAME_1]が勇者に渡した。", "ja", "fr")
+            .await
+            .unwrap();
+        assert_eq!(result, "[NAME_1] a donné [ITEM_5] au héros.");
+
+        let request = server.await.unwrap();
+        assert_eq!(request.request_line, "POST /translate HTTP/1.1");
+
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["q"], "{0}を{1}が勇者に渡した。");
+        assert_eq!(body["source"], "ja");
+        assert_eq!(body["target"], "fr");
+        assert_eq!(body["format"], "text");
+        assert_eq!(body["api_key"], "libre-key");
+    }
+
+    #[tokio::test]
+    async fn test_translate_reports_api_errors() {
+        let (base_url, _server) = serve_once(
+            "400 Bad Request",
+            r#"{"error":"ja is not supported"}"#,
+        )
+        .await;
+        let client = LibreTranslateClient::new(config(base_url));
+
+        let error = client.translate("勇者", "ja", "fr").await.unwrap_err();
+        assert_eq!(error, "HTTP error 400 Bad Request: ja is not supported");
+    }
+
+    #[tokio::test]
+    async fn test_list_models_returns_language_codes() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            r#"[{"code":"en","name":"English","targets":["fr","ja"]},{"code":"ja","name":"Japanese","targets":["en"]}]"#,
+        )
+        .await;
+        let client = LibreTranslateClient::new(config(base_url));
+
+        let languages = client.list_models().await.unwrap();
+        assert_eq!(languages, vec!["en".to_string(), "ja".to_string()]);
+
+        let request = server.await.unwrap();
+        assert_eq!(request.request_line, "GET /languages HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn test_call_api_translates_between_default_languages() {
+        let (base_url, server) = serve_once("200 OK", r#"{"translatedText":"Bonjour"}"#).await;
+        let client = LibreTranslateClient::new(LibreTranslateConfig {
+            api_key: None,
+            ..config(base_url)
+        });
+
+        assert_eq!(client.call_api("こんにちは", None).await.unwrap(), "Bonjour");
+
+        let body: serde_json::Value = serde_json::from_str(&server.await.unwrap().body).unwrap();
+        assert_eq!(body["source"], DEFAULT_SOURCE_LANGUAGE);
+        assert_eq!(body["target"], DEFAULT_TARGET_LANGUAGE);
+        assert!(body.get("api_key").is_none());
+    }
+}
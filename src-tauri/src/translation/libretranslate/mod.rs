@@ -0,0 +1,17 @@
+// LibreTranslate integration module exports
+// This module handles the LibreTranslate API (self-hosted or public instances)
+
+pub mod client;
+
+use crate::translation::machine;
+
+// Re-export main types
+pub use client::{
+    check_libretranslate_status, libretranslate_language_code, LibreTranslateClient,
+    LibreTranslateConfig,
+};
+
+/// Single translation manager for LibreTranslate
+pub type SingleTranslationManager = machine::SingleTranslationManager<LibreTranslateClient>;
+/// Sequential translation manager for LibreTranslate
+pub type SequentialTranslationManager = machine::SequentialTranslationManager<LibreTranslateClient>;
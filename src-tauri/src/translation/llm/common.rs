@@ -0,0 +1,40 @@
+// Common interface of LLM translation providers
+// The sequential manager only needs prompt translation and the provider defaults
+
+use crate::translation::common::types::{
+    BatchTranslationRequest, BatchTranslationResult, SingleTranslationRequest,
+    SingleTranslationResult,
+};
+use std::future::Future;
+use std::pin::Pin;
+use tauri::AppHandle;
+
+/// Single translation manager of an LLM provider (Ollama, RunPod, OpenAI-compatible)
+///
+/// Unlike machine translation engines, requests go through a prompt with the glossary,
+/// so every call needs the app handle to reach the frontend.
+pub trait LlmTranslationClient: Send + Sync + 'static {
+    /// Provider name, used in logs
+    fn provider_name(&self) -> &'static str;
+
+    /// Prefix of the sequential session ids
+    fn session_prefix(&self) -> &'static str;
+
+    /// Model used when a request does not name one
+    fn default_model(&self) -> String;
+
+    /// Translate a single text entry
+    fn translate_request<'a>(
+        &'a self,
+        app_handle: &'a AppHandle,
+        request: SingleTranslationRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<SingleTranslationResult, String>> + Send + 'a>>;
+
+    /// Translate several short entries with one numbered prompt
+    /// Entries returned as None must be translated again with `translate_request`
+    fn translate_batch_request<'a>(
+        &'a self,
+        app_handle: &'a AppHandle,
+        request: BatchTranslationRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<BatchTranslationResult, String>> + Send + 'a>>;
+}
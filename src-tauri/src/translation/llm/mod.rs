@@ -0,0 +1,10 @@
+// LLM translation module exports
+// Shared logic for prompt-based providers (Ollama, RunPod, OpenAI-compatible):
+// managers generic over the provider's single translation manager
+
+pub mod common;
+pub mod sequential;
+
+// Re-export main types
+pub use common::LlmTranslationClient;
+pub use sequential::SequentialTranslationManager;
@@ -0,0 +1,689 @@
+// Sequential translation logic for LLM providers
+// Shared by Ollama, RunPod and OpenAI-compatible servers, delegates to common sequential functions
+
+use crate::translation::common::functions::{
+    common_generate_session_id, common_get_session_progress, common_get_translation_settings,
+    common_pause_session, common_requeue_errors, common_resume_session, common_stop_session,
+};
+use crate::translation::common::types::*;
+use crate::translation::common::functions::{common_check_length_budget, common_dialogue_context, common_select_batch, SEQUENTIAL_REQUEST_DELAY_MS};
+use crate::translation::common::functions::{common_fan_out_error, common_fan_out_translation, common_group_duplicates};
+use crate::translation::common::retry::{classify_translation_error, with_retry, RetryPolicy, TranslationErrorKind};
+use crate::translation::llm::LlmTranslationClient;
+use crate::translation::memory::{lookup_memory_hits, MEMORY_MODEL};
+use crate::translation::ollama::common::{get_default_source_language, get_default_target_language};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// LLM sequential session wrapper
+/// Extends common SequentialSession with the app handle LLM providers need
+#[derive(Debug)]
+pub struct LlmSequentialSession {
+    /// Common session data
+    pub common: SequentialSession,
+    /// App handle for glossary and translation memory lookups
+    pub app_handle: AppHandle,
+}
+
+/// Sequential translation manager for an LLM provider
+pub struct SequentialTranslationManager<C: LlmTranslationClient> {
+    client: Arc<C>,
+    active_sessions: Arc<Mutex<HashMap<String, LlmSequentialSession>>>,
+    session_counter: Arc<Mutex<u64>>,
+}
+
+impl<C: LlmTranslationClient> SequentialTranslationManager<C> {
+    /// Create new sequential translation manager
+    pub fn new(single_manager: Arc<C>) -> Self {
+        Self {
+            client: single_manager,
+            active_sessions: Arc::new(Mutex::new(HashMap::new())),
+            session_counter: Arc::new(Mutex::new(1)),
+        }
+    }
+
+    /// Start a new sequential translation session
+    /// AppHandle is required for glossary lookup during translations
+    pub async fn start_session(
+        &self,
+        app_handle: AppHandle,
+        request: SequentialTranslationRequest,
+    ) -> Result<String, String> {
+        println!(
+            "🔧 [{} Sequential] start_session called with {} texts",
+            self.client.provider_name(),
+            request.texts.len()
+        );
+        let session_id = self.generate_session_id().await;
+
+        // Entries sharing a source text are translated once, then fanned out to all of them
+        let current_index = request
+            .start_from
+            .map(|id| {
+                // Find index of text to resume from
+                request
+                    .texts
+                    .iter()
+                    .position(|text| text.id == id)
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0);
+        let mut texts = request.texts.clone();
+        let duplicates = if request.deduplicate.unwrap_or(true) {
+            common_group_duplicates(
+                &mut texts,
+                current_index,
+                request.deduplicate_excluded_types.as_deref(),
+            )
+        } else {
+            HashMap::new()
+        };
+        println!(
+            "🧬 [{} Sequential] {} duplicate entries grouped with the entry sharing their source text",
+            self.client.provider_name(),
+            duplicates.values().map(Vec::len).sum::<usize>()
+        );
+
+        let session = LlmSequentialSession {
+            common: SequentialSession {
+                session_id: session_id.clone(),
+                project_id: request.project_id,
+                texts,
+                current_index,
+                processed_entries: HashMap::new(),
+                errors: Vec::new(),
+                successful_translations: Vec::new(),
+                status: SequentialStatus::Running,
+                start_time: std::time::Instant::now(),
+                translation_settings: TranslationSettings {
+                    source_language: request.source_language,
+                    target_language: request.target_language,
+                    model: request.model,
+                },
+                pause_settings: request.pause_settings.unwrap_or(PauseSettings {
+                    enabled: true,
+                    batch_size: 150,
+                    pause_duration_minutes: 5,
+                }),
+                batch_counter: 0,
+                length_budgets: request.length_budgets.unwrap_or_default(),
+                retranslate_over_budget: request.retranslate_over_budget.unwrap_or(false),
+                prompt_batch_size: request.prompt_batch_size.unwrap_or(1).max(1) as usize,
+                context_lines: request.context_lines.unwrap_or(0) as usize,
+                parallel_requests: request.parallel_requests.unwrap_or(1).max(1) as usize,
+                use_translation_memory: request.use_translation_memory.unwrap_or(true),
+                duplicates,
+                processing: false,
+                stats: Default::default(),
+                translated_texts: HashMap::new(),
+            },
+            app_handle,
+        };
+
+        {
+            let mut sessions = self.active_sessions.lock().await;
+            sessions.insert(session_id.clone(), session);
+        }
+
+        let manager = Arc::new(self.clone());
+        let session_id_clone = session_id.clone();
+        tokio::spawn(async move {
+            manager.process_session(session_id_clone).await;
+        });
+
+        Ok(session_id)
+    }
+
+    /// Get session progress
+    pub async fn get_progress(&self, session_id: &str) -> Option<SequentialProgress> {
+        let mut sessions = self.active_sessions.lock().await;
+        sessions.get_mut(session_id).map(|session| {
+            // Use common function but drain successful_translations first
+            let successful_translations = session.common.successful_translations.drain(..).collect::<Vec<_>>();
+            let mut progress = common_get_session_progress(&session.common);
+
+            // Note: pause_time_remaining is now managed by frontend
+            progress.pause_time_remaining = None;
+
+            progress.successful_translations = successful_translations;
+            progress
+        })
+    }
+
+    /// Pause session
+    pub async fn pause_session(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.active_sessions.lock().await;
+        if let Some(session) = sessions.get_mut(session_id) {
+            common_pause_session(&mut session.common);
+            Ok(())
+        } else {
+            Err(format!("Session {} not found", session_id))
+        }
+    }
+
+    /// Resume session
+    pub async fn resume_session(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.active_sessions.lock().await;
+        if let Some(session) = sessions.get_mut(session_id) {
+            common_resume_session(&mut session.common);
+
+            // Restart processing in background
+            let manager = Arc::new(self.clone());
+            let session_id = session_id.to_string();
+            tokio::spawn(async move {
+                manager.process_session(session_id).await;
+            });
+
+            Ok(())
+        } else {
+            Err(format!("Session {} not found", session_id))
+        }
+    }
+
+    /// Stop session
+    pub async fn stop_session(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.active_sessions.lock().await;
+        if let Some(session) = sessions.get_mut(session_id) {
+            common_stop_session(&mut session.common);
+            Ok(())
+        } else {
+            Err(format!("Session {} not found", session_id))
+        }
+    }
+
+    /// Requeue the failed entries of a session and resume it
+    /// Refused while the session is still processing: pause it and let in-flight requests finish first
+    pub async fn requeue_errors(&self, session_id: &str) -> Result<usize, String> {
+        let mut sessions = self.active_sessions.lock().await;
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        if session.common.processing {
+            return Err(format!("Session {} is still processing, pause it first", session_id));
+        }
+
+        let requeued = common_requeue_errors(&mut session.common);
+        if requeued > 0 {
+            session.common.status = SequentialStatus::Running;
+            session.common.stats.pause_ended();
+
+            let manager = Arc::new(self.clone());
+            let session_id = session_id.to_string();
+            tokio::spawn(async move {
+                manager.process_session(session_id).await;
+            });
+        }
+
+        Ok(requeued)
+    }
+
+    /// Get all active sessions
+    pub async fn get_active_sessions(&self) -> Vec<SequentialProgress> {
+        let sessions = self.active_sessions.lock().await;
+        let session_ids: Vec<String> = sessions.keys().cloned().collect();
+
+        let mut results = Vec::new();
+        for session_id in session_ids {
+            if let Some(progress) = self.get_progress(&session_id).await {
+                results.push(progress);
+            }
+        }
+        results
+    }
+
+    /// Process session (internal method)
+    ///
+    /// Up to `parallel_requests` chunks (one entry, or one batch prompt) are in
+    /// flight at once. Results are committed in dispatch order, so progress and
+    /// `current_index` only move over contiguous finished entries. Pause and stop
+    /// only halt dispatching: requests already sent finish and are committed.
+    async fn process_session(&self, session_id: String) {
+        // A resumed session may still be draining its previous loop, which picks up again on its own
+        let parallel_requests = {
+            let mut sessions = self.active_sessions.lock().await;
+            match sessions.get_mut(&session_id) {
+                Some(session) if !session.common.processing => {
+                    session.common.processing = true;
+                    session.common.parallel_requests
+                }
+                _ => return,
+            }
+        };
+
+        let mut in_flight: VecDeque<JoinHandle<Vec<EntryOutcome>>> = VecDeque::new();
+        let mut dispatch_index = None;
+
+        loop {
+            // Fill the worker pool while the session is running
+            while in_flight.len() < parallel_requests {
+                let chunk = {
+                    let sessions = self.active_sessions.lock().await;
+                    let Some(session) = sessions.get(&session_id) else {
+                        break;
+                    };
+                    if !matches!(session.common.status, SequentialStatus::Running) {
+                        break;
+                    }
+                    let start = *dispatch_index.get_or_insert(session.common.current_index);
+                    // project_id is passed to glossary lookup: global + project-specific terms
+                    let count = common_select_batch(
+                        &session.common.texts,
+                        start,
+                        session.common.prompt_batch_size,
+                    );
+                    if count == 0 {
+                        break; // No more entries to dispatch
+                    }
+                    (
+                        start,
+                        session.common.texts[start..start + count].to_vec(),
+                        session.common.project_id,
+                        session.app_handle.clone(),
+                    )
+                };
+
+                let (start, texts, project_id, app_handle) = chunk;
+                dispatch_index = Some(start + texts.len());
+
+                let manager = self.clone();
+                let session_id = session_id.clone();
+                in_flight.push_back(tokio::spawn(async move {
+                    manager
+                        .translate_chunk(&session_id, start, texts, project_id, app_handle)
+                        .await
+                }));
+            }
+
+            let Some(worker) = in_flight.pop_front() else {
+                // Nothing in flight: stop unless the session was resumed meanwhile
+                let mut sessions = self.active_sessions.lock().await;
+                if let Some(session) = sessions.get_mut(&session_id) {
+                    if matches!(session.common.status, SequentialStatus::Running)
+                        && session.common.current_index < session.common.texts.len()
+                    {
+                        dispatch_index = None;
+                        continue;
+                    }
+                    // Mark as completed when done
+                    session.common.processing = false;
+                    if session.common.current_index >= session.common.texts.len() {
+                        session.common.status = SequentialStatus::Completed;
+                    }
+                }
+                break;
+            };
+
+            let committed = match worker.await {
+                Ok(outcomes) => self.commit_outcomes(&session_id, outcomes).await,
+                Err(e) => Err(format!("Translation worker failed: {}", e)),
+            };
+
+            if committed.is_err() {
+                // Mark session as error if processing fails
+                for worker in in_flight.drain(..) {
+                    worker.abort();
+                }
+                let mut sessions = self.active_sessions.lock().await;
+                if let Some(session) = sessions.get_mut(&session_id) {
+                    session.common.status = SequentialStatus::Error;
+                    session.common.processing = false;
+                }
+                break;
+            }
+
+            // Note: Pause logic is now handled by frontend
+            // Backend only responds to explicit pause/resume commands
+
+            // Without parallel requests, keep a small delay between translations
+            if parallel_requests <= 1 {
+                tokio::time::sleep(tokio::time::Duration::from_millis(SEQUENTIAL_REQUEST_DELAY_MS)).await;
+            }
+        }
+    }
+
+    /// Translate a chunk of consecutive entries, without committing anything
+    /// With a prompt batch size above 1, consecutive short entries share one numbered prompt
+    async fn translate_chunk(
+        &self,
+        session_id: &str,
+        start: usize,
+        texts: Vec<TranslationText>,
+        project_id: i64,
+        app_handle: AppHandle,
+    ) -> Vec<EntryOutcome> {
+        let translation_settings = self.get_translation_settings(session_id).await;
+
+        // Entries already in the translation memory are reused without calling the model
+        let use_translation_memory = {
+            let sessions = self.active_sessions.lock().await;
+            sessions
+                .get(session_id)
+                .is_some_and(|session| session.common.use_translation_memory)
+        };
+        let memory_hits = if use_translation_memory {
+            lookup_memory_hits(
+                &app_handle,
+                &texts,
+                translation_settings.source_language.as_deref(),
+                translation_settings.target_language.as_deref(),
+            )
+            .await
+        } else {
+            vec![None; texts.len()]
+        };
+        let pending: Vec<TranslationText> = texts
+            .iter()
+            .zip(&memory_hits)
+            .filter(|(_, hit)| hit.is_none())
+            .map(|(text, _)| text.clone())
+            .collect();
+
+        // Entries left as None (batch error, missing or misaligned answer) get their own prompt
+        let mut batch_results = if pending.len() > 1 {
+            let context = self.dialogue_context(session_id, start).await;
+            self.translate_batch(&app_handle, &pending, &translation_settings, project_id, context)
+                .await
+        } else {
+            vec![None; pending.len()]
+        }
+        .into_iter();
+
+        let mut outcomes = Vec::with_capacity(texts.len());
+        for (offset, (text, memory_hit)) in texts.into_iter().zip(memory_hits).enumerate() {
+            let request = SingleTranslationRequest {
+                source_text: text.source_text.clone(),
+                source_language: translation_settings.source_language.clone(),
+                target_language: translation_settings.target_language.clone(),
+                context: self.dialogue_context(session_id, start + offset).await,
+                model: translation_settings.model.clone(),
+                project_id: Some(project_id),
+                text_type: text.text_type.clone(), // Category filtering in glossary lookup
+                max_length: None,
+            };
+
+            println!(
+                "🔤 [{} Translation] Entry {} - Source: \"{}\"",
+                self.client.provider_name(),
+                text.id,
+                text.source_text
+            );
+
+            // Transient and rate-limited failures are retried with backoff before giving up
+            let (result, attempts) = match memory_hit {
+                Some(result) => (Ok(result), 0),
+                None => match batch_results.next().flatten() {
+                    Some(result) => (Ok(result), 1),
+                    None => {
+                        with_retry(&RetryPolicy::default(), || {
+                            self.client.translate_request(&app_handle, request.clone())
+                        })
+                        .await
+                    }
+                },
+            };
+            outcomes.push(EntryOutcome { text, request, result, attempts });
+        }
+        outcomes
+    }
+
+    /// Commit the outcomes of a chunk, in order
+    async fn commit_outcomes(&self, session_id: &str, outcomes: Vec<EntryOutcome>) -> Result<(), String> {
+        for outcome in outcomes {
+            self.record_outcome(
+                session_id,
+                outcome.text.id,
+                outcome.text.location,
+                outcome.request,
+                outcome.result,
+                outcome.attempts,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Translate several entries with one numbered prompt
+    /// Returns one result per entry, None for each entry to translate again on its own
+    async fn translate_batch(
+        &self,
+        app_handle: &AppHandle,
+        texts: &[TranslationText],
+        translation_settings: &TranslationSettings,
+        project_id: i64,
+        context: Option<String>,
+    ) -> Vec<Option<SingleTranslationResult>> {
+        let request = BatchTranslationRequest {
+            source_texts: texts.iter().map(|text| text.source_text.clone()).collect(),
+            source_language: translation_settings.source_language.clone(),
+            target_language: translation_settings.target_language.clone(),
+            model: translation_settings.model.clone(),
+            project_id: Some(project_id),
+            text_type: texts[0].text_type.clone(),
+            context,
+        };
+
+        match self.client.translate_batch_request(app_handle, request).await {
+            Ok(result) => {
+                let missing = result.translations.iter().filter(|t| t.is_none()).count();
+                if missing > 0 {
+                    println!(
+                        "⚠️ [{} Translation] Batch of {}: {} entries fall back to single prompts",
+                        self.client.provider_name(),
+                        texts.len(),
+                        missing
+                    );
+                }
+
+                let model_used = result.model_used;
+                let time_per_entry = result.processing_time_ms / texts.len() as u64;
+                result
+                    .translations
+                    .into_iter()
+                    .map(|translation| {
+                        translation.map(|translated_text| SingleTranslationResult {
+                            translated_text,
+                            model_used: model_used.clone(),
+                            confidence: Some(0.8),
+                            processing_time_ms: time_per_entry,
+                        })
+                    })
+                    .collect()
+            }
+            Err(e) => {
+                println!(
+                    "⚠️ [{} Translation] Batch of {} failed, falling back to single prompts: {}",
+                    self.client.provider_name(),
+                    texts.len(),
+                    e
+                );
+                vec![None; texts.len()]
+            }
+        }
+    }
+
+    /// Previous lines of the same event page and speaker of the entry at `index`
+    async fn dialogue_context(&self, session_id: &str, index: usize) -> Option<String> {
+        let sessions = self.active_sessions.lock().await;
+        common_dialogue_context(&sessions.get(session_id)?.common, index)
+    }
+
+    /// Record the outcome of one entry, checking its length budget
+    async fn record_outcome(
+        &self,
+        session_id: &str,
+        entry_id: i32,
+        location: Option<String>,
+        request: SingleTranslationRequest,
+        outcome: Result<SingleTranslationResult, String>,
+        attempts: u32,
+    ) -> Result<(), String> {
+        let (length_budgets, retranslate_over_budget, app_handle) = {
+            let sessions = self.active_sessions.lock().await;
+            if let Some(session) = sessions.get(session_id) {
+                (
+                    session.common.length_budgets.clone(),
+                    session.common.retranslate_over_budget,
+                    session.app_handle.clone(),
+                )
+            } else {
+                return Err("Session not found".to_string());
+            }
+        };
+
+        match outcome {
+            Ok(mut result) => {
+                let source_chars = request.source_text.chars().count();
+                println!(
+                    "✅ [{} Translation] Entry {} - Translated: \"{}\"",
+                    self.client.provider_name(),
+                    entry_id,
+                    result.translated_text
+                );
+
+                // Check the field length budget, asking once for a shorter translation
+                let mut over_budget = common_check_length_budget(
+                    location.as_deref(),
+                    &result.translated_text,
+                    &length_budgets,
+                );
+                if let Some(violation) = over_budget.clone().filter(|_| retranslate_over_budget) {
+                    let shorter_request = SingleTranslationRequest {
+                        max_length: Some(violation.max_cells),
+                        ..request
+                    };
+                    if let Ok(shorter) = self.client.translate_request(&app_handle, shorter_request).await {
+                        let shorter_violation = common_check_length_budget(
+                            location.as_deref(),
+                            &shorter.translated_text,
+                            &length_budgets,
+                        );
+                        if shorter_violation
+                            .as_ref()
+                            .map_or(true, |v| v.actual_cells < violation.actual_cells)
+                        {
+                            result = shorter;
+                            over_budget = shorter_violation;
+                        }
+                    }
+                }
+
+                // Create successful translation record
+                let successful_translation = SuccessfulTranslation {
+                    entry_id,
+                    translated_text: result.translated_text.clone(),
+                    model_used: result.model_used,
+                    timestamp: chrono::Utc::now().timestamp(),
+                    processing_time_ms: result.processing_time_ms,
+                    over_budget,
+                };
+
+                // Mark as processed and store successful translation
+                let mut sessions = self.active_sessions.lock().await;
+                if let Some(session) = sessions.get_mut(session_id) {
+                    session.common.processed_entries.insert(entry_id, true);
+                    session
+                        .common
+                        .translated_texts
+                        .insert(entry_id, successful_translation.translated_text.clone());
+                    // Memory hits take no time and would skew the estimate
+                    if successful_translation.model_used != MEMORY_MODEL {
+                        session.common.stats.record(
+                            source_chars,
+                            successful_translation.processing_time_ms,
+                            &successful_translation.model_used,
+                        );
+                    }
+                    common_fan_out_translation(&mut session.common, &successful_translation);
+                    session.common.successful_translations.push(successful_translation);
+                    session.common.current_index += 1;
+                }
+                Ok(())
+            }
+            Err(e) => {
+                // Record translation error
+                let kind = classify_translation_error(&e);
+                let error = SequentialError {
+                    entry_id,
+                    error_message: format!("Translation failed: {}", e),
+                    timestamp: chrono::Utc::now().timestamp(),
+                    kind,
+                    attempts,
+                };
+
+                let mut sessions = self.active_sessions.lock().await;
+                if let Some(session) = sessions.get_mut(session_id) {
+                    common_fan_out_error(&mut session.common, &error);
+                    session.common.errors.push(error);
+                    session.common.processed_entries.insert(entry_id, false);
+                    session.common.current_index += 1; // Continue to next even on error
+
+                    // Every following entry would fail the same way: wait for the user to fix the settings
+                    if kind == TranslationErrorKind::FatalConfig {
+                        println!(
+                            "⛔ [{} Translation] Entry {} - Configuration error, pausing session {}: {}",
+                            self.client.provider_name(),
+                            entry_id,
+                            session_id,
+                            e
+                        );
+                        common_pause_session(&mut session.common);
+                    }
+                }
+                Ok(()) // Don't fail the whole session on single entry error
+            }
+        }
+    }
+
+    /// Generate unique session ID
+    async fn generate_session_id(&self) -> String {
+        let mut counter = self.session_counter.lock().await;
+        common_generate_session_id(self.client.session_prefix(), &mut counter)
+    }
+
+    /// Get translation settings for a session (with defaults)
+    async fn get_translation_settings(&self, session_id: &str) -> TranslationSettings {
+        let sessions = self.active_sessions.lock().await;
+        if let Some(session) = sessions.get(session_id) {
+            common_get_translation_settings(
+                session.common.translation_settings.clone(),
+                get_default_source_language,
+                get_default_target_language,
+                || self.client.default_model(),
+            )
+        } else {
+            // Fallback defaults if session not found
+            common_get_translation_settings(
+                TranslationSettings {
+                    source_language: None,
+                    target_language: None,
+                    model: None,
+                },
+                get_default_source_language,
+                get_default_target_language,
+                || self.client.default_model(),
+            )
+        }
+    }
+}
+
+impl Clone for LlmSequentialSession {
+    fn clone(&self) -> Self {
+        Self {
+            common: self.common.clone(),
+            app_handle: self.app_handle.clone(),
+        }
+    }
+}
+
+impl<C: LlmTranslationClient> Clone for SequentialTranslationManager<C> {
+    fn clone(&self) -> Self {
+        Self {
+            client: Arc::clone(&self.client),
+            active_sessions: Arc::clone(&self.active_sessions),
+            session_counter: Arc::clone(&self.session_counter),
+        }
+    }
+}
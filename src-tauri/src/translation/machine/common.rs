@@ -0,0 +1,161 @@
+// Common utilities for machine translation (non-LLM) providers
+// MT engines get the raw text, so engine placeholders must be shielded from them
+
+use crate::translation::common::functions::TranslationClient;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Placeholders produced by the text formatters: [COLOR_1], [NAME_2], [CDB_21:78:0],
+/// including one level of nesting such as [F_SIMPLE_[CSELF_17]]
+static PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[[A-Za-z][A-Za-z_]*(?:[^\[\]\s]|\[[^\[\]\s]*\])*\]").unwrap()
+});
+
+/// Client for a machine translation engine (DeepL, LibreTranslate, ...)
+///
+/// Unlike LLM providers there is no prompt: the engine receives the text and
+/// the language pair directly. Implementations protect placeholders and map
+/// our ISO 639-1 codes to the engine's own codes.
+pub trait MachineTranslationClient: TranslationClient {
+    /// Provider identifier, used for session ids and `model_used`
+    fn provider_name(&self) -> &'static str;
+
+    /// Translate formatted text (with placeholders) between two ISO 639-1 codes
+    fn translate_text(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<String, String>> + Send + '_>>;
+}
+
+/// Text with its placeholders swapped for engine-safe markers
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectedText {
+    pub text: String,
+    pub placeholders: Vec<String>,
+}
+
+/// Replace every placeholder with the marker built from its index
+pub fn protect_placeholders(text: &str, marker: impl Fn(usize) -> String) -> ProtectedText {
+    let mut placeholders = Vec::new();
+    let text = PLACEHOLDER_REGEX
+        .replace_all(text, |caps: &regex::Captures| {
+            let marker = marker(placeholders.len());
+            placeholders.push(caps[0].to_string());
+            marker
+        })
+        .into_owned();
+
+    ProtectedText { text, placeholders }
+}
+
+/// Check that a translation kept exactly the placeholders of its source, in any order
+/// Used to detect batch answers where the model shifted or swapped entries
+pub fn same_placeholders(source: &str, translated: &str) -> bool {
+    let collect = |text: &str| {
+        let mut found = PLACEHOLDER_REGEX
+            .find_iter(text)
+            .map(|m| m.as_str().to_string())
+            .collect::<Vec<_>>();
+        found.sort();
+        found
+    };
+    collect(source) == collect(translated)
+}
+
+/// Put the original placeholders back where the engine left the markers
+///
+/// `marker_regex` must capture the placeholder index in group 1. Every
+/// placeholder has to come back exactly once, otherwise the translation
+/// would silently lose a color code or a name.
+pub fn restore_placeholders(
+    translated: &str,
+    marker_regex: &Regex,
+    placeholders: &[String],
+) -> Result<String, String> {
+    let mut seen = vec![false; placeholders.len()];
+    let mut unknown = None;
+
+    let restored = marker_regex
+        .replace_all(translated, |caps: &regex::Captures| {
+            match caps[1].parse::<usize>().ok().filter(|i| *i < placeholders.len()) {
+                Some(index) => {
+                    seen[index] = true;
+                    placeholders[index].clone()
+                }
+                None => {
+                    unknown = Some(caps[0].to_string());
+                    caps[0].to_string()
+                }
+            }
+        })
+        .into_owned();
+
+    if let Some(marker) = unknown {
+        return Err(format!("Unknown placeholder marker in translation: {}", marker));
+    }
+    if let Some(missing) = seen.iter().position(|found| !found) {
+        return Err(format!(
+            "Placeholder {} was lost in translation",
+            placeholders[missing]
+        ));
+    }
+
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static BRACE_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\s*(\d+)\s*\}").unwrap());
+
+    #[test]
+    fn test_protect_placeholders_replaces_each_code() {
+        let protected = protect_placeholders(
+            "[COLOR_1]勇者[COLOR_0]は[F_SIMPLE_[CSELF_17]][CDB_21:78:0]を使った！",
+            |i| format!("{{{}}}", i),
+        );
+
+        assert_eq!(protected.text, "{0}勇者{1}は{2}{3}を使った！");
+        assert_eq!(
+            protected.placeholders,
+            vec!["[COLOR_1]", "[COLOR_0]", "[F_SIMPLE_[CSELF_17]]", "[CDB_21:78:0]"]
+        );
+    }
+
+    #[test]
+    fn test_protect_placeholders_keeps_plain_brackets() {
+        let protected = protect_placeholders("[1] 選択肢 [ ]", |i| format!("{{{}}}", i));
+        assert_eq!(protected.text, "[1] 選択肢 [ ]");
+        assert!(protected.placeholders.is_empty());
+    }
+
+    #[test]
+    fn test_same_placeholders_ignores_order() {
+        assert!(same_placeholders("[NAME_1]が[ITEM_5]を", "[ITEM_5] pour [NAME_1]"));
+        assert!(same_placeholders("勇者", "Héros"));
+        assert!(!same_placeholders("[NAME_1]が[ITEM_5]を", "[NAME_1] seulement"));
+        assert!(!same_placeholders("勇者", "[COLOR_1]Héros"));
+    }
+
+    #[test]
+    fn test_restore_placeholders_handles_reordering() {
+        let placeholders = vec!["[NAME_1]".to_string(), "[ITEM_5]".to_string()];
+        let restored =
+            restore_placeholders("{ 1 } pour { 0 }", &BRACE_MARKER, &placeholders).unwrap();
+        assert_eq!(restored, "[ITEM_5] pour [NAME_1]");
+    }
+
+    #[test]
+    fn test_restore_placeholders_reports_lost_markers() {
+        let placeholders = vec!["[NAME_1]".to_string(), "[ITEM_5]".to_string()];
+
+        let error = restore_placeholders("{0} seulement", &BRACE_MARKER, &placeholders).unwrap_err();
+        assert!(error.contains("[ITEM_5]"));
+
+        let error = restore_placeholders("{0} {1} {7}", &BRACE_MARKER, &placeholders).unwrap_err();
+        assert!(error.contains("{7}"));
+    }
+}
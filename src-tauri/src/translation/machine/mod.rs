@@ -0,0 +1,15 @@
+// Machine translation module exports
+// Shared logic for non-LLM engines (DeepL, LibreTranslate): placeholder
+// protection and managers generic over the engine client
+
+pub mod common;
+pub mod sequential;
+pub mod single;
+
+// Re-export main types
+pub use common::{
+    protect_placeholders, restore_placeholders, same_placeholders, MachineTranslationClient,
+    ProtectedText,
+};
+pub use sequential::SequentialTranslationManager;
+pub use single::SingleTranslationManager;
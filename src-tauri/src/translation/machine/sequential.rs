@@ -0,0 +1,397 @@
+// Sequential translation logic for machine translation engines
+// Thin wrapper that delegates to common sequential functions
+
+use crate::translation::common::functions::{
+    common_check_length_budget, common_fan_out_error, common_fan_out_translation,
+    common_generate_session_id, common_get_session_progress, common_get_translation_settings,
+    common_group_duplicates, common_pause_session, common_requeue_errors, common_resume_session,
+    common_stop_session,
+};
+use crate::translation::common::retry::{classify_translation_error, with_retry, RetryPolicy, TranslationErrorKind};
+use crate::translation::common::types::*;
+use crate::translation::machine::{MachineTranslationClient, SingleTranslationManager};
+use crate::translation::memory::{lookup_memory_hits, MEMORY_MODEL};
+use crate::translation::ollama::common::{get_default_source_language, get_default_target_language};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+
+/// Machine translation sequential session wrapper
+/// Extends common SequentialSession with the app handle of the translation memory lookup
+/// (MT engines do not use the glossary)
+#[derive(Debug, Clone)]
+pub struct MachineSequentialSession {
+    /// Common session data
+    pub common: SequentialSession,
+    /// App handle for translation memory lookup
+    pub app_handle: AppHandle,
+}
+
+/// Sequential translation manager for a machine translation engine
+pub struct SequentialTranslationManager<C: MachineTranslationClient> {
+    client: Arc<SingleTranslationManager<C>>,
+    active_sessions: Arc<Mutex<HashMap<String, MachineSequentialSession>>>,
+    session_counter: Arc<Mutex<u64>>,
+}
+
+impl<C: MachineTranslationClient> SequentialTranslationManager<C> {
+    /// Create new sequential translation manager
+    pub fn new(single_manager: Arc<SingleTranslationManager<C>>) -> Self {
+        Self {
+            client: single_manager,
+            active_sessions: Arc::new(Mutex::new(HashMap::new())),
+            session_counter: Arc::new(Mutex::new(1)),
+        }
+    }
+
+    /// Start a new sequential translation session
+    /// AppHandle is required for translation memory lookup
+    pub async fn start_session(
+        &self,
+        app_handle: AppHandle,
+        request: SequentialTranslationRequest,
+    ) -> Result<String, String> {
+        let session_id = self.generate_session_id().await;
+
+        // Entries sharing a source text are translated once, then fanned out to all of them
+        let current_index = request
+            .start_from
+            .map(|id| {
+                // Find index of text to resume from
+                request
+                    .texts
+                    .iter()
+                    .position(|text| text.id == id)
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0);
+        let mut texts = request.texts.clone();
+        let duplicates = if request.deduplicate.unwrap_or(true) {
+            common_group_duplicates(
+                &mut texts,
+                current_index,
+                request.deduplicate_excluded_types.as_deref(),
+            )
+        } else {
+            HashMap::new()
+        };
+
+        let common = SequentialSession {
+            session_id: session_id.clone(),
+            project_id: request.project_id,
+            texts,
+            current_index,
+            processed_entries: HashMap::new(),
+            errors: Vec::new(),
+            successful_translations: Vec::new(),
+            status: SequentialStatus::Running,
+            start_time: std::time::Instant::now(),
+            translation_settings: TranslationSettings {
+                source_language: request.source_language,
+                target_language: request.target_language,
+                model: request.model,
+            },
+            pause_settings: request.pause_settings.unwrap_or(PauseSettings {
+                enabled: true,
+                batch_size: 150,
+                pause_duration_minutes: 5,
+            }),
+            batch_counter: 0,
+            length_budgets: request.length_budgets.unwrap_or_default(),
+            // MT engines cannot be asked for a shorter translation
+            retranslate_over_budget: false,
+            // MT engines translate one text per request
+            prompt_batch_size: 1,
+            // MT engines take no context: the engine only receives the text
+            context_lines: 0,
+            translated_texts: HashMap::new(),
+            // One request at a time, MT APIs rate-limit concurrent calls
+            parallel_requests: 1,
+            use_translation_memory: request.use_translation_memory.unwrap_or(true),
+            duplicates,
+            processing: false,
+            stats: Default::default(),
+        };
+        let session = MachineSequentialSession { common, app_handle };
+
+        {
+            let mut sessions = self.active_sessions.lock().await;
+            sessions.insert(session_id.clone(), session);
+        }
+
+        let manager = Arc::new(self.clone());
+        let session_id_clone = session_id.clone();
+        tokio::spawn(async move {
+            manager.process_session(session_id_clone).await;
+        });
+
+        Ok(session_id)
+    }
+
+    /// Get session progress
+    pub async fn get_progress(&self, session_id: &str) -> Option<SequentialProgress> {
+        let mut sessions = self.active_sessions.lock().await;
+        sessions.get_mut(session_id).map(|session| {
+            // Use common function but drain successful_translations first
+            let successful_translations = session.common.successful_translations.drain(..).collect::<Vec<_>>();
+            let mut progress = common_get_session_progress(&session.common);
+
+            // Note: pause_time_remaining is now managed by frontend
+            progress.pause_time_remaining = None;
+
+            progress.successful_translations = successful_translations;
+            progress
+        })
+    }
+
+    /// Pause session
+    pub async fn pause_session(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.active_sessions.lock().await;
+        if let Some(session) = sessions.get_mut(session_id) {
+            common_pause_session(&mut session.common);
+            Ok(())
+        } else {
+            Err(format!("Session {} not found", session_id))
+        }
+    }
+
+    /// Resume session
+    pub async fn resume_session(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.active_sessions.lock().await;
+        if let Some(session) = sessions.get_mut(session_id) {
+            common_resume_session(&mut session.common);
+
+            // Restart processing in background
+            let manager = Arc::new(self.clone());
+            let session_id = session_id.to_string();
+            tokio::spawn(async move {
+                manager.process_session(session_id).await;
+            });
+
+            Ok(())
+        } else {
+            Err(format!("Session {} not found", session_id))
+        }
+    }
+
+    /// Stop session
+    pub async fn stop_session(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.active_sessions.lock().await;
+        if let Some(session) = sessions.get_mut(session_id) {
+            common_stop_session(&mut session.common);
+            Ok(())
+        } else {
+            Err(format!("Session {} not found", session_id))
+        }
+    }
+
+    /// Requeue the failed entries of a session and resume it
+    /// Refused while the session is still processing: pause it and let the current request finish first
+    pub async fn requeue_errors(&self, session_id: &str) -> Result<usize, String> {
+        let mut sessions = self.active_sessions.lock().await;
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        if session.common.processing {
+            return Err(format!("Session {} is still processing, pause it first", session_id));
+        }
+
+        let requeued = common_requeue_errors(&mut session.common);
+        if requeued > 0 {
+            session.common.status = SequentialStatus::Running;
+            session.common.stats.pause_ended();
+
+            let manager = Arc::new(self.clone());
+            let session_id = session_id.to_string();
+            tokio::spawn(async move {
+                manager.process_session(session_id).await;
+            });
+        }
+
+        Ok(requeued)
+    }
+
+    /// Process session (internal method)
+    async fn process_session(&self, session_id: String) {
+        // A resumed session may still be finishing its current entry, its loop picks up again on its own
+        {
+            let mut sessions = self.active_sessions.lock().await;
+            match sessions.get_mut(&session_id) {
+                Some(session) if !session.common.processing => session.common.processing = true,
+                _ => return,
+            }
+        }
+
+        loop {
+            let should_continue = {
+                let sessions = self.active_sessions.lock().await;
+                if let Some(session) = sessions.get(&session_id) {
+                    matches!(session.common.status, SequentialStatus::Running)
+                        && session.common.current_index < session.common.texts.len()
+                } else {
+                    false
+                }
+            };
+
+            if !should_continue {
+                break;
+            }
+
+            if self.process_next_entry(&session_id).await.is_err() {
+                let mut sessions = self.active_sessions.lock().await;
+                if let Some(session) = sessions.get_mut(&session_id) {
+                    session.common.status = SequentialStatus::Error;
+                }
+                break;
+            }
+        }
+
+        let mut sessions = self.active_sessions.lock().await;
+        if let Some(session) = sessions.get_mut(&session_id) {
+            session.common.processing = false;
+            if session.common.current_index >= session.common.texts.len() {
+                session.common.status = SequentialStatus::Completed;
+            }
+        }
+    }
+
+    /// Process next entry in session
+    async fn process_next_entry(&self, session_id: &str) -> Result<(), String> {
+        let (text, length_budgets, use_translation_memory, app_handle, settings) = {
+            let sessions = self.active_sessions.lock().await;
+            let session = sessions
+                .get(session_id)
+                .ok_or_else(|| "Session not found".to_string())?;
+            if session.common.current_index >= session.common.texts.len() {
+                return Ok(());
+            }
+            (
+                session.common.texts[session.common.current_index].clone(),
+                session.common.length_budgets.clone(),
+                session.common.use_translation_memory,
+                session.app_handle.clone(),
+                common_get_translation_settings(
+                    session.common.translation_settings.clone(),
+                    get_default_source_language,
+                    get_default_target_language,
+                    || self.client.provider_name().to_string(),
+                ),
+            )
+        };
+
+        // An entry already in the translation memory is reused without calling the engine
+        let memory_hit = if use_translation_memory {
+            lookup_memory_hits(
+                &app_handle,
+                std::slice::from_ref(&text),
+                settings.source_language.as_deref(),
+                settings.target_language.as_deref(),
+            )
+            .await
+            .pop()
+            .flatten()
+        } else {
+            None
+        };
+
+        let entry_id = text.id;
+        let location = text.location;
+        let source_chars = text.source_text.chars().count();
+        let request = SingleTranslationRequest {
+            source_text: text.source_text,
+            source_language: settings.source_language,
+            target_language: settings.target_language,
+            context: None,
+            model: None,
+            project_id: None,
+            text_type: None,
+            max_length: None,
+        };
+
+        // Transient and rate-limited failures are retried with backoff before giving up
+        let (outcome, attempts) = match memory_hit {
+            Some(result) => (Ok(result), 0),
+            None => {
+                with_retry(&RetryPolicy::default(), || self.client.translate(request.clone())).await
+            }
+        };
+
+        let mut sessions = self.active_sessions.lock().await;
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| "Session not found".to_string())?;
+        match outcome {
+            Ok(result) => {
+                let over_budget = common_check_length_budget(
+                    location.as_deref(),
+                    &result.translated_text,
+                    &length_budgets,
+                );
+                session.common.processed_entries.insert(entry_id, true);
+                // Memory hits take no time and would skew the estimate
+                if result.model_used != MEMORY_MODEL {
+                    session.common.stats.record(
+                        source_chars,
+                        result.processing_time_ms,
+                        &result.model_used,
+                    );
+                }
+                let successful_translation = SuccessfulTranslation {
+                    entry_id,
+                    translated_text: result.translated_text,
+                    model_used: result.model_used,
+                    timestamp: chrono::Utc::now().timestamp(),
+                    processing_time_ms: result.processing_time_ms,
+                    over_budget,
+                };
+                common_fan_out_translation(&mut session.common, &successful_translation);
+                session.common.successful_translations.push(successful_translation);
+            }
+            Err(e) => {
+                let kind = classify_translation_error(&e);
+                let error = SequentialError {
+                    entry_id,
+                    error_message: format!("Translation failed: {}", e),
+                    timestamp: chrono::Utc::now().timestamp(),
+                    kind,
+                    attempts,
+                };
+                common_fan_out_error(&mut session.common, &error);
+                session.common.errors.push(error);
+                session.common.processed_entries.insert(entry_id, false);
+
+                // Wrong key or exhausted quota: every following entry would fail the same way
+                if kind == TranslationErrorKind::FatalConfig {
+                    println!(
+                        "⛔ [{}] Entry {} - Configuration error, pausing session {}: {}",
+                        self.client.provider_name(),
+                        entry_id,
+                        session_id,
+                        e
+                    );
+                    common_pause_session(&mut session.common);
+                }
+            }
+        }
+        session.common.current_index += 1;
+        Ok(())
+    }
+
+    /// Generate unique session ID
+    async fn generate_session_id(&self) -> String {
+        let mut counter = self.session_counter.lock().await;
+        let prefix = format!("{}_seq_", self.client.provider_name());
+        common_generate_session_id(&prefix, &mut counter)
+    }
+}
+
+impl<C: MachineTranslationClient> Clone for SequentialTranslationManager<C> {
+    fn clone(&self) -> Self {
+        Self {
+            client: Arc::clone(&self.client),
+            active_sessions: Arc::clone(&self.active_sessions),
+            session_counter: Arc::clone(&self.session_counter),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// Single translation logic for machine translation engines
+// No prompt and no glossary: the engine receives the text and the language pair
+
+use crate::translation::common::types::{SingleTranslationRequest, SingleTranslationResult, TranslationSuggestion};
+use crate::translation::machine::MachineTranslationClient;
+use crate::translation::ollama::common::{
+    validate_translation_request, DEFAULT_SOURCE_LANGUAGE, DEFAULT_TARGET_LANGUAGE,
+};
+use std::sync::Arc;
+
+/// Single translation manager for a machine translation engine
+pub struct SingleTranslationManager<C: MachineTranslationClient> {
+    client: Arc<C>,
+}
+
+impl<C: MachineTranslationClient> SingleTranslationManager<C> {
+    /// Create new single translation manager
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+
+    /// Provider identifier of the underlying engine
+    pub fn provider_name(&self) -> &'static str {
+        self.client.provider_name()
+    }
+
+    /// Translate a single text entry
+    /// `model` and `max_length` are ignored: MT engines cannot be asked for shorter output
+    pub async fn translate(
+        &self,
+        request: SingleTranslationRequest,
+    ) -> Result<SingleTranslationResult, String> {
+        validate_translation_request(&request.source_text)?;
+
+        let start_time = std::time::Instant::now();
+        let source_lang = request.source_language.as_deref().unwrap_or(DEFAULT_SOURCE_LANGUAGE);
+        let target_lang = request.target_language.as_deref().unwrap_or(DEFAULT_TARGET_LANGUAGE);
+
+        let translated = self
+            .client
+            .translate_text(&request.source_text, source_lang, target_lang)
+            .await?;
+        let translated_text = translated.trim().to_string();
+        if translated_text.is_empty() {
+            return Err("Empty translation response".to_string());
+        }
+
+        Ok(SingleTranslationResult {
+            translated_text,
+            model_used: self.provider_name().to_string(),
+            confidence: None,
+            processing_time_ms: start_time.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Get translation suggestions for a text
+    /// MT engines are deterministic, so there is at most one suggestion
+    pub async fn get_suggestions(
+        &self,
+        source_text: &str,
+        context: Option<&str>,
+        max_suggestions: usize,
+    ) -> Result<Vec<TranslationSuggestion>, String> {
+        let request = SingleTranslationRequest {
+            source_text: source_text.to_string(),
+            source_language: None,
+            target_language: None,
+            context: context.map(|s| s.to_string()),
+            model: None,
+            project_id: None,
+            text_type: None,
+            max_length: None,
+        };
+
+        let result = self.translate(request).await.map_err(|e| {
+            format!("Failed to get {} suggestion: {}", self.provider_name(), e)
+        })?;
+
+        let mut suggestions = vec![TranslationSuggestion {
+            suggestion: result.translated_text,
+            confidence: 0.8,
+            source: self.provider_name().to_string(),
+        }];
+        suggestions.truncate(max_suggestions);
+        Ok(suggestions)
+    }
+}
+
+impl<C: MachineTranslationClient> Clone for SingleTranslationManager<C> {
+    fn clone(&self) -> Self {
+        Self {
+            client: Arc::clone(&self.client),
+        }
+    }
+}
@@ -0,0 +1,334 @@
+// Translation memory lookup module
+// Reuses the translations of source texts already translated, exactly or approximately
+// Entries live in the frontend database and are queried via Tauri events, like the glossary
+
+use crate::translation::common::types::{SingleTranslationResult, TranslationText};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, Listener};
+use uuid::Uuid;
+
+/// Model reported for translations taken from the memory
+pub const MEMORY_MODEL: &str = "translation-memory";
+
+/// Minimum similarity for an approximate match to be suggested
+pub const FUZZY_MATCH_THRESHOLD: f64 = 0.75;
+
+/// Translation memory entry structure matching frontend TranslationMemoryEntry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationMemoryEntry {
+    pub normalized_source: String,
+    pub source_text: String,
+    pub translated_text: String,
+}
+
+/// Request payload for translation memory lookup
+/// Without length bounds, returns the entries whose normalized source is one of `normalized_sources`;
+/// with them, returns the entries whose normalized source length is within the bounds (fuzzy candidates)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationMemoryLookupRequest {
+    pub request_id: String,
+    pub source_language: String,
+    pub target_language: String,
+    pub normalized_sources: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+}
+
+/// Response payload for translation memory lookup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationMemoryLookupResponse {
+    pub request_id: String,
+    pub success: bool,
+    pub data: Option<Vec<TranslationMemoryEntry>>,
+    pub error: Option<String>,
+}
+
+/// Memory entry close to a looked-up text
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryMatch {
+    pub source_text: String,
+    pub translated_text: String,
+    pub similarity: f64,
+}
+
+/// Key of a source text in the translation memory
+///
+/// Source texts are stored as prepared by the parsers (`prepare_for_translation`), so codes and
+/// meaningful spaces are already placeholders: only stray whitespace is normalized here.
+/// Line breaks are kept, a translation is only reused with the same line layout.
+/// Must stay in sync with `normalizeMemorySource` in app/composables/db/memory/memory.ts
+pub fn normalize_memory_source(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .split('\n')
+        .map(|line| {
+            line.split([' ', '\t'])
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+/// Similarity of two texts between 0 and 1, from their character edit distance
+pub fn memory_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    // Levenshtein distance, keeping one row of the matrix
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    1.0 - row[b.len()] as f64 / longest as f64
+}
+
+/// Source lengths a candidate can have to reach `threshold` with a text of `length` characters
+/// The edit distance is at least the length difference, which bounds the similarity
+fn fuzzy_length_bounds(length: usize, threshold: f64) -> (usize, usize) {
+    let min_length = (length as f64 * threshold).ceil() as usize;
+    let max_length = (length as f64 / threshold).floor() as usize;
+    (min_length, max_length)
+}
+
+/// Send a lookup request to the frontend and wait for its entries
+async fn request_memory_entries(
+    app_handle: &AppHandle,
+    request: TranslationMemoryLookupRequest,
+) -> Result<Vec<TranslationMemoryEntry>, String> {
+    let (tx, mut rx) =
+        tokio::sync::mpsc::unbounded_channel::<Result<Vec<TranslationMemoryEntry>, String>>();
+    let request_id = request.request_id.clone();
+
+    // Listen before emitting so a fast response cannot be missed
+    let listener_id = app_handle.listen("translation-memory-lookup-response", move |event| {
+        match serde_json::from_str::<TranslationMemoryLookupResponse>(event.payload()) {
+            Ok(response) if response.request_id == request_id => {
+                let result = if response.success {
+                    Ok(response.data.unwrap_or_default())
+                } else {
+                    Err(response.error.unwrap_or_else(|| "Unknown error".to_string()))
+                };
+                let _ = tx.send(result);
+            }
+            Ok(_) => {} // Response to another lookup
+            Err(e) => {
+                log::error!("Failed to parse translation-memory-lookup-response: {}", e);
+            }
+        }
+    });
+
+    log::debug!(
+        "Emitting translation-memory-lookup-request: request_id={}, {} source(s), {}-{}",
+        request.request_id,
+        request.normalized_sources.len(),
+        request.source_language,
+        request.target_language
+    );
+
+    if let Err(e) = app_handle.emit("translation-memory-lookup-request", &request) {
+        app_handle.unlisten(listener_id);
+        return Err(format!("Failed to emit translation-memory-lookup-request: {}", e));
+    }
+
+    // Wait for response with timeout (10 seconds)
+    let result = tokio::select! {
+        result = rx.recv() => result.unwrap_or_else(|| Err("Channel closed".to_string())),
+        _ = tokio::time::sleep(tokio::time::Duration::from_secs(10)) => {
+            Err("Timeout waiting for translation-memory-lookup-response".to_string())
+        }
+    };
+    app_handle.unlisten(listener_id);
+    result
+}
+
+/// Lookup the translations stored for exactly these source texts
+/// Returns the translations by normalized source text
+pub async fn lookup_memory_translations(
+    app_handle: &AppHandle,
+    source_texts: &[String],
+    source_language: &str,
+    target_language: &str,
+) -> Result<HashMap<String, String>, String> {
+    let mut normalized_sources: Vec<String> =
+        source_texts.iter().map(|text| normalize_memory_source(text)).collect();
+    normalized_sources.sort();
+    normalized_sources.dedup();
+
+    let entries = request_memory_entries(
+        app_handle,
+        TranslationMemoryLookupRequest {
+            request_id: Uuid::new_v4().to_string(),
+            source_language: source_language.to_string(),
+            target_language: target_language.to_string(),
+            normalized_sources,
+            min_length: None,
+            max_length: None,
+        },
+    )
+    .await?;
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| (entry.normalized_source, entry.translated_text))
+        .collect())
+}
+
+/// Lookup the memory entries similar to a source text, best first
+/// Only matches reaching FUZZY_MATCH_THRESHOLD are returned, exact matches included
+pub async fn lookup_memory_matches(
+    app_handle: &AppHandle,
+    source_text: &str,
+    source_language: &str,
+    target_language: &str,
+    max_matches: usize,
+) -> Result<Vec<MemoryMatch>, String> {
+    let normalized = normalize_memory_source(source_text);
+    let (min_length, max_length) =
+        fuzzy_length_bounds(normalized.chars().count(), FUZZY_MATCH_THRESHOLD);
+
+    let candidates = request_memory_entries(
+        app_handle,
+        TranslationMemoryLookupRequest {
+            request_id: Uuid::new_v4().to_string(),
+            source_language: source_language.to_string(),
+            target_language: target_language.to_string(),
+            normalized_sources: vec![normalized.clone()],
+            min_length: Some(min_length),
+            max_length: Some(max_length),
+        },
+    )
+    .await?;
+
+    Ok(rank_memory_matches(&normalized, candidates, max_matches))
+}
+
+/// Score candidates against a normalized source, keeping the best ones above the threshold
+fn rank_memory_matches(
+    normalized: &str,
+    candidates: Vec<TranslationMemoryEntry>,
+    max_matches: usize,
+) -> Vec<MemoryMatch> {
+    let mut matches: Vec<MemoryMatch> = candidates
+        .into_iter()
+        .filter_map(|entry| {
+            let similarity = memory_similarity(normalized, &entry.normalized_source);
+            (similarity >= FUZZY_MATCH_THRESHOLD).then_some(MemoryMatch {
+                source_text: entry.source_text,
+                translated_text: entry.translated_text,
+                similarity,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    matches.truncate(max_matches);
+    matches
+}
+
+/// Translations found in the memory for each entry of a sequential chunk, None when not found
+/// A failed lookup only means the entries go to the model
+pub async fn lookup_memory_hits(
+    app_handle: &AppHandle,
+    texts: &[TranslationText],
+    source_language: Option<&str>,
+    target_language: Option<&str>,
+) -> Vec<Option<SingleTranslationResult>> {
+    let source_lang = source_language.unwrap_or("ja");
+    let target_lang = target_language.unwrap_or("fr");
+
+    let source_texts: Vec<String> = texts.iter().map(|text| text.source_text.clone()).collect();
+    let translations =
+        match lookup_memory_translations(app_handle, &source_texts, source_lang, target_lang).await {
+            Ok(translations) => translations,
+            Err(e) => {
+                log::warn!("Failed to lookup translation memory: {}, continuing without it", e);
+                return vec![None; texts.len()];
+            }
+        };
+
+    source_texts
+        .iter()
+        .map(|source_text| {
+            translations
+                .get(&normalize_memory_source(source_text))
+                .map(|translated_text| SingleTranslationResult {
+                    translated_text: translated_text.clone(),
+                    model_used: MEMORY_MODEL.to_string(),
+                    confidence: Some(1.0),
+                    processing_time_ms: 0,
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(source: &str, translated: &str) -> TranslationMemoryEntry {
+        TranslationMemoryEntry {
+            normalized_source: normalize_memory_source(source),
+            source_text: source.to_string(),
+            translated_text: translated.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_memory_source() {
+        assert_eq!(normalize_memory_source("  こんにちは  "), "こんにちは");
+        assert_eq!(normalize_memory_source("Hello \t  [NAME_1]!"), "Hello [NAME_1]!");
+        assert_eq!(normalize_memory_source("\r\nLine one  \r\n  line two\n"), "Line one\nline two");
+        // Placeholders of meaningful spaces are left alone
+        assert_eq!(normalize_memory_source("[SPC_2]Hi"), "[SPC_2]Hi");
+    }
+
+    #[test]
+    fn test_memory_similarity() {
+        assert_eq!(memory_similarity("", ""), 1.0);
+        assert_eq!(memory_similarity("いらっしゃいませ", "いらっしゃいませ"), 1.0);
+        assert_eq!(memory_similarity("kitten", "sitting"), 1.0 - 3.0 / 7.0);
+        assert_eq!(memory_similarity("abcd", ""), 0.0);
+        assert_eq!(memory_similarity("いらっしゃいませ！", "いらっしゃいませ。"), 1.0 - 1.0 / 9.0);
+    }
+
+    #[test]
+    fn test_fuzzy_length_bounds_keep_every_reachable_length() {
+        let (min_length, max_length) = fuzzy_length_bounds(8, 0.75);
+        assert_eq!((min_length, max_length), (6, 10));
+        assert!(memory_similarity("abcdefgh", "abcdef") >= 0.75);
+        assert!(memory_similarity("abcdefgh", "abcdefghij") >= 0.75);
+        assert!(memory_similarity("abcdefgh", "abcde") < 0.75);
+        assert!(memory_similarity("abcdefgh", "abcdefghijk") < 0.75);
+    }
+
+    #[test]
+    fn test_rank_memory_matches() {
+        let candidates = vec![
+            entry("いらっしゃいませ。", "Bienvenue."),
+            entry("いらっしゃいませ！", "Bienvenue !"),
+            entry("さようなら", "Au revoir"),
+        ];
+
+        let matches = rank_memory_matches(&normalize_memory_source("いらっしゃいませ！"), candidates, 3);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].translated_text, "Bienvenue !");
+        assert_eq!(matches[0].similarity, 1.0);
+        assert_eq!(matches[1].translated_text, "Bienvenue.");
+    }
+}
@@ -2,8 +2,14 @@
 // This module handles translation services
 
 pub mod common;
+pub mod deepl;
 pub mod glossary;
+pub mod libretranslate;
+pub mod llm;
+pub mod machine;
+pub mod memory;
 pub mod ollama;
+pub mod openai_compat;
 pub mod provider;
 pub mod runpod;
 pub mod service;
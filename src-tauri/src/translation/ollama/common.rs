@@ -1,6 +1,9 @@
 // Common utilities and constants for Ollama translation module
 // Centralizes shared logic to avoid duplication (DRY principle)
 
+use once_cell::sync::Lazy;
+use regex::Regex;
+
 /// Default translation model
 pub const DEFAULT_MODEL: &str = "ludolingo:latest";
 
@@ -13,6 +16,20 @@ pub const DEFAULT_TARGET_LANGUAGE: &str = "fr";
 /// Maximum text length for translation (characters)
 pub const MAX_TEXT_LENGTH: usize = 10000;
 
+/// Longest entry (characters) packed into a batch prompt; longer texts get their own prompt
+pub const MAX_BATCH_ENTRY_LENGTH: usize = 200;
+
+/// Line break marker inside a batch prompt, so that one entry stays on one numbered line
+const BATCH_LINE_BREAK: &str = "<br>";
+
+/// Numbered line of a batch answer: "3. text", "3) text" or "3: text"
+static BATCH_ITEM_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(\d+)\s*[.):]\s?(.*)$").unwrap());
+
+/// Line break marker as the model may return it (<br>, <br/>, <BR />)
+static BATCH_LINE_BREAK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<br\s*/?>").unwrap());
+
 /// Build translation prompt for Ollama
 /// Simple prompt format: "Translate from {source} to {target}: {text}"
 /// The Modelfile (ludolingo.modelfile) handles:
@@ -26,11 +43,15 @@ pub const MAX_TEXT_LENGTH: usize = 10000;
 /// - glossary_terms contains ALWAYS global terms (project_id IS NULL)
 /// - IF project_id was provided during lookup: glossary_terms ALSO contains project-specific terms
 /// - All terms are combined and formatted together: "GLOSSARY:\nTerm1: Translation1\nTerm2: Translation2\n\nTranslate from ..."
+///
+/// Context (previous dialogue lines, speaker) comes after the glossary, in a
+/// CONTEXT section the model is told not to translate
 pub fn build_translation_prompt(
     source_text: &str,
     source_language: Option<&str>,
     target_language: Option<&str>,
     glossary_terms: Option<&[(String, String)]>,
+    context: Option<&str>,
 ) -> String {
     let source_lang = source_language.unwrap_or(DEFAULT_SOURCE_LANGUAGE);
     let target_lang = target_language.unwrap_or(DEFAULT_TARGET_LANGUAGE);
@@ -42,18 +63,122 @@ pub fn build_translation_prompt(
         String::new()
     };
 
-    // Build final prompt with optional glossary prefix
-    if glossary_section.is_empty() {
-        format!(
-            "Translate from {} to {}: {}",
-            source_lang, target_lang, source_text
-        )
+    // Build final prompt with optional glossary and context prefixes
+    format!(
+        "{}{}Translate from {} to {}: {}",
+        glossary_section,
+        format_context_for_prompt(context),
+        source_lang,
+        target_lang,
+        source_text
+    )
+}
+
+/// Format the context section of a prompt
+/// Empty when there is no context, so prompts without context are unchanged
+pub fn format_context_for_prompt(context: Option<&str>) -> String {
+    match context.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(context) => format!(
+            "CONTEXT (earlier dialogue, for reference only, do not translate it):\n{}\n\n",
+            context
+        ),
+        None => String::new(),
+    }
+}
+
+/// Build a numbered prompt translating several entries at once
+/// Each entry takes one line "N. text"; line breaks inside an entry become <br>
+/// The glossary and context sections are shared by all entries, as in `build_translation_prompt`
+pub fn build_batch_translation_prompt(
+    source_texts: &[String],
+    source_language: Option<&str>,
+    target_language: Option<&str>,
+    glossary_terms: Option<&[(String, String)]>,
+    context: Option<&str>,
+) -> String {
+    let source_lang = source_language.unwrap_or(DEFAULT_SOURCE_LANGUAGE);
+    let target_lang = target_language.unwrap_or(DEFAULT_TARGET_LANGUAGE);
+
+    let glossary_section = if let Some(terms) = glossary_terms {
+        crate::translation::glossary::format_glossary_for_prompt(terms)
     } else {
-        format!(
-            "{}Translate from {} to {}: {}",
-            glossary_section, source_lang, target_lang, source_text
-        )
+        String::new()
+    };
+
+    let entries = source_texts
+        .iter()
+        .enumerate()
+        .map(|(i, text)| {
+            let single_line = text.replace("\r\n", "\n").replace('\n', BATCH_LINE_BREAK);
+            format!("{}. {}", i + 1, single_line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "{}{}Translate each numbered line from {} to {}. Answer with exactly {} numbered lines in the same \"N. translation\" format, one per entry, and keep every {} marker.\n{}",
+        glossary_section,
+        format_context_for_prompt(context),
+        source_lang,
+        target_lang,
+        source_texts.len(),
+        BATCH_LINE_BREAK,
+        entries
+    )
+}
+
+/// Parse the answer to a batch prompt into one translation per entry
+///
+/// The result always has `expected_count` items, None for each entry whose
+/// translation is missing, empty or given twice. A number outside 1..=expected_count
+/// means the numbering drifted, so the whole answer is rejected.
+pub fn parse_batch_translation_response(response: &str, expected_count: usize) -> Vec<Option<String>> {
+    let mut items: Vec<Option<String>> = vec![None; expected_count];
+    let mut duplicated = vec![false; expected_count];
+    let mut current: Option<usize> = None;
+
+    for line in response.lines() {
+        if let Some(caps) = BATCH_ITEM_REGEX.captures(line) {
+            let number = caps[1].parse::<usize>().unwrap_or(0);
+            if number == 0 || number > expected_count {
+                return vec![None; expected_count];
+            }
+            let index = number - 1;
+            if items[index].is_some() {
+                duplicated[index] = true;
+            }
+            items[index] = Some(caps[2].to_string());
+            current = Some(index);
+        } else if let Some(index) = current {
+            // Continuation line: the model broke an entry over several lines
+            if !line.trim().is_empty() {
+                if let Some(text) = items[index].as_mut() {
+                    text.push('\n');
+                    text.push_str(line.trim());
+                }
+            }
+        }
     }
+
+    items
+        .into_iter()
+        .zip(duplicated)
+        .map(|(item, duplicated)| {
+            let text = item.filter(|_| !duplicated)?;
+            let text = BATCH_LINE_BREAK_REGEX.replace_all(&text, "\n");
+            parse_translation_response(&text).ok().filter(|t| !t.is_empty())
+        })
+        .collect()
+}
+
+/// Add a length constraint to a translation prompt
+/// Used to ask for a shorter translation when a database field has a length budget
+/// (item names, skill descriptions, ...)
+pub fn append_length_constraint(prompt: &str, max_length: u32) -> String {
+    format!(
+        "Keep the translation short: at most {} characters per line, abbreviate if needed.\n{}",
+        max_length, prompt
+    )
 }
 
 /// Parse and clean translation response from Ollama
@@ -113,3 +238,77 @@ pub fn get_translation_model_options() -> ollama_rs::models::ModelOptions {
     ollama_rs::models::ModelOptions::default()
         .num_ctx(2048) // Context window - required for API initialization
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_batch_translation_prompt_numbers_entries() {
+        let texts = vec!["はい".to_string(), "勇者が来た。\n行こう！".to_string()];
+        let prompt = build_batch_translation_prompt(&texts, Some("ja"), Some("fr"), None, None);
+
+        assert!(prompt.starts_with("Translate each numbered line from ja to fr."));
+        assert!(prompt.contains("exactly 2 numbered lines"));
+        assert!(prompt.ends_with("\n1. はい\n2. 勇者が来た。<br>行こう！"));
+    }
+
+    #[test]
+    fn test_build_translation_prompt_renders_context_before_text() {
+        let prompt = build_translation_prompt("彼が来たの。", Some("ja"), Some("fr"), None, None);
+        assert_eq!(prompt, "Translate from ja to fr: 彼が来たの。");
+
+        let prompt = build_translation_prompt(
+            "彼が来たの。",
+            Some("ja"),
+            Some("fr"),
+            None,
+            Some("Speaker: アリス\nPrevious lines:\n- ねえ、聞いて。 => Dis, écoute."),
+        );
+        assert_eq!(
+            prompt,
+            "CONTEXT (earlier dialogue, for reference only, do not translate it):\n\
+             Speaker: アリス\nPrevious lines:\n- ねえ、聞いて。 => Dis, écoute.\n\n\
+             Translate from ja to fr: 彼が来たの。"
+        );
+    }
+
+    #[test]
+    fn test_parse_batch_translation_response_aligns_items() {
+        let response = "Voici les traductions :\n1. Oui\n2) Le héros est venu.<br/>Allons-y !\n3: Non";
+        let items = parse_batch_translation_response(response, 3);
+
+        assert_eq!(
+            items,
+            vec![
+                Some("Oui".to_string()),
+                Some("Le héros est venu.\nAllons-y !".to_string()),
+                Some("Non".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_batch_translation_response_flags_missing_and_duplicated_items() {
+        let items = parse_batch_translation_response("1. Oui\n3. Non\n3. Peut-être\n", 4);
+        assert_eq!(items, vec![Some("Oui".to_string()), None, None, None]);
+
+        let items = parse_batch_translation_response("1. Oui\n2.\n", 2);
+        assert_eq!(items, vec![Some("Oui".to_string()), None]);
+    }
+
+    #[test]
+    fn test_parse_batch_translation_response_rejects_drifted_numbering() {
+        let items = parse_batch_translation_response("1. Oui\n2. Non\n3. Encore", 2);
+        assert_eq!(items, vec![None, None]);
+    }
+
+    #[test]
+    fn test_parse_batch_translation_response_joins_continuation_lines() {
+        let items = parse_batch_translation_response("1. Le héros\nest venu.\n\n2. Oui", 2);
+        assert_eq!(
+            items,
+            vec![Some("Le héros\nest venu.".to_string()), Some("Oui".to_string())]
+        );
+    }
+}
@@ -3,21 +3,26 @@
 
 pub mod client;
 pub mod common;
-pub mod sequential;
 pub mod single;
 // pub mod models;
 
+use crate::translation::llm;
+
 // Re-export main types
 pub use client::{check_ollama_status, ModelInfo, OllamaClient, OllamaConfig};
 pub use common::{
-    build_translation_prompt, get_default_model, get_default_source_language,
-    get_default_target_language, get_translation_model_options, parse_translation_response,
-    validate_translation_request, DEFAULT_MODEL, DEFAULT_SOURCE_LANGUAGE, DEFAULT_TARGET_LANGUAGE,
+    build_batch_translation_prompt, build_translation_prompt, format_context_for_prompt,
+    get_default_model,
+    get_default_source_language, get_default_target_language, get_translation_model_options,
+    parse_batch_translation_response, parse_translation_response, validate_translation_request,
+    DEFAULT_MODEL, DEFAULT_SOURCE_LANGUAGE, DEFAULT_TARGET_LANGUAGE, MAX_BATCH_ENTRY_LENGTH,
     MAX_TEXT_LENGTH,
 };
-pub use sequential::SequentialTranslationManager;
 pub use single::SingleTranslationManager;
 pub use crate::translation::common::types::{
     SequentialError, SequentialProgress, SequentialStatus, SequentialTranslationRequest, TranslationText,
     SingleTranslationRequest, SingleTranslationResult, TranslationSuggestion,
 };
+
+/// Sequential translation manager for Ollama
+pub type SequentialTranslationManager = llm::SequentialTranslationManager<SingleTranslationManager>;
@@ -2,9 +2,15 @@
 // Thin wrapper that delegates to common translation functions
 // Uses Ollama-specific API calls with the generic translation framework
 
-use crate::translation::common::functions::translate_single_common;
-use crate::translation::common::types::{SingleTranslationRequest, SingleTranslationResult, TranslationSuggestion};
-use crate::translation::ollama::OllamaClient;
+use crate::translation::common::functions::{translate_batch_common, translate_single_common};
+use crate::translation::common::types::{
+    BatchTranslationRequest, BatchTranslationResult, SingleTranslationRequest,
+    SingleTranslationResult, TranslationSuggestion,
+};
+use crate::translation::llm::LlmTranslationClient;
+use crate::translation::ollama::{get_default_model, OllamaClient};
+use std::future::Future;
+use std::pin::Pin;
 use tauri::AppHandle;
 
 /// Single translation manager
@@ -30,6 +36,16 @@ impl SingleTranslationManager {
         translate_single_common(&*self.client, request, app_handle).await
     }
 
+    /// Translate several short entries with one numbered prompt
+    /// Entries returned as None must be translated again with `translate`
+    pub async fn translate_batch(
+        &self,
+        app_handle: &AppHandle,
+        request: BatchTranslationRequest,
+    ) -> Result<BatchTranslationResult, String> {
+        translate_batch_common(&*self.client, request, app_handle).await
+    }
+
     /// Get translation suggestions for a text
     /// AppHandle is optional - if provided, glossary terms will be used
     pub async fn get_suggestions(
@@ -50,6 +66,7 @@ impl SingleTranslationManager {
             model: None,
             project_id: None, // Suggestions don't have project context, use global terms only
             text_type: None,  // Suggestions don't have text_type context, no category filtering
+            max_length: None,
         };
 
         // Translate with glossary if AppHandle is provided
@@ -82,3 +99,33 @@ impl SingleTranslationManager {
         Ok(suggestions)
     }
 }
+
+impl LlmTranslationClient for SingleTranslationManager {
+    fn provider_name(&self) -> &'static str {
+        "Ollama"
+    }
+
+    fn session_prefix(&self) -> &'static str {
+        "seq_"
+    }
+
+    fn default_model(&self) -> String {
+        get_default_model()
+    }
+
+    fn translate_request<'a>(
+        &'a self,
+        app_handle: &'a AppHandle,
+        request: SingleTranslationRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<SingleTranslationResult, String>> + Send + 'a>> {
+        Box::pin(self.translate(app_handle, request))
+    }
+
+    fn translate_batch_request<'a>(
+        &'a self,
+        app_handle: &'a AppHandle,
+        request: BatchTranslationRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<BatchTranslationResult, String>> + Send + 'a>> {
+        Box::pin(self.translate_batch(app_handle, request))
+    }
+}
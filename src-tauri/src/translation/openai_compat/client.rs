@@ -0,0 +1,381 @@
+// OpenAI-compatible HTTP client using reqwest
+// Works with any server exposing the OpenAI chat completions API
+// (llama.cpp server, vLLM, LM Studio, hosted OpenAI-compatible endpoints)
+// URL format: {base_url}/chat/completions and {base_url}/models
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use crate::translation::common::functions::TranslationClient;
+
+/// OpenAI-compatible client configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiCompatConfig {
+    /// API root including the version segment (e.g. http://localhost:8080/v1)
+    pub base_url: String,
+    /// Sent as a Bearer token when set (hosted endpoints)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Model used when a request does not name one
+    pub model: String,
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    #[serde(default)]
+    pub top_p: Option<f64>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
+impl Default for OpenAiCompatConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:8080/v1".to_string(),
+            api_key: None,
+            model: String::new(),
+            temperature: None,
+            top_p: None,
+            max_tokens: None,
+        }
+    }
+}
+
+/// OpenAI-compatible HTTP client wrapper
+#[derive(Clone)]
+pub struct OpenAiCompatClient {
+    client: Client,
+    config: OpenAiCompatConfig,
+    base_url: String,
+}
+
+/// Chat message (matching OpenAI API format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String, // "user", "assistant", "system"
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn user(content: String) -> Self {
+        Self {
+            role: "user".to_string(),
+            content,
+        }
+    }
+}
+
+/// Chat completion request body
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    stream: bool,
+}
+
+/// Chat completion response (only the fields we read)
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+/// Models list response (GET /models)
+#[derive(Debug, Deserialize)]
+struct ModelsResponse {
+    data: Vec<ModelEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    id: String,
+}
+
+impl OpenAiCompatClient {
+    /// Create new OpenAI-compatible client
+    pub fn new(config: OpenAiCompatConfig) -> Self {
+        let base_url = config.base_url.trim_end_matches('/').to_string();
+        let client = Client::new();
+
+        Self {
+            client,
+            config,
+            base_url,
+        }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &OpenAiCompatConfig {
+        &self.config
+    }
+
+    /// Get base URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Model used when a request does not name one
+    pub fn default_model(&self) -> String {
+        self.config.model.clone()
+    }
+
+    /// Add the Authorization header when an API key is configured
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.config.api_key.as_deref().filter(|key| !key.is_empty()) {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    /// List available models
+    /// GET /models
+    pub async fn fetch_models(&self) -> Result<Vec<String>, String> {
+        let url = format!("{}/models", self.base_url);
+
+        let response = self
+            .authorize(self.client.get(&url))
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP {} error", response.status()));
+        }
+
+        let parsed: ModelsResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        Ok(parsed.data.into_iter().map(|model| model.id).collect())
+    }
+
+    /// Send a single user prompt and return the first choice's content
+    /// POST /chat/completions
+    pub async fn chat_completion(&self, prompt: &str, model: Option<String>) -> Result<String, String> {
+        let url = format!("{}/chat/completions", self.base_url);
+
+        let model = model
+            .filter(|m| !m.is_empty())
+            .unwrap_or_else(|| self.default_model());
+        if model.is_empty() {
+            return Err("No model configured for OpenAI-compatible provider".to_string());
+        }
+
+        let request = ChatCompletionRequest {
+            model,
+            messages: vec![ChatMessage::user(prompt.to_string())],
+            temperature: self.config.temperature,
+            top_p: self.config.top_p,
+            max_tokens: self.config.max_tokens,
+            stream: false,
+        };
+
+        let response = self
+            .authorize(self.client.post(&url))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unable to read error body".to_string());
+            return Err(format!("HTTP error {}: {}", status, error_body));
+        }
+
+        let parsed: ChatCompletionResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        parsed
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or_else(|| "Response contained no choices".to_string())
+    }
+}
+
+/// Check OpenAI-compatible server availability and get available models
+/// Returns a JSON value with availability status and available models
+pub async fn check_openai_compat_status(config: OpenAiCompatConfig) -> Result<serde_json::Value, String> {
+    use tokio::time::{timeout, Duration};
+
+    if config.base_url.trim().is_empty() {
+        return Ok(serde_json::json!({
+            "available": false,
+            "error": "Base URL cannot be empty"
+        }));
+    }
+
+    let client = OpenAiCompatClient::new(config);
+
+    // Listing models doubles as the connection test
+    match timeout(Duration::from_secs(5), client.fetch_models()).await {
+        Ok(Ok(models)) => Ok(serde_json::json!({
+            "available": true,
+            "models_available": models
+        })),
+        Ok(Err(e)) => Ok(serde_json::json!({
+            "available": false,
+            "error": format!("Failed to list models: {}", e)
+        })),
+        Err(_) => Ok(serde_json::json!({
+            "available": false,
+            "error": "Connection timeout: server is not responding"
+        })),
+    }
+}
+
+impl TranslationClient for OpenAiCompatClient {
+    fn call_api(&self, prompt: &str, model: Option<String>) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<String, String>> + Send + '_>> {
+        let prompt = prompt.to_string();
+        Box::pin(async move { self.chat_completion(&prompt, model).await })
+    }
+
+    fn list_models(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<String>, String>> + Send + '_>> {
+        Box::pin(self.fetch_models())
+    }
+
+    fn test_connection(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), String>> + Send + '_>> {
+        Box::pin(async move {
+            self.fetch_models()
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("Connection test failed: {}", e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translation::common::test_server::{serve_once, CapturedRequest};
+
+    /// Stub server answering on an OpenAI-style /v1 root
+    async fn mock_server(
+        status: &'static str,
+        response_body: &'static str,
+    ) -> (String, tokio::task::JoinHandle<CapturedRequest>) {
+        let (root_url, handle) = serve_once(status, response_body).await;
+        (format!("{}/v1", root_url), handle)
+    }
+
+    fn config(base_url: String) -> OpenAiCompatConfig {
+        OpenAiCompatConfig {
+            base_url,
+            api_key: Some("secret-key".to_string()),
+            model: "qwen2.5-7b-instruct".to_string(),
+            temperature: Some(0.2),
+            top_p: Some(0.9),
+            max_tokens: Some(256),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call_api_sends_chat_completion() {
+        let (base_url, server) = mock_server(
+            "200 OK",
+            r#"{"id":"1","object":"chat.completion","choices":[{"index":0,"message":{"role":"assistant","content":"Bonjour"},"finish_reason":"stop"}]}"#,
+        )
+        .await;
+        let client = OpenAiCompatClient::new(config(base_url));
+
+        let result = client.call_api("Translate: こんにちは", None).await.unwrap();
+        assert_eq!(result, "Bonjour");
+
+        let request = server.await.unwrap();
+        assert_eq!(request.request_line, "POST /v1/chat/completions HTTP/1.1");
+        assert!(request.headers.contains("authorization: bearer secret-key"));
+
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["model"], "qwen2.5-7b-instruct");
+        assert_eq!(body["messages"][0]["role"], "user");
+        assert_eq!(body["messages"][0]["content"], "Translate: こんにちは");
+        assert_eq!(body["temperature"], 0.2);
+        assert_eq!(body["top_p"], 0.9);
+        assert_eq!(body["max_tokens"], 256);
+        assert_eq!(body["stream"], false);
+    }
+
+    #[tokio::test]
+    async fn test_call_api_uses_requested_model_without_api_key() {
+        let (base_url, server) = mock_server(
+            "200 OK",
+            r#"{"choices":[{"message":{"role":"assistant","content":"Salut"}}]}"#,
+        )
+        .await;
+        let client = OpenAiCompatClient::new(OpenAiCompatConfig {
+            api_key: None,
+            temperature: None,
+            top_p: None,
+            max_tokens: None,
+            ..config(base_url)
+        });
+
+        let result = client.call_api("Hi", Some("local-model".to_string())).await.unwrap();
+        assert_eq!(result, "Salut");
+
+        let request = server.await.unwrap();
+        assert!(!request.headers.contains("authorization"));
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["model"], "local-model");
+        assert!(body.get("temperature").is_none());
+        assert!(body.get("max_tokens").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_models_reads_model_ids() {
+        let (base_url, server) = mock_server(
+            "200 OK",
+            r#"{"object":"list","data":[{"id":"model-a","object":"model"},{"id":"model-b","object":"model"}]}"#,
+        )
+        .await;
+        let client = OpenAiCompatClient::new(config(base_url));
+
+        let models = client.list_models().await.unwrap();
+        assert_eq!(models, vec!["model-a".to_string(), "model-b".to_string()]);
+
+        let request = server.await.unwrap();
+        assert_eq!(request.request_line, "GET /v1/models HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn test_call_api_reports_http_errors() {
+        let (base_url, _server) = mock_server(
+            "401 Unauthorized",
+            r#"{"error":{"message":"Invalid API key"}}"#,
+        )
+        .await;
+        let client = OpenAiCompatClient::new(config(base_url));
+
+        let error = client.call_api("Hi", None).await.unwrap_err();
+        assert!(error.contains("401"));
+        assert!(error.contains("Invalid API key"));
+    }
+
+    #[tokio::test]
+    async fn test_check_status_reports_available_models() {
+        let (base_url, _server) = mock_server(
+            "200 OK",
+            r#"{"data":[{"id":"model-a"}]}"#,
+        )
+        .await;
+
+        let status = check_openai_compat_status(config(base_url)).await.unwrap();
+        assert_eq!(status["available"], true);
+        assert_eq!(status["models_available"][0], "model-a");
+    }
+}
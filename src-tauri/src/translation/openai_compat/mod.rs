@@ -0,0 +1,18 @@
+// OpenAI-compatible integration module exports
+// This module handles chat completions APIs (llama.cpp server, vLLM, LM Studio, hosted endpoints)
+
+pub mod client;
+pub mod single;
+
+use crate::translation::llm;
+
+// Re-export main types
+pub use client::{check_openai_compat_status, OpenAiCompatClient, OpenAiCompatConfig};
+pub use single::SingleTranslationManager;
+pub use crate::translation::common::types::{
+    SequentialError, SequentialProgress, SequentialStatus, SequentialTranslationRequest, TranslationText,
+    SingleTranslationRequest, SingleTranslationResult, TranslationSuggestion,
+};
+
+/// Sequential translation manager for OpenAI-compatible servers
+pub type SequentialTranslationManager = llm::SequentialTranslationManager<SingleTranslationManager>;
@@ -0,0 +1,129 @@
+// Single translation logic for OpenAI-compatible servers
+// Thin wrapper that delegates to common translation functions
+
+use crate::translation::common::functions::{translate_batch_common, translate_single_common};
+use crate::translation::common::types::{
+    BatchTranslationRequest, BatchTranslationResult, SingleTranslationRequest,
+    SingleTranslationResult, TranslationSuggestion,
+};
+use crate::translation::llm::LlmTranslationClient;
+use crate::translation::openai_compat::OpenAiCompatClient;
+use std::future::Future;
+use std::pin::Pin;
+use tauri::AppHandle;
+
+/// Single translation manager for OpenAI-compatible servers
+#[derive(Clone)]
+pub struct SingleTranslationManager {
+    client: std::sync::Arc<OpenAiCompatClient>,
+}
+
+impl SingleTranslationManager {
+    /// Create new single translation manager
+    pub fn new(client: std::sync::Arc<OpenAiCompatClient>) -> Self {
+        Self { client }
+    }
+
+    /// Translate a single text entry
+    /// Delegates to the common translation function
+    pub async fn translate(
+        &self,
+        app_handle: &AppHandle,
+        mut request: SingleTranslationRequest,
+    ) -> Result<SingleTranslationResult, String> {
+        // Resolve the model here so the result reports the one actually used
+        if request.model.as_deref().map_or(true, str::is_empty) {
+            request.model = Some(self.default_model());
+        }
+
+        translate_single_common(&*self.client, request, app_handle).await
+    }
+
+    /// Translate several short entries with one numbered prompt
+    /// Entries returned as None must be translated again with `translate`
+    pub async fn translate_batch(
+        &self,
+        app_handle: &AppHandle,
+        mut request: BatchTranslationRequest,
+    ) -> Result<BatchTranslationResult, String> {
+        if request.model.as_deref().map_or(true, str::is_empty) {
+            request.model = Some(self.default_model());
+        }
+
+        translate_batch_common(&*self.client, request, app_handle).await
+    }
+
+    /// Get translation suggestions for a text
+    pub async fn get_suggestions(
+        &self,
+        app_handle: Option<&AppHandle>,
+        source_text: &str,
+        context: Option<&str>,
+        max_suggestions: usize,
+    ) -> Result<Vec<TranslationSuggestion>, String> {
+        let mut suggestions = Vec::new();
+
+        let request = SingleTranslationRequest {
+            source_text: source_text.to_string(),
+            source_language: None,
+            target_language: None,
+            context: context.map(|s| s.to_string()),
+            model: None,
+            project_id: None,
+            text_type: None,
+            max_length: None,
+        };
+
+        if let Some(handle) = app_handle {
+            match self.translate(handle, request).await {
+                Ok(result) => {
+                    suggestions.push(TranslationSuggestion {
+                        suggestion: result.translated_text,
+                        confidence: result.confidence.unwrap_or(0.8),
+                        source: "openai_compat".to_string(),
+                    });
+                }
+                Err(e) => {
+                    return Err(format!("Failed to get OpenAI-compatible suggestion: {}", e));
+                }
+            }
+        } else {
+            // No AppHandle - cannot get suggestions without glossary context
+            return Err("AppHandle required for translation suggestions".to_string());
+        }
+
+        suggestions.truncate(max_suggestions);
+        Ok(suggestions)
+    }
+}
+
+impl LlmTranslationClient for SingleTranslationManager {
+    fn provider_name(&self) -> &'static str {
+        "OpenAI"
+    }
+
+    fn session_prefix(&self) -> &'static str {
+        "openai_seq_"
+    }
+
+    /// Model configured on the client
+    fn default_model(&self) -> String {
+        self.client.default_model()
+    }
+
+    fn translate_request<'a>(
+        &'a self,
+        app_handle: &'a AppHandle,
+        request: SingleTranslationRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<SingleTranslationResult, String>> + Send + 'a>> {
+        Box::pin(self.translate(app_handle, request))
+    }
+
+    fn translate_batch_request<'a>(
+        &'a self,
+        app_handle: &'a AppHandle,
+        request: BatchTranslationRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<BatchTranslationResult, String>> + Send + 'a>> {
+        Box::pin(self.translate_batch(app_handle, request))
+    }
+}
@@ -3,9 +3,10 @@
 
 pub mod client;
 pub mod common;
-pub mod sequential;
 pub mod single;
 
+use crate::translation::llm;
+
 // Re-export main types
 pub use client::{check_runpod_status, ModelInfo, RunPodClient, RunPodConfig};
 pub use common::{
@@ -13,9 +14,11 @@ pub use common::{
     get_default_target_language, parse_translation_response, validate_translation_request,
     DEFAULT_MODEL, DEFAULT_SOURCE_LANGUAGE, DEFAULT_TARGET_LANGUAGE, MAX_TEXT_LENGTH,
 };
-pub use sequential::SequentialTranslationManager;
 pub use single::SingleTranslationManager;
 pub use crate::translation::common::types::{
     SequentialError, SequentialProgress, SequentialStatus, SequentialTranslationRequest, TranslationText,
     SingleTranslationRequest, SingleTranslationResult, TranslationSuggestion,
 };
+
+/// Sequential translation manager for RunPod
+pub type SequentialTranslationManager = llm::SequentialTranslationManager<SingleTranslationManager>;
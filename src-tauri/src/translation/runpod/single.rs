@@ -1,9 +1,15 @@
 // Single translation logic for RunPod
 // Thin wrapper that delegates to common translation functions
 
-use crate::translation::common::functions::translate_single_common;
-use crate::translation::common::types::{SingleTranslationRequest, SingleTranslationResult, TranslationSuggestion};
-use crate::translation::runpod::RunPodClient;
+use crate::translation::common::functions::{translate_batch_common, translate_single_common};
+use crate::translation::common::types::{
+    BatchTranslationRequest, BatchTranslationResult, SingleTranslationRequest,
+    SingleTranslationResult, TranslationSuggestion,
+};
+use crate::translation::llm::LlmTranslationClient;
+use crate::translation::runpod::{get_default_model, RunPodClient};
+use std::future::Future;
+use std::pin::Pin;
 use tauri::AppHandle;
 
 /// Single translation manager for RunPod
@@ -31,6 +37,16 @@ impl SingleTranslationManager {
         translate_single_common(&*self.client, request, app_handle).await
     }
 
+    /// Translate several short entries with one numbered prompt
+    /// Entries returned as None must be translated again with `translate`
+    pub async fn translate_batch(
+        &self,
+        app_handle: &AppHandle,
+        request: BatchTranslationRequest,
+    ) -> Result<BatchTranslationResult, String> {
+        translate_batch_common(&*self.client, request, app_handle).await
+    }
+
     /// Get translation suggestions for a text
     pub async fn get_suggestions(
         &self,
@@ -49,6 +65,7 @@ impl SingleTranslationManager {
             model: None,
             project_id: None,
             text_type: None,
+            max_length: None,
         };
 
         if let Some(handle) = app_handle {
@@ -74,3 +91,33 @@ impl SingleTranslationManager {
     }
 
 }
+
+impl LlmTranslationClient for SingleTranslationManager {
+    fn provider_name(&self) -> &'static str {
+        "RunPod"
+    }
+
+    fn session_prefix(&self) -> &'static str {
+        "runpod_seq_"
+    }
+
+    fn default_model(&self) -> String {
+        get_default_model()
+    }
+
+    fn translate_request<'a>(
+        &'a self,
+        app_handle: &'a AppHandle,
+        request: SingleTranslationRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<SingleTranslationResult, String>> + Send + 'a>> {
+        Box::pin(self.translate(app_handle, request))
+    }
+
+    fn translate_batch_request<'a>(
+        &'a self,
+        app_handle: &'a AppHandle,
+        request: BatchTranslationRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<BatchTranslationResult, String>> + Send + 'a>> {
+        Box::pin(self.translate_batch(app_handle, request))
+    }
+}